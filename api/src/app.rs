@@ -8,13 +8,16 @@ use axum::{
 use communities_core::{
     application::{BeepServicesConfig, CommunitiesRepositories},
     create_repositories,
-    domain::{common::CoreError, outbox::ports::OutboxService},
+    domain::{
+        common::CoreError, member_role::ports::MemberRoleService, outbox::ports::OutboxService,
+    },
 };
 use outbox_dispatch::{
     dispatch::{Dispatch, Dispatcher},
     lapin::RabbitClient,
 };
 use sqlx::postgres::PgConnectOptions;
+use std::time::Duration;
 use tower_http::cors::CorsLayer;
 use utoipa::OpenApi;
 use utoipa_axum::router::OpenApiRouter;
@@ -29,7 +32,8 @@ use crate::{
             middleware::auth::{AuthMiddleware, auth_state::AuthState},
         },
     },
-    role_routes, server_invitation_routes, server_member_routes, server_routes,
+    role_routes, server_ban_routes, server_invitation_routes, server_join_request_routes,
+    server_member_routes, server_routes,
 };
 
 #[derive(OpenApi)]
@@ -108,6 +112,8 @@ impl App {
             .merge(server_routes())
             .merge(server_member_routes())
             .merge(server_invitation_routes())
+            .merge(server_ban_routes())
+            .merge(server_join_request_routes())
             .merge(channel_routes())
             .merge(role_routes())
             // Add application routes here
@@ -164,6 +170,8 @@ impl App {
     pub async fn start(mut self) -> Result<(), ApiError> {
         let health_addr = format!("0.0.0.0:{}", self.config.clone().server.health_port);
         let api_addr = format!("0.0.0.0:{}", self.config.clone().server.api_port);
+        let sweep_period =
+            Duration::from_secs(self.config.server.role_expiry_sweep_interval_secs.max(1));
         // Create TCP listeners for both servers
         let health_listener = tokio::net::TcpListener::bind(&health_addr)
             .await
@@ -180,7 +188,8 @@ impl App {
         tokio::try_join!(
             axum::serve(health_listener, self.health_router.clone()),
             axum::serve(api_listener, self.app_router.clone()),
-            self.dispatcher.dispatch()
+            self.dispatcher.dispatch(),
+            sweep_expired_roles(self.state.clone(), sweep_period)
         )
         .expect("Failed to start servers");
 
@@ -192,6 +201,19 @@ impl App {
     }
 }
 
+/// Unassigns expired roles periodically, a failed pass being retried on the next tick
+async fn sweep_expired_roles(state: AppState, period: Duration) -> Result<(), std::io::Error> {
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+        match state.service.unassign_expired_roles().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Unassigned {} expired roles", count),
+            Err(e) => tracing::error!("Failed to unassign expired roles: {}", e),
+        }
+    }
+}
+
 pub trait AppBuilder {
     fn build(config: Config) -> impl Future<Output = Result<App, ApiError>>;
     fn with_state(self, state: AppState) -> impl Future<Output = Result<App, ApiError>>;
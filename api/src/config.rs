@@ -180,6 +180,14 @@ pub struct ServerConfig {
         default_value = "8081"
     )]
     pub health_port: u16,
+
+    /// Seconds between two passes unassigning expired roles
+    #[arg(
+        long = "role-expiry-sweep-interval",
+        env = "ROLE_EXPIRY_SWEEP_INTERVAL_SECS",
+        default_value = "60"
+    )]
+    pub role_expiry_sweep_interval_secs: u64,
 }
 
 #[derive(Clone, Parser, Debug, Default)]
@@ -8,11 +8,13 @@ use communities_core::domain::{
     channel::{
         entities::{
             Channel, ChannelId, CreatePrivateChannelRequest, CreateServerChannelRequest,
-            UpdateChannelRequest,
+            OverwriteTarget, PermissionOverwrite, PermissionOverwriteRequest, UpdateChannelRequest,
         },
         ports::ChannelService,
     },
+    role::entities::{Permissions, RoleId},
     server::{entities::ServerId, ports::ServerService},
+    server_member::MemberId,
 };
 use uuid::Uuid;
 
@@ -199,3 +201,220 @@ pub async fn delete_channel(
     state.service.delete_channel(channel_id).await?;
     Ok(Response::deleted(()))
 }
+
+/// Channels holding overwrites belong to a server the user must manage channels of
+async fn managed_channel_server(
+    state: &AppState,
+    user_identity: &UserIdentity,
+    channel_id: ChannelId,
+) -> Result<(), ApiError> {
+    let channel = state.service.get_channel_by_id(channel_id).await?;
+    if let Some(server_id) = channel.server_id {
+        user_identity
+            .can_manage_channels_in_server(server_id)
+            .await?;
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/channels/{id}/permissions",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel ID")
+    ),
+    responses(
+        (status = 200, description = "Permission overwrites of the channel", body = Vec<PermissionOverwrite>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Not authorized to manage this channel"),
+        (status = 404, description = "Channel not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn list_permission_overwrites(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<Vec<PermissionOverwrite>>, ApiError> {
+    let channel_id = ChannelId::from(id);
+    managed_channel_server(&state, &user_identity, channel_id).await?;
+
+    let overwrites = state.service.list_permission_overwrites(channel_id).await?;
+    Ok(Response::ok(overwrites))
+}
+
+#[utoipa::path(
+    get,
+    path = "/channels/{id}/permissions/@me",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel ID")
+    ),
+    responses(
+        (status = 200, description = "Permissions of the current user in the channel", body = Permissions),
+        (status = 400, description = "Bad request - Private channel"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Channel not found or not a member of its server"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_my_channel_permissions(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<Permissions>, ApiError> {
+    let permissions = state
+        .service
+        .get_channel_permissions(ChannelId::from(id), user_identity.user_id)
+        .await?;
+    Ok(Response::ok(permissions))
+}
+
+#[utoipa::path(
+    put,
+    path = "/channels/{id}/permissions/roles/{role_id}",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel ID"),
+        ("role_id" = String, Path, description = "Role ID")
+    ),
+    request_body = PermissionOverwriteRequest,
+    responses(
+        (status = 200, description = "Overwrite of the role saved", body = PermissionOverwrite),
+        (status = 400, description = "Bad request - Invalid permissions or role of another server"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Role not below your highest role or permissions not held"),
+        (status = 404, description = "Channel or role not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn upsert_role_overwrite(
+    Path((id, role_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<PermissionOverwriteRequest>,
+) -> Result<Response<PermissionOverwrite>, ApiError> {
+    let channel_id = ChannelId::from(id);
+    managed_channel_server(&state, &user_identity, channel_id).await?;
+
+    let overwrite = state
+        .service
+        .upsert_permission_overwrite(
+            channel_id,
+            OverwriteTarget::Role(RoleId(role_id)),
+            request,
+            user_identity.user_id,
+        )
+        .await?;
+    Ok(Response::ok(overwrite))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/channels/{id}/permissions/roles/{role_id}",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel ID"),
+        ("role_id" = String, Path, description = "Role ID")
+    ),
+    responses(
+        (status = 200, description = "Overwrite of the role removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Role not below your highest role"),
+        (status = 404, description = "Channel, role or overwrite not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_role_overwrite(
+    Path((id, role_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<()>, ApiError> {
+    let channel_id = ChannelId::from(id);
+    managed_channel_server(&state, &user_identity, channel_id).await?;
+
+    state
+        .service
+        .delete_permission_overwrite(
+            channel_id,
+            OverwriteTarget::Role(RoleId(role_id)),
+            user_identity.user_id,
+        )
+        .await?;
+    Ok(Response::deleted(()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/channels/{id}/permissions/members/{member_id}",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel ID"),
+        ("member_id" = String, Path, description = "Member ID")
+    ),
+    request_body = PermissionOverwriteRequest,
+    responses(
+        (status = 200, description = "Overwrite of the member saved", body = PermissionOverwrite),
+        (status = 400, description = "Bad request - Invalid permissions or member of another server"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Permissions not held"),
+        (status = 404, description = "Channel or member not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn upsert_member_overwrite(
+    Path((id, member_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<PermissionOverwriteRequest>,
+) -> Result<Response<PermissionOverwrite>, ApiError> {
+    let channel_id = ChannelId::from(id);
+    managed_channel_server(&state, &user_identity, channel_id).await?;
+
+    let overwrite = state
+        .service
+        .upsert_permission_overwrite(
+            channel_id,
+            OverwriteTarget::Member(MemberId(member_id)),
+            request,
+            user_identity.user_id,
+        )
+        .await?;
+    Ok(Response::ok(overwrite))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/channels/{id}/permissions/members/{member_id}",
+    tag = "channels",
+    params(
+        ("id" = String, Path, description = "Channel ID"),
+        ("member_id" = String, Path, description = "Member ID")
+    ),
+    responses(
+        (status = 200, description = "Overwrite of the member removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Not authorized to manage this channel"),
+        (status = 404, description = "Channel, member or overwrite not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn delete_member_overwrite(
+    Path((id, member_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<()>, ApiError> {
+    let channel_id = ChannelId::from(id);
+    managed_channel_server(&state, &user_identity, channel_id).await?;
+
+    state
+        .service
+        .delete_permission_overwrite(
+            channel_id,
+            OverwriteTarget::Member(MemberId(member_id)),
+            user_identity.user_id,
+        )
+        .await?;
+    Ok(Response::deleted(()))
+}
@@ -2,9 +2,13 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 use crate::{
     http::channels::handlers::{
-        __path_create_server_channel, __path_delete_channel, __path_get_channel,
-        __path_list_channels, __path_update_channel, create_server_channel, delete_channel,
-        get_channel, list_channels, update_channel,
+        __path_create_server_channel, __path_delete_channel, __path_delete_member_overwrite,
+        __path_delete_role_overwrite, __path_get_channel, __path_get_my_channel_permissions,
+        __path_list_channels, __path_list_permission_overwrites, __path_update_channel,
+        __path_upsert_member_overwrite, __path_upsert_role_overwrite, create_server_channel,
+        delete_channel, delete_member_overwrite, delete_role_overwrite, get_channel,
+        get_my_channel_permissions, list_channels, list_permission_overwrites, update_channel,
+        upsert_member_overwrite, upsert_role_overwrite,
     },
     http::server::AppState,
 };
@@ -17,4 +21,8 @@ pub fn channel_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(get_channel))
         .routes(routes!(update_channel))
         .routes(routes!(delete_channel))
+        .routes(routes!(list_permission_overwrites))
+        .routes(routes!(get_my_channel_permissions))
+        .routes(routes!(upsert_role_overwrite, delete_role_overwrite))
+        .routes(routes!(upsert_member_overwrite, delete_member_overwrite))
 }
@@ -1,5 +1,5 @@
 use communities_core::domain::{
-    common::GetPaginated,
+    common::{GetCursor, GetPaginated},
     friend::{
         entities::{
             AcceptFriendRequestInput, CreateFriendRequestInput, DeclineFriendRequestInput,
@@ -26,10 +26,12 @@ use crate::http::server::{
     path = "/friends",
     tag = "friends",
     params(
-        GetPaginated
+        GetPaginated,
+        GetCursor
     ),
     responses(
         (status = 200, description = "List of friends retrieved successfully", body = PaginatedResponse<Friend>),
+        (status = 400, description = "Invalid cursor"),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     )
@@ -38,15 +40,25 @@ pub async fn get_friends(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     Query(pagination): Query<GetPaginated>,
+    Query(cursor): Query<GetCursor>,
 ) -> Result<Response<PaginatedResponse<Friend>>, ApiError> {
     let user_id = UserId::from(user_identity.user_id);
 
+    if let Some(after) = cursor.decode()? {
+        let page = state
+            .service
+            .get_friends_after(after, pagination.limit, &user_id)
+            .await?;
+        return Ok(Response::ok(PaginatedResponse::from_cursor_page(page)));
+    }
+
     let (friends, total) = state.service.get_friends(&pagination, &user_id).await?;
 
     let response = PaginatedResponse {
         data: friends,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
@@ -112,6 +124,7 @@ pub async fn get_friend_requests(
         data: friends,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
@@ -145,6 +158,7 @@ pub async fn get_friend_invitations(
         data: friends,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
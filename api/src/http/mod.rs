@@ -3,6 +3,8 @@ pub mod friend;
 pub mod health;
 pub mod role;
 pub mod server;
+pub mod server_bans;
 pub mod server_invitations;
+pub mod server_join_requests;
 pub mod server_members;
 pub mod servers;
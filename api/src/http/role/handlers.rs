@@ -4,17 +4,28 @@ use axum::{
 };
 use communities_core::domain::{
     common::GetPaginated,
-    member_role::{entities::MemberRole, ports::MemberRoleService},
+    friend::entities::UserId,
+    member_role::{
+        entities::{
+            AssignRoleRequest, AssignedRole, ListRoleMembersQuery, MemberRole, MemberRolesChange,
+            SetMemberRolesRequest,
+        },
+        ports::MemberRoleService,
+    },
     role::{
         self,
         entities::{
-            CreateRoleInput, CreateRoleRequest, Permissions, Role, RoleId, UpdateRoleInput,
+            CreateRoleInput, CreateRoleRequest, EffectivePermissions, PERMISSIONS,
+            PermissionDefinition, Permissions, ReorderRolesRequest, Role, RoleId, UpdateRoleInput,
             UpdateRoleRequest,
         },
         ports::RoleService,
     },
     server::entities::ServerId,
-    server_member::{MemberId, ServerMember},
+    server_member::{
+        MemberId,
+        entities::{BulkMemberResult, BulkMembersRequest, ListedMember},
+    },
 };
 use uuid::Uuid;
 
@@ -37,6 +48,7 @@ use crate::{
         (status = 201, description = "", body = Role),
         (status = 400, description = "Bad request - Invalid role"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - No role above the new one or permissions not held"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -50,7 +62,7 @@ pub async fn create_role(
         .can_manage_role_in_servers(ServerId(server_id))
         .await?;
     let permissions =
-        Permissions::try_from(request.permissions).map_err(|e| ApiError::BadRequest {
+        Permissions::try_from(request.permissions.bits()).map_err(|e| ApiError::BadRequest {
             msg: e.to_string(),
             error_code: None,
         })?;
@@ -58,8 +70,12 @@ pub async fn create_role(
         server_id,
         name: request.name,
         permissions,
+        appearance: request.appearance,
     };
-    let role = state.service.create_role(create_role).await?;
+    let role = state
+        .service
+        .create_role(create_role, user_identity.user_id)
+        .await?;
     Ok(Response::created(role))
 }
 
@@ -128,6 +144,7 @@ pub async fn list_roles_by_server(
         data,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
     Ok(paginated.into())
 }
@@ -140,7 +157,7 @@ pub async fn list_roles_by_server(
          ("server_id" = String, Path, description = "Server ID")
      ),
      responses(
-         (status = 200, description = "User roles retrieved successfully", body = Vec<Role>),
+         (status = 200, description = "User roles retrieved successfully, with the expiry of each assignment", body = Vec<AssignedRole>),
          (status = 401, description = "Unauthorized"),
          (status = 403, description = "Forbidden - Not a member of the server"),
          (status = 404, description = "Server not found"),
@@ -151,7 +168,7 @@ pub async fn get_user_roles_in_server(
     Path(server_id): Path<Uuid>,
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
-) -> Result<Response<Vec<Role>>, ApiError> {
+) -> Result<Response<Vec<AssignedRole>>, ApiError> {
     let roles = state
         .service
         .list_roles_by_user_and_server(user_identity.user_id, ServerId(server_id))
@@ -160,6 +177,118 @@ pub async fn get_user_roles_in_server(
     Ok(Response::ok(roles))
 }
 
+#[utoipa::path(
+     get,
+     path = "/permissions",
+     tag = "roles",
+     responses(
+         (status = 200, description = "Every permission bit with its name and description", body = Vec<PermissionDefinition>),
+         (status = 401, description = "Unauthorized"),
+         (status = 500, description = "Internal server error")
+     )
+ )]
+pub async fn list_permissions() -> Result<Response<Vec<PermissionDefinition>>, ApiError> {
+    Ok(Response::ok(PERMISSIONS.to_vec()))
+}
+
+#[utoipa::path(
+     get,
+     path = "/servers/{server_id}/roles/@me/permissions",
+     tag = "roles",
+     params(
+         ("server_id" = String, Path, description = "Server ID")
+     ),
+     responses(
+         (status = 200, description = "Effective permissions of the user", body = EffectivePermissions),
+         (status = 401, description = "Unauthorized"),
+         (status = 403, description = "Forbidden - Not a member of the server"),
+         (status = 404, description = "Server not found"),
+         (status = 500, description = "Internal server error")
+     )
+ )]
+pub async fn get_my_permissions(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<EffectivePermissions>, ApiError> {
+    user_identity.can_view_server(ServerId(server_id)).await?;
+    let permissions = state
+        .service
+        .get_effective_permissions(ServerId(server_id), user_identity.user_id)
+        .await?;
+    Ok(Response::ok(permissions))
+}
+
+#[utoipa::path(
+     get,
+     path = "/servers/{server_id}/members/{user_id}/permissions",
+     tag = "roles",
+     params(
+         ("server_id" = String, Path, description = "Server ID"),
+         ("user_id" = String, Path, description = "User ID")
+     ),
+     responses(
+         (status = 200, description = "Effective permissions of the member", body = EffectivePermissions),
+         (status = 401, description = "Unauthorized"),
+         (status = 403, description = "Forbidden - Cannot manage the roles of the server"),
+         (status = 404, description = "Server or member not found"),
+         (status = 500, description = "Internal server error")
+     )
+ )]
+pub async fn get_member_permissions(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<EffectivePermissions>, ApiError> {
+    let server_id = ServerId(server_id);
+    let user_id = UserId(user_id);
+
+    // Members see their own permissions, those managing roles see everyone's
+    if user_id == user_identity.user_id {
+        user_identity.can_view_server(server_id).await?;
+    } else {
+        user_identity.can_manage_role_in_servers(server_id).await?;
+    }
+    let permissions = state
+        .service
+        .get_effective_permissions(server_id, user_id)
+        .await?;
+    Ok(Response::ok(permissions))
+}
+
+#[utoipa::path(
+    put,
+    path = "/servers/{server_id}/roles/positions",
+    tag = "roles",
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    request_body = ReorderRolesRequest,
+    responses(
+        (status = 200, description = "Roles of the server, highest first", body = Vec<Role>),
+        (status = 400, description = "Bad request - The order does not list every role once"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Moving a role not below your highest role"),
+        (status = 404, description = "Server not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn reorder_roles(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<ReorderRolesRequest>,
+) -> Result<Response<Vec<Role>>, ApiError> {
+    user_identity
+        .can_manage_role_in_servers(ServerId(server_id))
+        .await?;
+    let roles = state
+        .service
+        .reorder_roles(ServerId(server_id), request, user_identity.user_id)
+        .await?;
+    Ok(Response::ok(roles))
+}
+
 #[utoipa::path(
     put,
     path = "/roles/{role_id}",
@@ -170,9 +299,9 @@ pub async fn get_user_roles_in_server(
     request_body = UpdateRoleInput,
     responses(
         (status = 200, description = "Role updated successfully", body = Role),
-        (status = 400, description = "Bad request - Invalid permissions"),
+        (status = 400, description = "Bad request - Invalid permissions or color, or default role renamed or restyled"),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - Not the server owner"),
+        (status = 403, description = "Forbidden - Role not below your highest role or permissions not held"),
         (status = 404, description = "Server not found"),
         (status = 500, description = "Internal server error")
     )
@@ -190,11 +319,12 @@ pub async fn update_role(
     let update_role = UpdateRoleInput {
         id: RoleId(role_id),
         name: request.name,
-        permissions: request.permissions,
+        permissions: request.permissions.map(|permissions| permissions.bits()),
+        appearance: request.appearance,
     };
     let role = state
         .service
-        .update_role(update_role)
+        .update_role(update_role, user_identity.user_id)
         .await
         .map_err(Into::<ApiError>::into)?;
     Ok(Response::ok(role))
@@ -209,6 +339,7 @@ pub async fn update_role(
     ),
     responses(
         (status = 200, description = "Role deleted successfully"),
+        (status = 400, description = "Default role cannot be deleted"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - Not the server owner"),
         (status = 404, description = "Server or role not found"),
@@ -226,7 +357,7 @@ pub async fn delete_role(
         .await?;
     state
         .service
-        .delete_role(&RoleId(role_id))
+        .delete_role(&RoleId(role_id), user_identity.user_id)
         .await
         .map_err(Into::<ApiError>::into)?;
     Ok(Response::deleted(()))
@@ -236,12 +367,14 @@ pub async fn delete_role(
      post,
      path = "/roles/{role_id}/members/{member_id}",
      tag = "role",
+     request_body(content = Option<AssignRoleRequest>, description = "Optional expiry of the assignment"),
      params(
          ("role_id" = String, Path, description = "Role ID"),
          ("member_id" = String, Path, description = "Member ID")
      ),
      responses(
          (status = 201, description = "Role assigned successfully to member"),
+         (status = 400, description = "Expiry date in the past or default role"),
          (status = 401, description = "Unauthorized"),
          (status = 403, description = "Forbidden - Not the server owner"),
          (status = 404, description = "Server or role not found"),
@@ -252,14 +385,21 @@ pub async fn assign_role(
     Path((role_id, member_id)): Path<(Uuid, Uuid)>,
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
+    request: Option<Json<AssignRoleRequest>>,
 ) -> Result<Response<MemberRole>, ApiError> {
     let role = state.service.get_role(&RoleId(role_id)).await?;
     user_identity
         .can_manage_role_in_servers(role.server_id)
         .await?;
+    let request = request.map(|Json(request)| request).unwrap_or_default();
     let member_role = state
         .service
-        .assign_member_to_role(RoleId(role_id), MemberId(member_id))
+        .assign_member_to_role(
+            RoleId(role_id),
+            MemberId(member_id),
+            request,
+            user_identity.user_id,
+        )
         .await
         .map_err(Into::<ApiError>::into)?;
     Ok(Response::created(member_role))
@@ -275,6 +415,7 @@ pub async fn assign_role(
      ),
      responses(
          (status = 201, description = "Role unassigned successfully from member"),
+         (status = 400, description = "Default role cannot be unassigned"),
          (status = 401, description = "Unauthorized"),
          (status = 403, description = "Forbidden - Not the server owner"),
          (status = 404, description = "Server or role not found"),
@@ -292,21 +433,123 @@ pub async fn unassign_role(
         .await?;
     state
         .service
-        .unassign_member_from_role(RoleId(role_id), MemberId(member_id))
+        .unassign_member_from_role(RoleId(role_id), MemberId(member_id), user_identity.user_id)
         .await
         .map_err(Into::<ApiError>::into)?;
     Ok(Response::deleted(()))
 }
 
+#[utoipa::path(
+     put,
+     path = "/servers/{server_id}/members/{user_id}/roles",
+     tag = "role",
+     request_body = SetMemberRolesRequest,
+     params(
+         ("server_id" = String, Path, description = "Server ID"),
+         ("user_id" = String, Path, description = "User ID")
+     ),
+     responses(
+         (status = 200, description = "Roles assigned to and removed from the member", body = MemberRolesChange),
+         (status = 401, description = "Unauthorized"),
+         (status = 403, description = "Forbidden - A role ranks above the actor"),
+         (status = 404, description = "Member or role not found"),
+         (status = 500, description = "Internal server error")
+     )
+ )]
+pub async fn set_member_roles(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<SetMemberRolesRequest>,
+) -> Result<Response<MemberRolesChange>, ApiError> {
+    let server_id = ServerId(server_id);
+    user_identity.can_manage_role_in_servers(server_id).await?;
+    let change = state
+        .service
+        .set_member_roles(server_id, UserId(user_id), request, user_identity.user_id)
+        .await?;
+    Ok(Response::ok(change))
+}
+
+#[utoipa::path(
+     post,
+     path = "/roles/{role_id}/members/bulk/assign",
+     tag = "role",
+     request_body = BulkMembersRequest,
+     params(
+         ("role_id" = String, Path, description = "Role ID")
+     ),
+     responses(
+         (status = 200, description = "Outcome of the assignment for each user", body = Vec<BulkMemberResult>),
+         (status = 400, description = "No user, too many users or default role"),
+         (status = 401, description = "Unauthorized"),
+         (status = 403, description = "Forbidden - Not the server owner"),
+         (status = 404, description = "Role not found"),
+         (status = 500, description = "Internal server error")
+     )
+ )]
+pub async fn bulk_assign_role(
+    Path(role_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<BulkMembersRequest>,
+) -> Result<Response<Vec<BulkMemberResult>>, ApiError> {
+    let role = state.service.get_role(&RoleId(role_id)).await?;
+    user_identity
+        .can_manage_role_in_servers(role.server_id)
+        .await?;
+    let results = state
+        .service
+        .bulk_assign_role(RoleId(role_id), request, user_identity.user_id)
+        .await?;
+    Ok(Response::ok(results))
+}
+
+#[utoipa::path(
+     post,
+     path = "/roles/{role_id}/members/bulk/unassign",
+     tag = "role",
+     request_body = BulkMembersRequest,
+     params(
+         ("role_id" = String, Path, description = "Role ID")
+     ),
+     responses(
+         (status = 200, description = "Outcome of the unassignment for each user", body = Vec<BulkMemberResult>),
+         (status = 400, description = "No user, too many users or default role"),
+         (status = 401, description = "Unauthorized"),
+         (status = 403, description = "Forbidden - Not the server owner"),
+         (status = 404, description = "Role not found"),
+         (status = 500, description = "Internal server error")
+     )
+ )]
+pub async fn bulk_unassign_role(
+    Path(role_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<BulkMembersRequest>,
+) -> Result<Response<Vec<BulkMemberResult>>, ApiError> {
+    let role = state.service.get_role(&RoleId(role_id)).await?;
+    user_identity
+        .can_manage_role_in_servers(role.server_id)
+        .await?;
+    let results = state
+        .service
+        .bulk_unassign_role(RoleId(role_id), request, user_identity.user_id)
+        .await?;
+    Ok(Response::ok(results))
+}
+
 #[utoipa::path(
      get,
      path = "/roles/{role_id}/members",
      tag = "role",
      params(
-         ("role_id" = String, Path, description = "Role ID")
+         ("role_id" = String, Path, description = "Role ID"),
+         GetPaginated,
+         ListRoleMembersQuery
      ),
      responses(
-         (status = 200, description = "Members retrieved successfully", body = PaginatedResponse<Vec<ServerMember>>),
+         (status = 200, description = "Members retrieved successfully", body = PaginatedResponse<ListedMember>),
          (status = 401, description = "Unauthorized"),
          (status = 403, description = "Forbidden - Not the server owner"),
          (status = 404, description = "Role not found"),
@@ -318,20 +561,22 @@ pub async fn list_members_by_role(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     Query(pagination): Query<GetPaginated>,
-) -> Result<Response<PaginatedResponse<ServerMember>>, ApiError> {
+    Query(query): Query<ListRoleMembersQuery>,
+) -> Result<Response<PaginatedResponse<ListedMember>>, ApiError> {
     let role = state.service.get_role(&RoleId(role_id)).await?;
     user_identity
         .can_manage_role_in_servers(role.server_id)
         .await?;
     let (data, total) = state
         .service
-        .list_members_by_role(&RoleId(role_id), &pagination)
+        .list_members_by_role(&RoleId(role_id), &pagination, query)
         .await
         .map_err(Into::<ApiError>::into)?;
-    let paginated: PaginatedResponse<ServerMember> = PaginatedResponse {
+    let paginated: PaginatedResponse<ListedMember> = PaginatedResponse {
         data,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
     Ok(paginated.into())
 }
@@ -3,11 +3,15 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use crate::{
     AppState,
     http::role::handlers::{
-        __path_assign_role, __path_create_role, __path_delete_role, __path_get_role,
-        __path_get_user_roles_in_server, __path_list_members_by_role,
-        __path_list_roles_by_server, __path_unassign_role, __path_update_role, assign_role,
-        create_role, delete_role, get_role, get_user_roles_in_server, list_members_by_role,
-        list_roles_by_server, unassign_role, update_role,
+        __path_assign_role, __path_bulk_assign_role, __path_bulk_unassign_role, __path_create_role,
+        __path_delete_role, __path_get_member_permissions, __path_get_my_permissions,
+        __path_get_role, __path_get_user_roles_in_server, __path_list_members_by_role,
+        __path_list_permissions, __path_list_roles_by_server, __path_reorder_roles,
+        __path_set_member_roles, __path_unassign_role, __path_update_role, assign_role,
+        bulk_assign_role, bulk_unassign_role, create_role, delete_role, get_member_permissions,
+        get_my_permissions, get_role, get_user_roles_in_server, list_members_by_role,
+        list_permissions, list_roles_by_server, reorder_roles, set_member_roles, unassign_role,
+        update_role,
     },
 };
 
@@ -18,8 +22,15 @@ pub fn role_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(update_role))
         .routes(routes!(list_roles_by_server))
         .routes(routes!(get_user_roles_in_server))
+        .routes(routes!(list_permissions))
+        .routes(routes!(get_my_permissions))
+        .routes(routes!(get_member_permissions))
+        .routes(routes!(reorder_roles))
         .routes(routes!(delete_role))
         .routes(routes!(assign_role))
         .routes(routes!(unassign_role))
+        .routes(routes!(bulk_assign_role))
+        .routes(routes!(bulk_unassign_role))
+        .routes(routes!(set_member_roles))
         .routes(routes!(list_members_by_role))
 }
@@ -111,6 +111,100 @@ impl From<CoreError> for ApiError {
                 error_code: None,
             },
             CoreError::Forbidden => ApiError::Forbidden,
+            CoreError::RoleHierarchy | CoreError::PermissionEscalation => ApiError::Forbidden,
+            CoreError::DefaultRoleDeletion => ApiError::BadRequest {
+                msg: "The default role cannot be deleted".to_string(),
+                error_code: Some("DEFAULT_ROLE_DELETION".to_string()),
+            },
+            CoreError::DefaultRoleAssignment => ApiError::BadRequest {
+                msg: "The default role cannot be assigned or unassigned".to_string(),
+                error_code: Some("DEFAULT_ROLE_ASSIGNMENT".to_string()),
+            },
+            CoreError::DefaultRoleUpdate => ApiError::BadRequest {
+                msg: "Only the permissions of the default role can be edited".to_string(),
+                error_code: Some("DEFAULT_ROLE_UPDATE".to_string()),
+            },
+            CoreError::InvalidRoleOrder { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_ROLE_ORDER".to_string()),
+            },
+            CoreError::InvalidRoleColor { color } => ApiError::BadRequest {
+                msg: format!("Invalid role color {color}: expected an RGB value"),
+                error_code: Some("INVALID_ROLE_COLOR".to_string()),
+            },
+            CoreError::InvalidRoleExpiry { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_ROLE_EXPIRY".to_string()),
+            },
+            CoreError::InvalidPermissionOverwrite { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_PERMISSION_OVERWRITE".to_string()),
+            },
+            CoreError::PermissionOverwriteNotFound { .. } => {
+                ApiError::NotFound { error_code: None }
+            }
+            CoreError::ServerTemplateNotFound { .. } => ApiError::NotFound { error_code: None },
+            CoreError::InvalidServerTemplate { msg } => ApiError::BadRequest {
+                msg,
+                error_code: None,
+            },
+            CoreError::InvalidServerExport { msg } => ApiError::BadRequest {
+                msg,
+                error_code: None,
+            },
+            CoreError::InvalidVanitySlug { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_VANITY_SLUG".to_string()),
+            },
+            CoreError::VanitySlugAlreadyTaken { .. } => ApiError::Conflict {
+                error_code: "VANITY_SLUG_TAKEN".to_string(),
+            },
+            CoreError::VanitySlugNotFound { .. } | CoreError::ServerVanityNotFound { .. } => {
+                ApiError::NotFound { error_code: None }
+            }
+            CoreError::InvalidServerSettings { msg } => ApiError::BadRequest {
+                msg,
+                error_code: None,
+            },
+            CoreError::InvalidServerBan { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_SERVER_BAN".to_string()),
+            },
+            CoreError::ServerBanNotFound { .. } => ApiError::NotFound { error_code: None },
+            CoreError::UserBanned { .. } => ApiError::Conflict {
+                error_code: "USER_BANNED".to_string(),
+            },
+            CoreError::InvalidMemberProfile { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_MEMBER_PROFILE".to_string()),
+            },
+            CoreError::InvalidMemberTimeout { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_MEMBER_TIMEOUT".to_string()),
+            },
+            CoreError::InvalidMemberRemoval { msg } => ApiError::BadRequest {
+                msg,
+                error_code: None,
+            },
+            CoreError::InvalidCursor => ApiError::BadRequest {
+                msg: "Invalid pagination cursor".to_string(),
+                error_code: Some("INVALID_CURSOR".to_string()),
+            },
+            CoreError::InvalidBulkRequest { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_BULK_REQUEST".to_string()),
+            },
+            CoreError::InvalidJoinRequest { msg } => ApiError::BadRequest {
+                msg,
+                error_code: Some("INVALID_JOIN_REQUEST".to_string()),
+            },
+            CoreError::JoinRequestNotFound => ApiError::NotFound { error_code: None },
+            CoreError::JoinRequestAlreadyPending { .. } => ApiError::Conflict {
+                error_code: "JOIN_REQUEST_ALREADY_PENDING".to_string(),
+            },
+            CoreError::JoinRequestAlreadyReviewed { .. } => ApiError::Conflict {
+                error_code: "JOIN_REQUEST_ALREADY_REVIEWED".to_string(),
+            },
             _ => ApiError::InternalServerError,
         }
     }
@@ -146,7 +240,7 @@ impl From<UserError> for ApiError {
             UserError::UserNotFound => ApiError::NotFound {
                 error_code: error.error_code().to_string().into(),
             },
-            _ => ApiError::InternalServerError,
+            UserError::ServiceUnavailable { msg } => ApiError::ServiceUnavailable { msg },
         }
     }
 }
@@ -34,6 +34,8 @@ impl From<CommunitiesRepositories> for AppState {
             repositories.server_invitation_repository,
             repositories.authorization_repository,
             repositories.server_pictures_repository,
+            repositories.server_ban_repository,
+            repositories.server_join_request_repository,
         );
         AppState { service }
     }
@@ -4,8 +4,14 @@ use chrono::Utc;
 use communities_core::{
     CommunitiesService,
     domain::{
-        authorization::ports::AuthorizationService, common::CoreError, friend::entities::UserId,
-        server::entities::ServerId, server_member::MemberService,
+        authorization::ports::AuthorizationService,
+        common::CoreError,
+        friend::entities::UserId,
+        server::{
+            entities::{ServerId, ServerTemplate, ServerVisibility},
+            ports::ServerService,
+        },
+        server_member::MemberService,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -58,6 +64,25 @@ impl UserIdentity {
         Ok(true)
     }
 
+    /// A template exposes the roles and channels of its source server: its creator, members
+    /// of the source server and anyone for a template of a public server can read it
+    pub async fn can_view_template(&self, template: &ServerTemplate) -> Result<bool, CoreError> {
+        if template.creator_id == self.user_id {
+            return Ok(true);
+        }
+        match template.source_server_id {
+            Some(server_id) => {
+                let server = self.service.get_server(&server_id).await;
+                if matches!(&server, Ok(server) if server.visibility == ServerVisibility::Public) {
+                    return Ok(true);
+                }
+                self.can_view_server(server_id).await
+            }
+            None if template.snapshot.settings.visibility == ServerVisibility::Public => Ok(true),
+            None => Err(CoreError::Forbidden),
+        }
+    }
+
     pub async fn can_manage_role_in_servers(&self, server_id: ServerId) -> Result<bool, CoreError> {
         self.service
             .can_manage_roles_in_server(self.user_id, server_id)
@@ -70,6 +95,56 @@ impl UserIdentity {
             .await
     }
 
+    /// Leaving only requires being a member of the server
+    pub async fn can_leave_server(&self, server_id: ServerId) -> Result<bool, CoreError> {
+        self.can_view_server(server_id).await
+    }
+
+    pub async fn can_kick_members(&self, server_id: ServerId) -> Result<bool, CoreError> {
+        if !self
+            .service
+            .can_kick_members(self.user_id, server_id)
+            .await?
+        {
+            return Err(CoreError::Forbidden);
+        }
+        Ok(true)
+    }
+
+    pub async fn can_ban_members(&self, server_id: ServerId) -> Result<bool, CoreError> {
+        if !self
+            .service
+            .can_ban_members(self.user_id, server_id)
+            .await?
+        {
+            return Err(CoreError::Forbidden);
+        }
+        Ok(true)
+    }
+
+    pub async fn can_moderate_members(&self, server_id: ServerId) -> Result<bool, CoreError> {
+        if !self
+            .service
+            .can_moderate_members(self.user_id, server_id)
+            .await?
+        {
+            return Err(CoreError::Forbidden);
+        }
+        Ok(true)
+    }
+
+    /// Pruning removes members in bulk, so only the owner and administrators can do it
+    pub async fn can_prune_members(&self, server_id: ServerId) -> Result<bool, CoreError> {
+        if !self
+            .service
+            .is_server_administrator(self.user_id, server_id)
+            .await?
+        {
+            return Err(CoreError::Forbidden);
+        }
+        Ok(true)
+    }
+
     pub async fn can_update_or_change_nickname(
         &self,
         server_id: ServerId,
@@ -86,7 +161,8 @@ impl UserIdentity {
                 .service
                 .can_change_nickname(self.user_id, server_id)
                 .await
-                .unwrap_or(false);
+                .unwrap_or(false)
+                && self.service.can_change_own_nickname(server_id).await?;
         }
 
         if !can_do_operation {
@@ -3,7 +3,7 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response as AxumResponse},
 };
-use communities_core::domain::common::TotalPaginatedElements;
+use communities_core::domain::common::{CursorPage, TotalPaginatedElements};
 use serde::Serialize;
 use utoipa::ToSchema;
 
@@ -62,7 +62,22 @@ where
 pub struct PaginatedResponse<T: Serialize> {
     pub data: Vec<T>,
     pub total: TotalPaginatedElements,
+    /// Requested page, 0 when listing by cursor
     pub page: u32,
+    /// Cursor of the next page when listing by cursor, absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T: Serialize> PaginatedResponse<T> {
+    pub fn from_cursor_page((data, total, next_cursor): CursorPage<T>) -> Self {
+        Self {
+            data,
+            total,
+            page: 0,
+            next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        }
+    }
 }
 
 impl<T: Serialize> Into<Response<PaginatedResponse<T>>> for PaginatedResponse<T> {
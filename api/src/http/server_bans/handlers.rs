@@ -0,0 +1,197 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use communities_core::domain::{
+    common::GetPaginated,
+    friend::entities::UserId,
+    server::entities::ServerId,
+    server_ban::{
+        entities::{BanMemberRequest, BulkBanRequest, ServerBan},
+        ports::ServerBanService,
+    },
+    server_member::entities::BulkMemberResult,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::http::server::{
+    ApiError, AppState, Response, api_error::ErrorBody, middleware::auth::entities::UserIdentity,
+    response::PaginatedResponse,
+};
+
+#[utoipa::path(
+    put,
+    path = "/servers/{server_id}/bans/{user_id}",
+    tag = "server_bans",
+    request_body = BanMemberRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("user_id" = String, Path, description = "ID of the user to ban")
+    ),
+    responses(
+        (status = 200, description = "User banned successfully", body = ServerBan),
+        (status = 400, description = "Invalid reason or expiry", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to ban or cannot ban the owner", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn ban_member(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<BanMemberRequest>,
+) -> Result<Response<ServerBan>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    let user_id = UserId::from(user_id);
+
+    user_identity.can_ban_members(server_id).await?;
+
+    let ban = state
+        .service
+        .ban_member(server_id, user_id, *user_identity, request)
+        .await?;
+    Ok(Response::ok(ban))
+}
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/bans/bulk",
+    tag = "server_bans",
+    request_body = BulkBanRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Outcome of the ban for each user", body = Vec<BulkMemberResult>),
+        (status = 400, description = "No user, too many users, invalid reason or expiry", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to ban", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_ban_members(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<BulkBanRequest>,
+) -> Result<Response<Vec<BulkMemberResult>>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_ban_members(server_id).await?;
+
+    let results = state
+        .service
+        .bulk_ban_members(server_id, *user_identity, request)
+        .await?;
+    Ok(Response::ok(results))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/bans",
+    tag = "server_bans",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        GetPaginated
+    ),
+    responses(
+        (status = 200, description = "Active bans retrieved successfully", body = PaginatedResponse<ServerBan>),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to view bans", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_bans(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Query(pagination): Query<GetPaginated>,
+) -> Result<Response<PaginatedResponse<ServerBan>>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_ban_members(server_id).await?;
+
+    let page = pagination.page;
+    let (bans, total) = state.service.list_bans(server_id, &pagination).await?;
+
+    let response = PaginatedResponse {
+        data: bans,
+        total,
+        page,
+        next_cursor: None,
+    };
+
+    Ok(Response::ok(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/bans/{user_id}",
+    tag = "server_bans",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("user_id" = String, Path, description = "ID of the banned user")
+    ),
+    responses(
+        (status = 200, description = "Ban retrieved successfully", body = ServerBan),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to view bans", body = ErrorBody),
+        (status = 404, description = "Ban not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_ban(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerBan>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    let user_id = UserId::from(user_id);
+
+    user_identity.can_ban_members(server_id).await?;
+
+    let ban = state.service.get_ban(server_id, user_id).await?;
+    Ok(Response::ok(ban))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/servers/{server_id}/bans/{user_id}",
+    tag = "server_bans",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("user_id" = String, Path, description = "ID of the banned user")
+    ),
+    responses(
+        (status = 200, description = "Ban lifted successfully"),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to lift bans", body = ErrorBody),
+        (status = 404, description = "Ban not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unban_member(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<serde_json::Value>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    let user_id = UserId::from(user_id);
+
+    user_identity.can_ban_members(server_id).await?;
+
+    state
+        .service
+        .unban_member(server_id, user_id, *user_identity)
+        .await?;
+    Ok(Response::deleted(json!({})))
+}
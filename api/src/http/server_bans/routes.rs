@@ -0,0 +1,18 @@
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::http::{
+    server::AppState,
+    server_bans::handlers::{
+        __path_ban_member, __path_bulk_ban_members, __path_get_ban, __path_list_bans,
+        __path_unban_member, ban_member, bulk_ban_members, get_ban, list_bans, unban_member,
+    },
+};
+
+pub fn server_ban_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(ban_member))
+        .routes(routes!(bulk_ban_members))
+        .routes(routes!(list_bans))
+        .routes(routes!(get_ban))
+        .routes(routes!(unban_member))
+}
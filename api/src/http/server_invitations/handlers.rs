@@ -4,11 +4,14 @@ use axum::{
 };
 use communities_core::domain::{
     friend::entities::UserId,
-    server::{entities::ServerId, ports::ServerService},
+    server::{
+        entities::{ServerId, ServerPreview},
+        ports::ServerService,
+    },
     server_invitation::{
         entities::{
             AcceptInvitationInput, CreateServerInvitationRequest, ServerInvitation,
-            ServerInvitationId,
+            ServerInvitationId, ServerVanityUrl, SetVanitySlugRequest, VanityLookup,
         },
         ports::ServerInvitationService,
     },
@@ -102,3 +105,168 @@ pub async fn accept_invitation(
     state.service.accept_invitation(&accept_input).await?;
     Ok(Response::ok(()))
 }
+
+#[utoipa::path(
+    get,
+    path = "/invitations/{invitation_id}/preview",
+    tag = "server_invitations",
+    params(
+        ("invitation_id" = String, Path, description = "Invitation ID")
+    ),
+    responses(
+        (status = 200, description = "Server preview retrieved successfully", body = ServerPreview),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Not the invitee or invitation expired"),
+        (status = 404, description = "Invitation not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_invitation_preview(
+    Path(invitation_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerPreview>, ApiError> {
+    let invitation_id = ServerInvitationId::from(invitation_id);
+    let preview = state
+        .service
+        .get_invitation_preview(&invitation_id, &user_identity)
+        .await?;
+    Ok(Response::ok(preview))
+}
+
+#[utoipa::path(
+    put,
+    path = "/servers/{server_id}/vanity",
+    tag = "server_invitations",
+    request_body = SetVanitySlugRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Vanity slug claimed successfully", body = ServerVanityUrl),
+        (status = 400, description = "Bad request - Invalid or reserved slug"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Missing permission or server is not public"),
+        (status = 404, description = "Server not found"),
+        (status = 409, description = "Conflict - Slug already taken"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn set_vanity_slug(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<SetVanitySlugRequest>,
+) -> Result<Response<ServerVanityUrl>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    user_identity.can_manage_server(server_id).await?;
+
+    let vanity = state
+        .service
+        .set_vanity_slug(&server_id, &user_identity, request)
+        .await?;
+    Ok(Response::ok(vanity))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/vanity",
+    tag = "server_invitations",
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Vanity slug retrieved successfully", body = ServerVanityUrl),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Server has no vanity slug"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_server_vanity(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerVanityUrl>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    user_identity.can_manage_server(server_id).await?;
+
+    let vanity = state.service.get_server_vanity(&server_id).await?;
+    Ok(Response::ok(vanity))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/servers/{server_id}/vanity",
+    tag = "server_invitations",
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Vanity slug released successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Server has no vanity slug"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn remove_vanity_slug(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<()>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    user_identity.can_manage_server(server_id).await?;
+
+    state.service.remove_vanity_slug(&server_id).await?;
+    Ok(Response::deleted(()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/vanity/{slug}",
+    tag = "server_invitations",
+    params(
+        ("slug" = String, Path, description = "Vanity slug")
+    ),
+    responses(
+        (status = 200, description = "Vanity slug resolved successfully", body = VanityLookup),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Vanity slug not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn resolve_vanity_slug(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Extension(_user_identity): Extension<UserIdentity>,
+) -> Result<Response<VanityLookup>, ApiError> {
+    let lookup = state.service.resolve_vanity_slug(&slug).await?;
+    Ok(Response::ok(lookup))
+}
+
+#[utoipa::path(
+    post,
+    path = "/vanity/{slug}/join",
+    tag = "server_invitations",
+    params(
+        ("slug" = String, Path, description = "Vanity slug")
+    ),
+    responses(
+        (status = 200, description = "Server joined successfully", body = VanityLookup),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Vanity slug not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn join_by_vanity_slug(
+    Path(slug): Path<String>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<VanityLookup>, ApiError> {
+    let lookup = state
+        .service
+        .join_by_vanity_slug(&slug, *user_identity)
+        .await?;
+    Ok(Response::ok(lookup))
+}
@@ -4,7 +4,11 @@ use crate::{
     http::server::AppState,
     http::server_invitations::handlers::{
         __path_accept_invitation, __path_create_invitation, __path_get_invitation,
-        accept_invitation, create_invitation, get_invitation,
+        __path_get_invitation_preview, __path_get_server_vanity, __path_join_by_vanity_slug,
+        __path_remove_vanity_slug, __path_resolve_vanity_slug, __path_set_vanity_slug,
+        accept_invitation, create_invitation, get_invitation, get_invitation_preview,
+        get_server_vanity, join_by_vanity_slug, remove_vanity_slug, resolve_vanity_slug,
+        set_vanity_slug,
     },
 };
 
@@ -13,4 +17,12 @@ pub fn server_invitation_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(create_invitation))
         .routes(routes!(get_invitation))
         .routes(routes!(accept_invitation))
+        .routes(routes!(get_invitation_preview))
+        .routes(routes!(
+            set_vanity_slug,
+            get_server_vanity,
+            remove_vanity_slug
+        ))
+        .routes(routes!(resolve_vanity_slug))
+        .routes(routes!(join_by_vanity_slug))
 }
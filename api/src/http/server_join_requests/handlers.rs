@@ -0,0 +1,227 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use communities_core::domain::{
+    common::GetPaginated,
+    server::entities::ServerId,
+    server_join_request::{
+        entities::{
+            JoinQuestions, ListJoinRequestsQuery, ServerJoinRequest, ServerJoinRequestId,
+            SubmitJoinRequestRequest,
+        },
+        ports::ServerJoinRequestService,
+    },
+};
+use uuid::Uuid;
+
+use crate::http::server::{
+    ApiError, AppState, Response, api_error::ErrorBody, middleware::auth::entities::UserIdentity,
+    response::PaginatedResponse,
+};
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/join-requests/questions",
+    tag = "server_join_requests",
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Questions applicants can answer", body = JoinQuestions),
+        (status = 400, description = "The server does not accept join requests", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_join_questions(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Response<JoinQuestions>, ApiError> {
+    let questions = state
+        .service
+        .get_join_questions(ServerId::from(server_id))
+        .await?;
+    Ok(Response::ok(questions))
+}
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/join-requests",
+    tag = "server_join_requests",
+    request_body = SubmitJoinRequestRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 201, description = "Join request submitted", body = ServerJoinRequest),
+        (status = 400, description = "The server does not accept join requests or invalid answers", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 409, description = "Already a member, banned or a request is already pending", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn submit_join_request(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<SubmitJoinRequestRequest>,
+) -> Result<Response<ServerJoinRequest>, ApiError> {
+    let join_request = state
+        .service
+        .submit_join_request(ServerId::from(server_id), *user_identity, request)
+        .await?;
+    Ok(Response::created(join_request))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/join-requests/@me",
+    tag = "server_join_requests",
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Latest join request of the current user", body = ServerJoinRequest),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 404, description = "No join request for this server", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_own_join_request(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerJoinRequest>, ApiError> {
+    let join_request = state
+        .service
+        .get_own_join_request(ServerId::from(server_id), *user_identity)
+        .await?;
+    Ok(Response::ok(join_request))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/join-requests",
+    tag = "server_join_requests",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        GetPaginated,
+        ListJoinRequestsQuery
+    ),
+    responses(
+        (status = 200, description = "Join requests retrieved successfully", body = PaginatedResponse<ServerJoinRequest>),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to review join requests", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_join_requests(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Query(pagination): Query<GetPaginated>,
+    Query(query): Query<ListJoinRequestsQuery>,
+) -> Result<Response<PaginatedResponse<ServerJoinRequest>>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_moderate_members(server_id).await?;
+
+    let page = pagination.page;
+    let (requests, total) = state
+        .service
+        .list_join_requests(server_id, query, &pagination)
+        .await?;
+
+    let response = PaginatedResponse {
+        data: requests,
+        total,
+        page,
+        next_cursor: None,
+    };
+
+    Ok(Response::ok(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/join-requests/{request_id}/approve",
+    tag = "server_join_requests",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("request_id" = String, Path, description = "Join request ID")
+    ),
+    responses(
+        (status = 200, description = "Join request approved, the applicant is now a member", body = ServerJoinRequest),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to review join requests", body = ErrorBody),
+        (status = 404, description = "Join request not found", body = ErrorBody),
+        (status = 409, description = "Join request already reviewed or applicant banned", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn approve_join_request(
+    Path((server_id, request_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerJoinRequest>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_moderate_members(server_id).await?;
+
+    let join_request = state
+        .service
+        .approve_join_request(
+            server_id,
+            ServerJoinRequestId::from(request_id),
+            *user_identity,
+        )
+        .await?;
+    Ok(Response::ok(join_request))
+}
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/join-requests/{request_id}/deny",
+    tag = "server_join_requests",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("request_id" = String, Path, description = "Join request ID")
+    ),
+    responses(
+        (status = 200, description = "Join request denied", body = ServerJoinRequest),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to review join requests", body = ErrorBody),
+        (status = 404, description = "Join request not found", body = ErrorBody),
+        (status = 409, description = "Join request already reviewed", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn deny_join_request(
+    Path((server_id, request_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerJoinRequest>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_moderate_members(server_id).await?;
+
+    let join_request = state
+        .service
+        .deny_join_request(
+            server_id,
+            ServerJoinRequestId::from(request_id),
+            *user_identity,
+        )
+        .await?;
+    Ok(Response::ok(join_request))
+}
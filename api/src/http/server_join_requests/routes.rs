@@ -0,0 +1,20 @@
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::http::{
+    server::AppState,
+    server_join_requests::handlers::{
+        __path_approve_join_request, __path_deny_join_request, __path_get_join_questions,
+        __path_get_own_join_request, __path_list_join_requests, __path_submit_join_request,
+        approve_join_request, deny_join_request, get_join_questions, get_own_join_request,
+        list_join_requests, submit_join_request,
+    },
+};
+
+pub fn server_join_request_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_join_questions))
+        .routes(routes!(submit_join_request, list_join_requests))
+        .routes(routes!(get_own_join_request))
+        .routes(routes!(approve_join_request))
+        .routes(routes!(deny_join_request))
+}
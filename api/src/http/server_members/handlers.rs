@@ -3,7 +3,7 @@ use axum::{
     extract::{Path, Query, State},
 };
 use communities_core::domain::{
-    common::GetPaginated,
+    common::{GetCursor, GetPaginated},
     friend::entities::UserId,
     server::{
         entities::{ServerId, ServerVisibility},
@@ -11,7 +11,11 @@ use communities_core::domain::{
     },
     server_member::{
         CreateMemberInput,
-        entities::{ServerMember, UpdateMemberInput},
+        entities::{
+            BulkKickRequest, BulkMemberResult, KickMemberRequest, ListMembersQuery, ListedMember,
+            MemberProfile, PruneMembersRequest, PruneMembersResult, ServerMember,
+            TimeoutMemberRequest, UpdateMemberInput,
+        },
         ports::MemberService,
     },
 };
@@ -31,6 +35,8 @@ pub struct UpdateMemberRequest {
     /// New nickname for the member
     #[schema(example = "NewNickname")]
     pub nickname: Option<String>,
+    /// New per-server profile, replacing the current one. Left unchanged when omitted.
+    pub profile: Option<MemberProfile>,
 }
 
 #[utoipa::path(
@@ -80,10 +86,13 @@ pub async fn create_member(
     tag = "server_members",
     params(
         ("server_id" = String, Path, description = "Server ID"),
-        GetPaginated
+        GetPaginated,
+        GetCursor,
+        ListMembersQuery
     ),
     responses(
-        (status = 200, description = "List of members retrieved successfully", body = PaginatedResponse<ServerMember>),
+        (status = 200, description = "List of members retrieved successfully", body = PaginatedResponse<ListedMember>),
+        (status = 400, description = "Invalid cursor", body = ErrorBody),
         (status = 401, description = "Unauthorized", body = ErrorBody),
         (status = 403, description = "Forbidden - Cannot access private server members", body = ErrorBody),
         (status = 404, description = "Server not found", body = ErrorBody),
@@ -96,18 +105,32 @@ pub async fn list_members(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     Query(pagination): Query<GetPaginated>,
-) -> Result<Response<PaginatedResponse<ServerMember>>, ApiError> {
+    Query(cursor): Query<GetCursor>,
+    Query(query): Query<ListMembersQuery>,
+) -> Result<Response<PaginatedResponse<ListedMember>>, ApiError> {
     let server_id = ServerId::from(server_id);
 
     user_identity.can_view_server(server_id).await?;
 
+    if let Some(after) = cursor.decode()? {
+        let page = state
+            .service
+            .list_members_after(server_id, after, pagination.limit, query)
+            .await?;
+        return Ok(Response::ok(PaginatedResponse::from_cursor_page(page)));
+    }
+
     let page = pagination.page;
-    let (members, total) = state.service.list_members(server_id, pagination).await?;
+    let (members, total) = state
+        .service
+        .list_members(server_id, pagination, query)
+        .await?;
 
     let response = PaginatedResponse {
         data: members,
         total,
         page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
@@ -124,7 +147,7 @@ pub async fn list_members(
     ),
     responses(
         (status = 200, description = "Member updated successfully", body = ServerMember),
-        (status = 400, description = "Invalid nickname", body = ErrorBody),
+        (status = 400, description = "Invalid nickname or profile", body = ErrorBody),
         (status = 401, description = "Unauthorized", body = ErrorBody),
         (status = 403, description = "Forbidden - Not authorized to update member", body = ErrorBody),
         (status = 404, description = "Member not found", body = ErrorBody),
@@ -150,6 +173,7 @@ pub async fn update_member(
         server_id,
         user_id,
         nickname: request.nickname,
+        profile: request.profile,
     };
 
     let member = state.service.update_member(input).await?;
@@ -165,9 +189,10 @@ pub async fn update_member(
         ("user_id" = String, Path, description = "User ID")
     ),
     responses(
-        (status = 200, description = "Member removed successfully"),
+        (status = 200, description = "Member kicked successfully"),
+        (status = 400, description = "Cannot kick yourself", body = ErrorBody),
         (status = 401, description = "Unauthorized", body = ErrorBody),
-        (status = 403, description = "Forbidden - Not authorized to remove member", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to kick members or member owns the server", body = ErrorBody),
         (status = 404, description = "Member not found", body = ErrorBody),
         (status = 500, description = "Internal server error", body = ErrorBody)
     ),
@@ -181,8 +206,261 @@ pub async fn delete_member(
     let server_id = ServerId::from(server_id);
     let user_id = UserId::from(user_id);
 
-    user_identity.can_manage_server(server_id).await?;
+    user_identity.can_kick_members(server_id).await?;
+
+    state
+        .service
+        .kick_member(
+            server_id,
+            user_id,
+            *user_identity,
+            KickMemberRequest::default(),
+        )
+        .await?;
+    Ok(Response::ok(json!({})))
+}
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/members/{user_id}/kick",
+    tag = "server_members",
+    request_body = KickMemberRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("user_id" = String, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Member kicked successfully"),
+        (status = 400, description = "Reason too long or cannot kick yourself", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to kick members or member owns the server", body = ErrorBody),
+        (status = 404, description = "Member not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn kick_member(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<KickMemberRequest>,
+) -> Result<Response<serde_json::Value>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    let user_id = UserId::from(user_id);
+
+    user_identity.can_kick_members(server_id).await?;
 
-    state.service.delete_member(server_id, user_id).await?;
+    state
+        .service
+        .kick_member(server_id, user_id, *user_identity, request)
+        .await?;
     Ok(Response::ok(json!({})))
 }
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/members/bulk/kick",
+    tag = "server_members",
+    request_body = BulkKickRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Outcome of the kick for each user", body = Vec<BulkMemberResult>),
+        (status = 400, description = "No user, too many users or reason too long", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to kick members", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_kick_members(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<BulkKickRequest>,
+) -> Result<Response<Vec<BulkMemberResult>>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_kick_members(server_id).await?;
+
+    let results = state
+        .service
+        .bulk_kick_members(server_id, *user_identity, request)
+        .await?;
+    Ok(Response::ok(results))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{server_id}/members/prune",
+    tag = "server_members",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        PruneMembersRequest
+    ),
+    responses(
+        (status = 200, description = "Number of members a prune would remove", body = PruneMembersResult),
+        (status = 400, description = "Number of days out of range", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not the owner or an administrator", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn count_prunable_members(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Query(request): Query<PruneMembersRequest>,
+) -> Result<Response<PruneMembersResult>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_prune_members(server_id).await?;
+
+    let result = state
+        .service
+        .count_prunable_members(server_id, request)
+        .await?;
+    Ok(Response::ok(result))
+}
+
+#[utoipa::path(
+    post,
+    path = "/servers/{server_id}/members/prune",
+    tag = "server_members",
+    request_body = PruneMembersRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Number of members removed", body = PruneMembersResult),
+        (status = 400, description = "Number of days out of range", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not the owner or an administrator", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn prune_members(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<PruneMembersRequest>,
+) -> Result<Response<PruneMembersResult>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_prune_members(server_id).await?;
+
+    let result = state
+        .service
+        .prune_members(server_id, *user_identity, request)
+        .await?;
+    Ok(Response::ok(result))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/servers/{server_id}/members/@me",
+    tag = "server_members",
+    params(
+        ("server_id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Server left successfully"),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not a member or owner of the server", body = ErrorBody),
+        (status = 404, description = "Server not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn leave_server(
+    Path(server_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<serde_json::Value>, ApiError> {
+    let server_id = ServerId::from(server_id);
+
+    user_identity.can_leave_server(server_id).await?;
+
+    state
+        .service
+        .leave_server(server_id, *user_identity)
+        .await?;
+    Ok(Response::ok(json!({})))
+}
+
+#[utoipa::path(
+    put,
+    path = "/servers/{server_id}/members/{user_id}/timeout",
+    tag = "server_members",
+    request_body = TimeoutMemberRequest,
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("user_id" = String, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Member timed out successfully", body = ServerMember),
+        (status = 400, description = "Invalid timeout", body = ErrorBody),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to moderate members", body = ErrorBody),
+        (status = 404, description = "Member not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn timeout_member(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<TimeoutMemberRequest>,
+) -> Result<Response<ServerMember>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    let user_id = UserId::from(user_id);
+
+    user_identity.can_moderate_members(server_id).await?;
+
+    let member = state
+        .service
+        .timeout_member(server_id, user_id, *user_identity, request)
+        .await?;
+    Ok(Response::ok(member))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/servers/{server_id}/members/{user_id}/timeout",
+    tag = "server_members",
+    params(
+        ("server_id" = String, Path, description = "Server ID"),
+        ("user_id" = String, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Timeout cleared successfully", body = ServerMember),
+        (status = 401, description = "Unauthorized", body = ErrorBody),
+        (status = 403, description = "Forbidden - Not authorized to moderate members", body = ErrorBody),
+        (status = 404, description = "Member not found", body = ErrorBody),
+        (status = 500, description = "Internal server error", body = ErrorBody)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn clear_member_timeout(
+    Path((server_id, user_id)): Path<(Uuid, Uuid)>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerMember>, ApiError> {
+    let server_id = ServerId::from(server_id);
+    let user_id = UserId::from(user_id);
+
+    user_identity.can_moderate_members(server_id).await?;
+
+    let member = state
+        .service
+        .clear_member_timeout(server_id, user_id, *user_identity)
+        .await?;
+    Ok(Response::ok(member))
+}
@@ -3,8 +3,12 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use crate::http::{
     server::AppState,
     server_members::handlers::{
-        __path_create_member, __path_delete_member, __path_list_members, __path_update_member,
-        create_member, delete_member, list_members, update_member,
+        __path_bulk_kick_members, __path_clear_member_timeout, __path_count_prunable_members,
+        __path_create_member, __path_delete_member, __path_kick_member, __path_leave_server,
+        __path_list_members, __path_prune_members, __path_timeout_member, __path_update_member,
+        bulk_kick_members, clear_member_timeout, count_prunable_members, create_member,
+        delete_member, kick_member, leave_server, list_members, prune_members, timeout_member,
+        update_member,
     },
 };
 
@@ -14,4 +18,9 @@ pub fn server_member_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(list_members))
         .routes(routes!(update_member))
         .routes(routes!(delete_member))
+        .routes(routes!(leave_server))
+        .routes(routes!(kick_member))
+        .routes(routes!(bulk_kick_members))
+        .routes(routes!(count_prunable_members, prune_members))
+        .routes(routes!(timeout_member, clear_member_timeout))
 }
@@ -3,11 +3,13 @@ use axum::{
     extract::{Path, Query, State},
 };
 use communities_core::domain::{
-    common::GetPaginated,
+    common::{GetCursor, GetPaginated},
     server::{
         entities::{
-            CreateServerRequest, SearchServerQuery, Server, ServerId, ServerVisibility,
-            UpdateServerRequest,
+            CreateServerFromTemplateRequest, CreateServerRequest, CreateServerTemplateRequest,
+            SearchServerQuery, Server, ServerExport, ServerId, ServerPreview, ServerPreviewQuery,
+            ServerSettings, ServerTemplate, ServerTemplateId, ServerVisibility,
+            UpdateServerRequest, UpdateServerSettingsRequest,
         },
         ports::ServerService,
     },
@@ -69,6 +71,97 @@ pub async fn get_server(
     Ok(Response::ok(server))
 }
 
+#[utoipa::path(
+    get,
+    path = "/servers/{id}/preview",
+    tag = "servers",
+    params(
+        ("id" = String, Path, description = "Server ID"),
+        ServerPreviewQuery
+    ),
+    responses(
+        (status = 200, description = "Server preview retrieved successfully", body = ServerPreview),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Server is private and no valid invitation was provided"),
+        (status = 404, description = "Server not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_server_preview(
+    Path(id): Path<Uuid>,
+    Query(query): Query<ServerPreviewQuery>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerPreview>, ApiError> {
+    let server_id = ServerId::from(id);
+    let preview = state
+        .service
+        .preview_server(&server_id, &user_identity, query.invitation_id)
+        .await?;
+
+    Ok(Response::ok(preview))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{id}/settings",
+    tag = "servers",
+    params(
+        ("id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Server settings retrieved successfully", body = ServerSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Not a member of the server"),
+        (status = 404, description = "Server not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_server_settings(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerSettings>, ApiError> {
+    let server_id = ServerId::from(id);
+    user_identity.can_view_server(server_id).await?;
+
+    let settings = state.service.get_server_settings(&server_id).await?;
+    Ok(Response::ok(settings))
+}
+
+#[utoipa::path(
+    put,
+    path = "/servers/{id}/settings",
+    tag = "servers",
+    params(
+        ("id" = String, Path, description = "Server ID")
+    ),
+    request_body = UpdateServerSettingsRequest,
+    responses(
+        (status = 200, description = "Server settings updated successfully", body = ServerSettings),
+        (status = 400, description = "Bad request - Channel or role does not belong to the server"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Missing permission to manage the server"),
+        (status = 404, description = "Server, channel or role not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn update_server_settings(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<UpdateServerSettingsRequest>,
+) -> Result<Response<ServerSettings>, ApiError> {
+    let server_id = ServerId::from(id);
+    user_identity.can_manage_server(server_id).await?;
+
+    let settings = state
+        .service
+        .update_server_settings(&server_id, request)
+        .await?;
+    Ok(Response::ok(settings))
+}
+
 #[utoipa::path(
     get,
     path = "/servers",
@@ -93,6 +186,7 @@ pub async fn list_servers(
         data: servers,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
@@ -103,10 +197,12 @@ pub async fn list_servers(
     path = "/servers/@me",
     tag = "servers",
     params(
-        GetPaginated
+        GetPaginated,
+        GetCursor
     ),
     responses(
         (status = 200, description = "List of servers retrieved successfully", body = PaginatedResponse<Server>),
+        (status = 400, description = "Invalid cursor"),
         (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error")
     )
@@ -115,7 +211,16 @@ pub async fn list_user_servers(
     State(state): State<AppState>,
     Extension(user_identity): Extension<UserIdentity>,
     Query(pagination): Query<GetPaginated>,
+    Query(cursor): Query<GetCursor>,
 ) -> Result<Response<PaginatedResponse<Server>>, ApiError> {
+    if let Some(after) = cursor.decode()? {
+        let page = state
+            .service
+            .list_user_servers_after(after, pagination.limit, *user_identity)
+            .await?;
+        return Ok(Response::ok(PaginatedResponse::from_cursor_page(page)));
+    }
+
     let (servers, total) = state
         .service
         .list_user_servers(&pagination, *user_identity)
@@ -125,6 +230,7 @@ pub async fn list_user_servers(
         data: servers,
         total,
         page: pagination.page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
@@ -228,7 +334,158 @@ pub async fn search_or_discover_servers(
         data: servers,
         total,
         page: safe_pagination.page,
+        next_cursor: None,
     };
 
     Ok(Response::ok(response))
 }
+
+#[utoipa::path(
+    post,
+    path = "/servers/{id}/templates",
+    tag = "servers",
+    params(
+        ("id" = String, Path, description = "Server ID")
+    ),
+    request_body = CreateServerTemplateRequest,
+    responses(
+        (status = 201, description = "Template created successfully", body = ServerTemplate),
+        (status = 400, description = "Bad request - Invalid template"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Missing server management permission"),
+        (status = 404, description = "Server not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn create_server_template(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<CreateServerTemplateRequest>,
+) -> Result<Response<ServerTemplate>, ApiError> {
+    let server_id = ServerId::from(id);
+    user_identity.can_manage_server(server_id).await?;
+
+    let input = request.into_input(server_id, *user_identity);
+    let template = state.service.create_template(input).await?;
+    Ok(Response::created(template))
+}
+
+#[utoipa::path(
+    get,
+    path = "/templates/{template_id}",
+    tag = "servers",
+    params(
+        ("template_id" = String, Path, description = "Template ID")
+    ),
+    responses(
+        (status = 200, description = "Template retrieved successfully", body = ServerTemplate),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Template of a private server you are not a member of"),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_server_template(
+    Path(template_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerTemplate>, ApiError> {
+    let template = state
+        .service
+        .get_template(&ServerTemplateId::from(template_id))
+        .await?;
+    user_identity.can_view_template(&template).await?;
+    Ok(Response::ok(template))
+}
+
+#[utoipa::path(
+    post,
+    path = "/templates/{template_id}/servers",
+    tag = "servers",
+    params(
+        ("template_id" = String, Path, description = "Template ID")
+    ),
+    request_body = CreateServerFromTemplateRequest,
+    responses(
+        (status = 201, description = "Server created from template successfully", body = Server),
+        (status = 400, description = "Bad request - Invalid server name or template"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Template of a private server you are not a member of"),
+        (status = 404, description = "Template not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn create_server_from_template(
+    Path(template_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(request): Json<CreateServerFromTemplateRequest>,
+) -> Result<Response<Server>, ApiError> {
+    let template_id = ServerTemplateId::from(template_id);
+    let template = state.service.get_template(&template_id).await?;
+    user_identity.can_view_template(&template).await?;
+
+    let server = state
+        .service
+        .create_server_from_template(&template_id, *user_identity, request)
+        .await?;
+    Ok(Response::created(server))
+}
+
+#[utoipa::path(
+    get,
+    path = "/servers/{id}/export",
+    tag = "servers",
+    params(
+        ("id" = String, Path, description = "Server ID")
+    ),
+    responses(
+        (status = 200, description = "Server exported successfully", body = ServerExport),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Not the server owner"),
+        (status = 404, description = "Server not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn export_server(
+    Path(id): Path<Uuid>,
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+) -> Result<Response<ServerExport>, ApiError> {
+    let server_id = ServerId::from(id);
+
+    // Exports contain every member, only the owner can take them
+    let existing_server = state.service.get_server(&server_id).await?;
+    if existing_server.owner_id != user_identity.user_id {
+        return Err(ApiError::Forbidden);
+    }
+
+    let export = state.service.export_server(&server_id).await?;
+    Ok(Response::ok(export))
+}
+
+/// Imports an exported server as a new server owned by the caller
+///
+/// The other exported members are invited rather than joined: their nicknames and roles
+/// are not restored when they accept.
+#[utoipa::path(
+    post,
+    path = "/servers/import",
+    tag = "servers",
+    request_body = ServerExport,
+    responses(
+        (status = 201, description = "Server imported successfully", body = Server),
+        (status = 400, description = "Bad request - Invalid or unsupported export bundle"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn import_server(
+    State(state): State<AppState>,
+    Extension(user_identity): Extension<UserIdentity>,
+    Json(bundle): Json<ServerExport>,
+) -> Result<Response<Server>, ApiError> {
+    let server = state.service.import_server(*user_identity, bundle).await?;
+    Ok(Response::created(server))
+}
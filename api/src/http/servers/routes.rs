@@ -3,9 +3,14 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 use crate::http::{
     server::AppState,
     servers::handlers::{
-        __path_create_server, __path_delete_server, __path_get_server, __path_list_user_servers,
-        __path_search_or_discover_servers, __path_update_server, create_server, delete_server,
-        get_server, list_user_servers, search_or_discover_servers, update_server,
+        __path_create_server, __path_create_server_from_template, __path_create_server_template,
+        __path_delete_server, __path_export_server, __path_get_server, __path_get_server_preview,
+        __path_get_server_settings, __path_get_server_template, __path_import_server,
+        __path_list_user_servers, __path_search_or_discover_servers, __path_update_server,
+        __path_update_server_settings, create_server, create_server_from_template,
+        create_server_template, delete_server, export_server, get_server, get_server_preview,
+        get_server_settings, get_server_template, import_server, list_user_servers,
+        search_or_discover_servers, update_server, update_server_settings,
     },
 };
 
@@ -14,8 +19,15 @@ pub fn server_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(create_server))
         .routes(routes!(search_or_discover_servers))
         .routes(routes!(get_server))
+        .routes(routes!(get_server_preview))
+        .routes(routes!(get_server_settings, update_server_settings))
         .routes(routes!(list_user_servers))
         // .routes(routes!(list_servers))
         .routes(routes!(update_server))
         .routes(routes!(delete_server))
+        .routes(routes!(create_server_template))
+        .routes(routes!(get_server_template))
+        .routes(routes!(create_server_from_template))
+        .routes(routes!(export_server))
+        .routes(routes!(import_server))
 }
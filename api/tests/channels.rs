@@ -175,3 +175,40 @@ async fn test_delete_channel_not_found(ctx: &mut context::TestContext) {
 
     res.assert_status(StatusCode::NOT_FOUND);
 }
+
+// ============================================================================
+// PERMISSION OVERWRITE TESTS
+// ============================================================================
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_list_permission_overwrites_unauthorized(ctx: &mut context::TestContext) {
+    let channel_id = Uuid::new_v4();
+    let res = ctx
+        .unauthenticated_router
+        .get(&format!("/channels/{}/permissions", channel_id))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_upsert_role_overwrite_channel_not_found(ctx: &mut context::TestContext) {
+    let fake_channel_id = Uuid::new_v4();
+    let role_id = Uuid::new_v4();
+
+    let res = ctx
+        .authenticated_router
+        .put(&format!(
+            "/channels/{}/permissions/roles/{}",
+            fake_channel_id, role_id
+        ))
+        .json(&json!({
+            "allow": 0x40,
+            "deny": 0x80
+        }))
+        .await;
+
+    res.assert_status(StatusCode::NOT_FOUND);
+}
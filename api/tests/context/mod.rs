@@ -96,6 +96,7 @@ impl AsyncTestContext for TestContext {
         let server = api::config::ServerConfig {
             api_port: 8080,
             health_port: 8081,
+            role_expiry_sweep_interval_secs: 60,
         };
 
         let cors_origins = vec!["http://localhost:3003".to_string()];
@@ -122,6 +122,57 @@ async fn test_create_role_with_valid_permissions(ctx: &mut context::TestContext)
     );
 }
 
+#[test_context(context::TestContext)]
+#[ignore]
+#[tokio::test]
+async fn test_create_role_with_preset_or_permission_names(ctx: &mut context::TestContext) {
+    let server_input = CreateServerRequest {
+        name: "Test Server".to_string(),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+
+    let server_res = ctx
+        .authenticated_router
+        .post("/servers")
+        .json(&server_input)
+        .await;
+    server_res.assert_status(StatusCode::CREATED);
+    let server: Value = server_res.json();
+    let server_id = server.get("id").and_then(|v| v.as_str()).unwrap();
+
+    let res = ctx
+        .authenticated_router
+        .post(&format!("/servers/{}/roles", server_id))
+        .json(&json!({
+            "name": "Read Only",
+            "permissions": "ReadOnly"
+        }))
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let body: Value = res.json();
+    assert_eq!(body["permissions"], json!(0x40));
+    assert_eq!(body["permission_names"], json!(["ViewChannels"]));
+
+    let res = ctx
+        .authenticated_router
+        .post(&format!("/servers/{}/roles", server_id))
+        .json(&json!({
+            "name": "Writer",
+            "permissions": ["ViewChannels", "SendMessages"]
+        }))
+        .await;
+    res.assert_status(StatusCode::CREATED);
+    let body: Value = res.json();
+    assert_eq!(body["permissions"], json!(0x40 | 0x80));
+    assert_eq!(
+        body["permission_names"],
+        json!(["ViewChannels", "SendMessages"])
+    );
+}
+
 // ============================================================================
 // GET ROLE TESTS
 // ============================================================================
@@ -338,6 +389,84 @@ async fn test_list_roles_by_server_pagination(ctx: &mut context::TestContext) {
     );
 }
 
+// ============================================================================
+// REORDER ROLES TESTS
+// ============================================================================
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_reorder_roles_unauthorized(ctx: &mut context::TestContext) {
+    let server_id = Uuid::new_v4();
+    let res = ctx
+        .unauthenticated_router
+        .put(&format!("/servers/{}/roles/positions", server_id))
+        .json(&json!({
+            "role_ids": [Uuid::new_v4()]
+        }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    res.assert_json(&json!(Into::<ErrorBody>::into(ApiError::Unauthorized)));
+}
+
+// ============================================================================
+// EFFECTIVE PERMISSIONS TESTS
+// ============================================================================
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_list_permissions_unauthorized(ctx: &mut context::TestContext) {
+    let res = ctx.unauthenticated_router.get("/permissions").await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    res.assert_json(&json!(Into::<ErrorBody>::into(ApiError::Unauthorized)));
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_get_my_permissions_unauthorized(ctx: &mut context::TestContext) {
+    let server_id = Uuid::new_v4();
+    let res = ctx
+        .unauthenticated_router
+        .get(&format!("/servers/{}/roles/@me/permissions", server_id))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    res.assert_json(&json!(Into::<ErrorBody>::into(ApiError::Unauthorized)));
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_get_member_permissions_unauthorized(ctx: &mut context::TestContext) {
+    let server_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let res = ctx
+        .unauthenticated_router
+        .get(&format!(
+            "/servers/{}/members/{}/permissions",
+            server_id, user_id
+        ))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    res.assert_json(&json!(Into::<ErrorBody>::into(ApiError::Unauthorized)));
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_set_member_roles_unauthorized(ctx: &mut context::TestContext) {
+    let server_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let res = ctx
+        .unauthenticated_router
+        .put(&format!("/servers/{}/members/{}/roles", server_id, user_id))
+        .json(&json!({ "role_ids": [] }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+    res.assert_json(&json!(Into::<ErrorBody>::into(ApiError::Unauthorized)));
+}
+
 // ============================================================================
 // UPDATE ROLE TESTS
 // ============================================================================
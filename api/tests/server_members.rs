@@ -226,4 +226,72 @@ async fn test_delete_member_unauthenticated(ctx: &mut context::TestContext) {
     res.assert_status(StatusCode::UNAUTHORIZED);
 }
 
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_timeout_member_unauthenticated(ctx: &mut context::TestContext) {
+    let server_id = "550e8400-e29b-41d4-a716-446655440001";
+    let user_id = "550e8400-e29b-41d4-a716-446655440000";
+    let res = ctx
+        .unauthenticated_router
+        .put(&format!(
+            "/servers/{}/members/{}/timeout",
+            server_id, user_id
+        ))
+        .json(&json!({ "until": "2099-01-01T00:00:00Z" }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_leave_server_unauthenticated(ctx: &mut context::TestContext) {
+    let server_id = "550e8400-e29b-41d4-a716-446655440001";
+    let res = ctx
+        .unauthenticated_router
+        .delete(&format!("/servers/{}/members/@me", server_id))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_bulk_kick_members_unauthenticated(ctx: &mut context::TestContext) {
+    let server_id = "550e8400-e29b-41d4-a716-446655440001";
+    let res = ctx
+        .unauthenticated_router
+        .post(&format!("/servers/{}/members/bulk/kick", server_id))
+        .json(&json!({ "user_ids": ["550e8400-e29b-41d4-a716-446655440000"] }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
+
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_prune_members_unauthenticated(ctx: &mut context::TestContext) {
+    let server_id = "550e8400-e29b-41d4-a716-446655440001";
+    let res = ctx
+        .unauthenticated_router
+        .post(&format!("/servers/{}/members/prune", server_id))
+        .json(&json!({ "days": 30 }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
 
+#[test_context(context::TestContext)]
+#[tokio::test]
+async fn test_submit_join_request_unauthenticated(ctx: &mut context::TestContext) {
+    let server_id = "550e8400-e29b-41d4-a716-446655440001";
+    let res = ctx
+        .unauthenticated_router
+        .post(&format!("/servers/{}/join-requests", server_id))
+        .json(&json!({
+            "answers": ["A friend invited me"]
+        }))
+        .await;
+
+    res.assert_status(StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,5 @@
+-- Drop the trigger first
+DROP TRIGGER IF EXISTS update_server_templates_updated_at ON server_templates;
+
+-- Drop the table
+DROP TABLE IF EXISTS server_templates;
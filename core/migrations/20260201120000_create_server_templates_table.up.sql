@@ -0,0 +1,20 @@
+-- Create the server_templates table
+-- A template stores a versioned JSON snapshot of a server's roles, channel tree and settings
+CREATE TABLE server_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    source_server_id UUID REFERENCES servers(id) ON DELETE SET NULL,
+    creator_id UUID NOT NULL,
+    snapshot JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NULL
+);
+
+-- Create trigger to automatically update updated_at on UPDATE
+CREATE TRIGGER update_server_templates_updated_at
+    BEFORE UPDATE ON server_templates
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE INDEX idx_server_templates_source_server_id ON server_templates(source_server_id) WHERE source_server_id IS NOT NULL;
+CREATE INDEX idx_server_templates_creator_id ON server_templates(creator_id);
@@ -0,0 +1,6 @@
+-- Drop the indexes first
+DROP INDEX IF EXISTS idx_server_invitations_vanity_server_id;
+DROP INDEX IF EXISTS idx_server_invitations_vanity_slug;
+
+ALTER TABLE server_invitations DROP CONSTRAINT IF EXISTS chk_server_invitations_vanity_slug_general;
+ALTER TABLE server_invitations DROP COLUMN IF EXISTS vanity_slug;
@@ -0,0 +1,9 @@
+-- A vanity slug is carried by a general, non-expiring invitation of the server
+ALTER TABLE server_invitations ADD COLUMN vanity_slug VARCHAR(32) DEFAULT NULL;
+
+ALTER TABLE server_invitations ADD CONSTRAINT chk_server_invitations_vanity_slug_general
+    CHECK (vanity_slug IS NULL OR (invitee_id IS NULL AND expires_at IS NULL));
+
+-- Slugs are unique regardless of case, and a server has at most one
+CREATE UNIQUE INDEX idx_server_invitations_vanity_slug ON server_invitations(LOWER(vanity_slug)) WHERE vanity_slug IS NOT NULL;
+CREATE UNIQUE INDEX idx_server_invitations_vanity_server_id ON server_invitations(server_id) WHERE vanity_slug IS NOT NULL;
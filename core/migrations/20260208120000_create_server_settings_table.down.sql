@@ -0,0 +1,8 @@
+-- Drop the trigger first
+DROP TRIGGER IF EXISTS update_server_settings_updated_at ON server_settings;
+
+-- Drop the table
+DROP TABLE IF EXISTS server_settings;
+
+-- Drop the enum type
+DROP TYPE IF EXISTS server_join_mode;
@@ -0,0 +1,19 @@
+-- Create the server_join_mode enum type
+CREATE TYPE server_join_mode AS ENUM ('open', 'invite_only', 'request_to_join');
+
+-- Create the server_settings table
+-- A server without a row uses the default settings
+CREATE TABLE server_settings (
+    server_id UUID PRIMARY KEY REFERENCES servers(id) ON DELETE CASCADE,
+    join_mode server_join_mode NOT NULL DEFAULT 'open',
+    system_channel_id UUID REFERENCES channels(id) ON DELETE SET NULL,
+    default_role_ids UUID[] NOT NULL DEFAULT '{}',
+    allow_nickname_change BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NULL
+);
+
+-- Create trigger to automatically update updated_at on UPDATE
+CREATE TRIGGER update_server_settings_updated_at
+    BEFORE UPDATE ON server_settings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,5 @@
+-- Drop the index first
+DROP INDEX IF EXISTS idx_server_bans_server_id_created_at;
+
+-- Drop the table
+DROP TABLE IF EXISTS server_bans;
@@ -0,0 +1,13 @@
+-- Create the server_bans table
+-- A user has at most one ban per server, a NULL expiry means the ban is permanent
+CREATE TABLE server_bans (
+    server_id UUID NOT NULL REFERENCES servers(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    moderator_id UUID NOT NULL,
+    reason VARCHAR(512),
+    expires_at TIMESTAMP WITH TIME ZONE DEFAULT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (server_id, user_id)
+);
+
+CREATE INDEX idx_server_bans_server_id_created_at ON server_bans(server_id, created_at DESC);
@@ -0,0 +1,2 @@
+-- A timed out member cannot send messages until this date, an elapsed date is ignored on read
+ALTER TABLE server_members ADD COLUMN timed_out_until TIMESTAMP WITH TIME ZONE DEFAULT NULL;
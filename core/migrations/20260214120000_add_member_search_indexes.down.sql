@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_member_roles_role_id;
+DROP INDEX IF EXISTS idx_server_members_server_id_nickname;
+DROP INDEX IF EXISTS idx_server_members_server_id_joined_at;
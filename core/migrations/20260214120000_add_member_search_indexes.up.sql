@@ -0,0 +1,8 @@
+-- Member lists are paged by join date within a server
+CREATE INDEX idx_server_members_server_id_joined_at ON server_members(server_id, joined_at DESC, id DESC);
+
+-- Case insensitive nickname prefix search
+CREATE INDEX idx_server_members_server_id_nickname ON server_members(server_id, LOWER(nickname) text_pattern_ops);
+
+-- Filtering members by role goes from the role to its members
+CREATE INDEX idx_member_roles_role_id ON member_roles(role_id, member_id);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_friends_user_id_2_created_at;
+DROP INDEX IF EXISTS idx_friends_user_id_1_created_at;
+DROP INDEX IF EXISTS idx_server_members_user_id_joined_at;
@@ -0,0 +1,6 @@
+-- The servers of a user are paged by join date
+CREATE INDEX idx_server_members_user_id_joined_at ON server_members(user_id, joined_at DESC, server_id DESC);
+
+-- Friend lists are paged by friendship date from either side
+CREATE INDEX idx_friends_user_id_1_created_at ON friends(user_id_1, created_at DESC);
+CREATE INDEX idx_friends_user_id_2_created_at ON friends(user_id_2, created_at DESC);
@@ -0,0 +1,14 @@
+-- Drop the trigger first
+DROP TRIGGER IF EXISTS update_server_join_requests_updated_at ON server_join_requests;
+
+-- Drop the indexes
+DROP INDEX IF EXISTS idx_server_join_requests_server_id_status;
+DROP INDEX IF EXISTS idx_server_join_requests_pending;
+
+-- Drop the table
+DROP TABLE IF EXISTS server_join_requests;
+
+-- Drop the enum type
+DROP TYPE IF EXISTS server_join_request_status;
+
+ALTER TABLE server_settings DROP COLUMN IF EXISTS join_questions;
@@ -0,0 +1,27 @@
+-- Questions applicants answer when requesting to join
+ALTER TABLE server_settings ADD COLUMN join_questions TEXT[] NOT NULL DEFAULT '{}';
+
+-- Create the server_join_request_status enum type
+CREATE TYPE server_join_request_status AS ENUM ('pending', 'approved', 'denied');
+
+-- Create the server_join_requests table
+-- Answers are kept along with the question they answer, as questions can change later
+CREATE TABLE server_join_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    server_id UUID NOT NULL REFERENCES servers(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    status server_join_request_status NOT NULL DEFAULT 'pending',
+    answers JSONB NOT NULL DEFAULT '[]',
+    reviewer_id UUID DEFAULT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NULL
+);
+
+-- A user has at most one pending request per server
+CREATE UNIQUE INDEX idx_server_join_requests_pending ON server_join_requests(server_id, user_id) WHERE status = 'pending';
+CREATE INDEX idx_server_join_requests_server_id_status ON server_join_requests(server_id, status, created_at DESC);
+
+-- Create trigger to automatically update updated_at on UPDATE
+CREATE TRIGGER update_server_join_requests_updated_at
+    BEFORE UPDATE ON server_join_requests
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1 @@
+ALTER TABLE server_members DROP COLUMN IF EXISTS profile;
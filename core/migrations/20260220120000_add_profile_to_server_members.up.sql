@@ -0,0 +1,2 @@
+-- Per-server profile of a member (avatar, bio, pronouns), kept as a document so fields can be added freely
+ALTER TABLE server_members ADD COLUMN profile JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_roles_server_id_position;
+ALTER TABLE roles DROP COLUMN IF EXISTS position;
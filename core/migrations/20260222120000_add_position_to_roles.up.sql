@@ -0,0 +1,14 @@
+-- Rank of a role in its server hierarchy, higher positions outrank lower ones.
+-- The default role stays at 0 and existing roles are ranked by creation order
+ALTER TABLE roles ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+UPDATE roles
+SET position = ranked.position
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY server_id ORDER BY created_at, id) AS position
+    FROM roles
+    WHERE id <> server_id
+) AS ranked
+WHERE roles.id = ranked.id;
+
+CREATE INDEX idx_roles_server_id_position ON roles(server_id, position);
@@ -0,0 +1,19 @@
+-- Allow and deny bits applied on top of server-wide permissions in one channel,
+-- either for a role or for a single member
+CREATE TABLE channel_permission_overwrites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    role_id UUID REFERENCES roles(id) ON DELETE CASCADE,
+    member_id UUID REFERENCES server_members(id) ON DELETE CASCADE,
+    allow_permissions INTEGER NOT NULL DEFAULT 0,
+    deny_permissions INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ,
+    CONSTRAINT overwrite_single_target CHECK ((role_id IS NULL) <> (member_id IS NULL)),
+    CONSTRAINT overwrite_disjoint_permissions CHECK (allow_permissions & deny_permissions = 0)
+);
+
+CREATE UNIQUE INDEX idx_channel_overwrites_channel_role
+    ON channel_permission_overwrites(channel_id, role_id) WHERE role_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_channel_overwrites_channel_member
+    ON channel_permission_overwrites(channel_id, member_id) WHERE member_id IS NOT NULL;
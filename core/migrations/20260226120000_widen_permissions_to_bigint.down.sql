@@ -0,0 +1,6 @@
+-- Drop every bit above ModerateMembers (0x1000) before narrowing back to 32 bits
+ALTER TABLE channel_permission_overwrites
+    ALTER COLUMN deny_permissions TYPE INTEGER USING (deny_permissions & 8191)::INTEGER;
+ALTER TABLE channel_permission_overwrites
+    ALTER COLUMN allow_permissions TYPE INTEGER USING (allow_permissions & 8191)::INTEGER;
+ALTER TABLE roles ALTER COLUMN permissions TYPE INTEGER USING (permissions & 8191)::INTEGER;
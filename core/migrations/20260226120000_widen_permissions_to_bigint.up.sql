@@ -0,0 +1,12 @@
+-- Permission bitmasks are 64-bit to leave room for new permissions
+ALTER TABLE roles ALTER COLUMN permissions TYPE BIGINT;
+ALTER TABLE channel_permission_overwrites ALTER COLUMN allow_permissions TYPE BIGINT;
+ALTER TABLE channel_permission_overwrites ALTER COLUMN deny_permissions TYPE BIGINT;
+
+-- Banning and kicking used to require ManageServer (0x2): keep those roles able to,
+-- through BanMembers (0x2000) and KickMembers (0x4000)
+UPDATE roles SET permissions = permissions | 24576 WHERE permissions & 2 <> 0;
+
+-- Every member can join and speak in voice channels by default, through Connect (0x8000)
+-- and Speak (0x10000)
+UPDATE roles SET permissions = permissions | 98304 WHERE id = server_id;
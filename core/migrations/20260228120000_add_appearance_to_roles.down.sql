@@ -0,0 +1,6 @@
+ALTER TABLE roles
+    DROP CONSTRAINT IF EXISTS roles_color_rgb,
+    DROP COLUMN IF EXISTS has_icon,
+    DROP COLUMN IF EXISTS mentionable,
+    DROP COLUMN IF EXISTS hoist,
+    DROP COLUMN IF EXISTS color;
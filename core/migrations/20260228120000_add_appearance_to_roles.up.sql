@@ -0,0 +1,8 @@
+-- How a role is displayed: an RGB color (0 for none), whether its members are listed
+-- apart, whether it can be mentioned, and whether an icon was stored in the content service
+ALTER TABLE roles
+    ADD COLUMN color INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN hoist BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN mentionable BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN has_icon BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD CONSTRAINT roles_color_rgb CHECK (color BETWEEN 0 AND 16777215);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_member_roles_expires_at;
+
+ALTER TABLE member_roles DROP COLUMN IF EXISTS expires_at;
@@ -0,0 +1,4 @@
+-- Role assignments may expire, the sweeper unassigns them once past
+ALTER TABLE member_roles ADD COLUMN expires_at TIMESTAMPTZ;
+
+CREATE INDEX idx_member_roles_expires_at ON member_roles (expires_at) WHERE expires_at IS NOT NULL;
@@ -0,0 +1,15 @@
+DROP TRIGGER IF EXISTS roles_protect_default_role ON roles;
+DROP FUNCTION IF EXISTS protect_default_role();
+
+DROP TRIGGER IF EXISTS member_roles_protect_default_role ON member_roles;
+DROP FUNCTION IF EXISTS protect_default_role_assignment();
+
+DROP TRIGGER IF EXISTS server_members_assign_default_role ON server_members;
+DROP FUNCTION IF EXISTS assign_default_role_to_member();
+
+DROP INDEX IF EXISTS roles_one_default_per_server;
+
+ALTER TABLE roles
+    DROP CONSTRAINT IF EXISTS roles_default_at_bottom,
+    DROP CONSTRAINT IF EXISTS roles_default_shares_server_id,
+    DROP COLUMN IF EXISTS is_default;
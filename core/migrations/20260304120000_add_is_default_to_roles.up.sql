@@ -0,0 +1,79 @@
+-- The default role is flagged instead of being recognised by sharing the id of its server
+ALTER TABLE roles ADD COLUMN is_default BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE roles SET is_default = TRUE WHERE id = server_id;
+
+ALTER TABLE roles
+    ADD CONSTRAINT roles_default_shares_server_id CHECK (NOT is_default OR id = server_id),
+    ADD CONSTRAINT roles_default_at_bottom CHECK (NOT is_default OR position = 0);
+
+-- At most one default role per server
+CREATE UNIQUE INDEX roles_one_default_per_server ON roles (server_id) WHERE is_default;
+
+-- Every member holds the default role of their server
+INSERT INTO member_roles (member_id, role_id)
+SELECT sm.id, r.id
+FROM server_members sm
+INNER JOIN roles r ON r.server_id = sm.server_id AND r.is_default
+ON CONFLICT (member_id, role_id) DO NOTHING;
+
+CREATE OR REPLACE FUNCTION assign_default_role_to_member()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO member_roles (member_id, role_id)
+    SELECT NEW.id, id FROM roles WHERE server_id = NEW.server_id AND is_default
+    ON CONFLICT (member_id, role_id) DO NOTHING;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER server_members_assign_default_role
+    AFTER INSERT ON server_members
+    FOR EACH ROW
+    EXECUTE FUNCTION assign_default_role_to_member();
+
+-- The default role is only unassigned when the member or the role itself is removed
+CREATE OR REPLACE FUNCTION protect_default_role_assignment()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM roles WHERE id = OLD.role_id AND is_default)
+        AND EXISTS (SELECT 1 FROM server_members WHERE id = OLD.member_id) THEN
+        RAISE EXCEPTION 'The default role cannot be unassigned';
+    END IF;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER member_roles_protect_default_role
+    BEFORE DELETE ON member_roles
+    FOR EACH ROW
+    EXECUTE FUNCTION protect_default_role_assignment();
+
+-- The default role goes away with its server only, and only its permissions can be edited
+CREATE OR REPLACE FUNCTION protect_default_role()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        IF OLD.is_default AND EXISTS (SELECT 1 FROM servers WHERE id = OLD.server_id) THEN
+            RAISE EXCEPTION 'The default role cannot be deleted';
+        END IF;
+        RETURN OLD;
+    END IF;
+
+    IF NEW.is_default IS DISTINCT FROM OLD.is_default THEN
+        RAISE EXCEPTION 'A role cannot become or stop being the default role';
+    END IF;
+    IF OLD.is_default
+        AND (NEW.name, NEW.position, NEW.color, NEW.hoist, NEW.mentionable, NEW.has_icon)
+            IS DISTINCT FROM
+            (OLD.name, OLD.position, OLD.color, OLD.hoist, OLD.mentionable, OLD.has_icon) THEN
+        RAISE EXCEPTION 'Only the permissions of the default role can be edited';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER roles_protect_default_role
+    BEFORE UPDATE OR DELETE ON roles
+    FOR EACH ROW
+    EXECUTE FUNCTION protect_default_role();
@@ -22,7 +22,9 @@ use crate::{
         outbox::{MessageRouter, postgres::PostgresOutboxRepository},
         role::repositories::postgres::PostgresRoleRepository,
         server::repositories::postgres::PostgresServerRepository,
+        server_ban::repositories::postgres::PostgresServerBanRepository,
         server_invitation::repositories::postgres::PostgresServerInvitationRepository,
+        server_join_request::repositories::postgres::PostgresServerJoinRequestRepository,
         server_member::repositories::PostgresMemberRepository,
         server_pictures::repositories::reqwest::ReqwestServerPicturesRepository,
         user::repositories::http::HttpUserRepository,
@@ -48,6 +50,8 @@ pub type CommunitiesService = Service<
     PostgresServerInvitationRepository,
     SpiceDbAuthorizationRepository,
     ReqwestServerPicturesRepository,
+    PostgresServerBanRepository,
+    PostgresServerJoinRequestRepository,
 >;
 
 #[derive(Clone)]
@@ -67,6 +71,8 @@ pub struct CommunitiesRepositories {
     pub server_invitation_repository: PostgresServerInvitationRepository,
     pub authorization_repository: SpiceDbAuthorizationRepository,
     pub server_pictures_repository: ReqwestServerPicturesRepository,
+    pub server_ban_repository: PostgresServerBanRepository,
+    pub server_join_request_repository: PostgresServerJoinRequestRepository,
 }
 
 pub async fn create_repositories(
@@ -89,6 +95,7 @@ pub async fn create_repositories(
         message_routing_config.clone().upsert_role,
         message_routing_config.clone().user_join_server,
         message_routing_config.clone().member_assign_to_role,
+        message_routing_config.clone().create_channel,
     );
     let friendship_repository = PostgresFriendshipRepository::new(pool.clone());
     let user_repository = HttpUserRepository::new(beep_services.user_service_url);
@@ -98,11 +105,16 @@ pub async fn create_repositories(
         message_routing_config.clone().user_leave_server,
         message_routing_config.clone().user_join_server,
         message_routing_config.clone().member_assign_to_role,
+        message_routing_config.clone().member_timeout,
+        message_routing_config.clone().member_remove,
+        message_routing_config.clone().member_update,
     );
     let channel_repository = PostgresChannelRepository::new(
         pool.clone(),
         message_routing_config.clone().create_channel,
         message_routing_config.clone().delete_channel,
+        message_routing_config.clone().upsert_channel_overwrite,
+        message_routing_config.clone().delete_channel_overwrite,
     );
     let keycloak_repository = KeycloakAuthRepository::new(keycloak_issuer, None);
     let role_repository = PostgresRoleRepository::new(
@@ -110,6 +122,7 @@ pub async fn create_repositories(
         message_routing_config.clone().upsert_role,
         message_routing_config.clone().upsert_role,
         message_routing_config.clone().delete_role,
+        message_routing_config.clone().role_appearance,
     );
     let outbox_repository = PostgresOutboxRepository::new(pool.clone());
     let channel_member_repository = MockChannelMemberRepository::new();
@@ -119,6 +132,14 @@ pub async fn create_repositories(
         message_routing_config.clone().member_unassign_from_role,
     );
     let server_invitation_repository = PostgresServerInvitationRepository::new(pool.clone());
+    let server_join_request_repository = PostgresServerJoinRequestRepository::new(pool.clone());
+    let server_ban_repository = PostgresServerBanRepository::new(
+        pool.clone(),
+        message_routing_config.clone().member_ban,
+        message_routing_config.clone().member_unban,
+        message_routing_config.clone().user_leave_server,
+        message_routing_config.clone().member_remove,
+    );
     let spicedb_repository = SpiceDbRepository::new(spicedb_config)
         .await
         .map_err(|e| CoreError::ServiceUnavailable(e.to_string()))?;
@@ -140,6 +161,8 @@ pub async fn create_repositories(
         server_invitation_repository,
         authorization_repository,
         server_pictures_repository,
+        server_ban_repository,
+        server_join_request_repository,
     })
 }
 
@@ -159,6 +182,8 @@ impl From<CommunitiesRepositories> for CommunitiesService {
             repos.server_invitation_repository,
             repos.authorization_repository,
             repos.server_pictures_repository,
+            repos.server_ban_repository,
+            repos.server_join_request_repository,
         )
     }
 }
@@ -191,6 +216,7 @@ pub async fn create_repositories_with_mock_authz(
         message_routing_config.clone().upsert_role,
         message_routing_config.clone().user_join_server,
         message_routing_config.clone().member_assign_to_role,
+        message_routing_config.clone().create_channel,
     );
     let friendship_repository = PostgresFriendshipRepository::new(pool.clone());
     let user_repository = HttpUserRepository::new(beep_services.user_service_url);
@@ -200,11 +226,16 @@ pub async fn create_repositories_with_mock_authz(
         message_routing_config.clone().user_leave_server,
         message_routing_config.clone().user_join_server,
         message_routing_config.clone().member_assign_to_role,
+        message_routing_config.clone().member_timeout,
+        message_routing_config.clone().member_remove,
+        message_routing_config.clone().member_update,
     );
     let channel_repository = PostgresChannelRepository::new(
         pool.clone(),
         message_routing_config.clone().create_channel,
         message_routing_config.clone().delete_channel,
+        message_routing_config.clone().upsert_channel_overwrite,
+        message_routing_config.clone().delete_channel_overwrite,
     );
     let keycloak_repository = KeycloakAuthRepository::new(keycloak_issuer, None);
     let role_repository = PostgresRoleRepository::new(
@@ -212,6 +243,7 @@ pub async fn create_repositories_with_mock_authz(
         message_routing_config.clone().upsert_role,
         message_routing_config.clone().upsert_role,
         message_routing_config.clone().delete_role,
+        message_routing_config.clone().role_appearance,
     );
     let outbox_repository = PostgresOutboxRepository::new(pool.clone());
     let channel_member_repository = MockChannelMemberRepository::new();
@@ -221,6 +253,14 @@ pub async fn create_repositories_with_mock_authz(
         message_routing_config.clone().member_unassign_from_role,
     );
     let server_invitation_repository = PostgresServerInvitationRepository::new(pool.clone());
+    let server_join_request_repository = PostgresServerJoinRequestRepository::new(pool.clone());
+    let server_ban_repository = PostgresServerBanRepository::new(
+        pool.clone(),
+        message_routing_config.clone().member_ban,
+        message_routing_config.clone().member_unban,
+        message_routing_config.clone().user_leave_server,
+        message_routing_config.clone().member_remove,
+    );
 
     // Use mock authorization repository instead of SpiceDB
     let authorization_repository = SpiceDbAuthorizationRepository::new_mock();
@@ -243,6 +283,8 @@ pub async fn create_repositories_with_mock_authz(
         server_invitation_repository,
         authorization_repository,
         server_pictures_repository,
+        server_ban_repository,
+        server_join_request_repository,
     })
 }
 
@@ -269,8 +311,16 @@ pub struct MessageRoutingConfig {
     pub user_leave_server: MessageRoutingInfo,
     pub upsert_role: MessageRoutingInfo,
     pub delete_role: MessageRoutingInfo,
+    pub role_appearance: MessageRoutingInfo,
     pub member_assign_to_role: MessageRoutingInfo,
     pub member_unassign_from_role: MessageRoutingInfo,
+    pub member_ban: MessageRoutingInfo,
+    pub member_unban: MessageRoutingInfo,
+    pub member_timeout: MessageRoutingInfo,
+    pub member_remove: MessageRoutingInfo,
+    pub member_update: MessageRoutingInfo,
+    pub upsert_channel_overwrite: MessageRoutingInfo,
+    pub delete_channel_overwrite: MessageRoutingInfo,
 }
 
 impl MessageRoutingConfig {
@@ -286,6 +336,10 @@ impl MessageRoutingConfig {
         config.insert(self.delete_server.exchange_name(), Routing::DeleteServer);
         config.insert(self.upsert_role.exchange_name(), Routing::UpsertRole);
         config.insert(self.delete_role.exchange_name(), Routing::DeleteRole);
+        config.insert(
+            self.role_appearance.exchange_name(),
+            Routing::RoleAppearance,
+        );
         config.insert(
             self.user_join_server.exchange_name(),
             Routing::UserJoinServer,
@@ -303,6 +357,19 @@ impl MessageRoutingConfig {
             self.member_unassign_from_role.exchange_name(),
             Routing::MemberUnassignFromRole,
         );
+        config.insert(self.member_ban.exchange_name(), Routing::MemberBan);
+        config.insert(self.member_unban.exchange_name(), Routing::MemberUnban);
+        config.insert(self.member_timeout.exchange_name(), Routing::MemberTimeout);
+        config.insert(self.member_remove.exchange_name(), Routing::MemberRemove);
+        config.insert(self.member_update.exchange_name(), Routing::MemberUpdate);
+        config.insert(
+            self.upsert_channel_overwrite.exchange_name(),
+            Routing::UpsertChannelOverwrite,
+        );
+        config.insert(
+            self.delete_channel_overwrite.exchange_name(),
+            Routing::DeleteChannelOverwrite,
+        );
         config
     }
 }
@@ -317,6 +384,14 @@ pub enum Routing {
     UserLeaveServer,
     UpsertRole,
     DeleteRole,
+    RoleAppearance,
     MemberAssignToRole,
     MemberUnassignFromRole,
+    MemberBan,
+    MemberUnban,
+    MemberTimeout,
+    MemberRemove,
+    MemberUpdate,
+    UpsertChannelOverwrite,
+    DeleteChannelOverwrite,
 }
@@ -60,6 +60,34 @@ pub trait AuthorizationService: Send + Sync {
         user_id: UserId,
         server_id: ServerId,
     ) -> impl Future<Output = Result<bool, CoreError>>;
+
+    /// Whether the user can time out members, resolved from the permission bits of their roles
+    fn can_moderate_members(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>>;
+
+    /// Whether the user can kick members, resolved from the permission bits of their roles
+    fn can_kick_members(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>>;
+
+    /// Whether the user can ban and unban members, resolved from the permission bits of their roles
+    fn can_ban_members(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>>;
+
+    /// Whether the user owns the server or holds a role with the administrator bit
+    fn is_server_administrator(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>>;
 }
 
 #[derive(Clone, Debug)]
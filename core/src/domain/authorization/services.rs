@@ -12,17 +12,19 @@ use crate::{
         health::port::HealthRepository,
         member_role::ports::MemberRoleRepository,
         outbox::ports::OutboxRepository,
-        role::ports::RoleRepository,
+        role::{entities::Permission, ports::RoleRepository},
         server::{entities::ServerId, ports::ServerRepository},
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
+        server_join_request::ports::ServerJoinRequestRepository,
         server_member::MemberRepository,
         server_pictures::ServerPicturesRepository,
         user::port::UserRepository,
     },
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> AuthorizationService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> AuthorizationService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -37,6 +39,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     fn check_authz(
         &self,
@@ -134,4 +138,76 @@ where
             SpiceDbObject::Server(server_id.to_string()),
         )
     }
+
+    fn can_moderate_members(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>> {
+        self.holds_permission(user_id, server_id, Permission::ModerateMembers)
+    }
+
+    fn can_kick_members(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>> {
+        self.holds_permission(user_id, server_id, Permission::KickMembers)
+    }
+
+    fn can_ban_members(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>> {
+        self.holds_permission(user_id, server_id, Permission::BanMembers)
+    }
+
+    fn is_server_administrator(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>> {
+        self.holds_permission(user_id, server_id, Permission::Administrator)
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// Whether the user owns the server or holds `permission` through one of their roles.
+    /// The authorization schema has no relation for the newer bits, so they are resolved here
+    async fn holds_permission(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+        permission: Permission,
+    ) -> Result<bool, CoreError> {
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        if server.owner_id == user_id {
+            return Ok(true);
+        }
+
+        let roles = self
+            .member_role_repository
+            .list_roles_by_user_and_server(user_id, server_id)
+            .await?;
+        Ok(roles.iter().any(|role| role.permissions.has(permission)))
+    }
 }
@@ -7,7 +7,12 @@ use thiserror::Error;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::server::entities::ServerId;
+use crate::domain::{
+    common::CoreError,
+    role::entities::{Permission, Permissions, RoleId},
+    server::entities::ServerId,
+    server_member::MemberId,
+};
 
 pub const MAX_CHANNEL_NAME_SIZE: usize = 30;
 
@@ -260,3 +265,256 @@ impl UpdateChannelRequest {
         }
     }
 }
+
+/// Subject of a channel permission overwrite
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum OverwriteTarget {
+    Role(RoleId),
+    Member(MemberId),
+}
+
+/// Allow and deny bits applied on top of server-wide permissions in one channel
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PermissionOverwrite {
+    pub channel_id: ChannelId,
+    pub target: OverwriteTarget,
+    pub allow: Permissions,
+    pub deny: Permissions,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for PermissionOverwrite {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        let role_id: Option<Uuid> = row.get("role_id");
+        let member_id: Option<Uuid> = row.get("member_id");
+        let target = match (role_id, member_id) {
+            (Some(role_id), _) => OverwriteTarget::Role(RoleId(role_id)),
+            (None, member_id) => OverwriteTarget::Member(MemberId(member_id.unwrap_or_default())),
+        };
+        Self {
+            channel_id: ChannelId(row.get("channel_id")),
+            target,
+            allow: Permissions(row.get("allow_permissions")),
+            deny: Permissions(row.get("deny_permissions")),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PermissionOverwriteRequest {
+    pub allow: i64,
+    pub deny: i64,
+}
+
+impl PermissionOverwriteRequest {
+    pub fn into_input(
+        self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+    ) -> Result<UpsertPermissionOverwriteInput, CoreError> {
+        let invalid = |msg: String| CoreError::InvalidPermissionOverwrite { msg };
+        let allow = Permissions::try_from(self.allow).map_err(|e| invalid(e.to_string()))?;
+        let deny = Permissions::try_from(self.deny).map_err(|e| invalid(e.to_string()))?;
+        if *allow & *deny != 0 {
+            return Err(invalid(
+                "a permission cannot be both allowed and denied".to_string(),
+            ));
+        }
+        Ok(UpsertPermissionOverwriteInput {
+            channel_id,
+            target,
+            allow,
+            deny,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertPermissionOverwriteInput {
+    pub channel_id: ChannelId,
+    pub target: OverwriteTarget,
+    pub allow: Permissions,
+    pub deny: Permissions,
+}
+
+/// Event emitted when a permission overwrite is removed from a channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletePermissionOverwriteEvent {
+    pub channel_id: ChannelId,
+    pub target: OverwriteTarget,
+}
+
+/// The member permissions are computed for, with every role they hold
+pub struct OverwriteSubject<'a> {
+    pub member_id: MemberId,
+    pub default_role_id: RoleId,
+    pub role_ids: &'a [RoleId],
+}
+
+/// Narrows or widens server-wide permissions with the overwrites of a channel.
+/// `levels` holds the overwrites of the outermost folder first and of the channel itself last,
+/// so a channel inherits the overwrites of its folders and can override them.
+/// On each level the default role overwrite applies first, then the other roles of the subject
+/// (their denies merged, then their allows), then the overwrite of the member.
+/// Administrators are never restricted.
+pub fn compute_channel_permissions(
+    base: &Permissions,
+    levels: &[Vec<PermissionOverwrite>],
+    subject: &OverwriteSubject,
+) -> Permissions {
+    if base.has(Permission::Administrator) {
+        return base.clone();
+    }
+
+    let apply = |bits: i64, allow: i64, deny: i64| (bits & !deny) | allow;
+    let mut bits = **base;
+    for overwrites in levels {
+        if let Some(overwrite) = overwrites
+            .iter()
+            .find(|overwrite| overwrite.target == OverwriteTarget::Role(subject.default_role_id))
+        {
+            bits = apply(bits, *overwrite.allow, *overwrite.deny);
+        }
+
+        let (allow, deny) = overwrites
+            .iter()
+            .filter(|overwrite| match overwrite.target {
+                OverwriteTarget::Role(role_id) => {
+                    role_id != subject.default_role_id && subject.role_ids.contains(&role_id)
+                }
+                OverwriteTarget::Member(_) => false,
+            })
+            .fold((0, 0), |(allow, deny), overwrite| {
+                (allow | *overwrite.allow, deny | *overwrite.deny)
+            });
+        bits = apply(bits, allow, deny);
+
+        if let Some(overwrite) = overwrites
+            .iter()
+            .find(|overwrite| overwrite.target == OverwriteTarget::Member(subject.member_id))
+        {
+            bits = apply(bits, *overwrite.allow, *overwrite.deny);
+        }
+    }
+    Permissions(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overwrite(target: OverwriteTarget, allow: i64, deny: i64) -> PermissionOverwrite {
+        PermissionOverwrite {
+            channel_id: ChannelId(Uuid::new_v4()),
+            target,
+            allow: Permissions(allow),
+            deny: Permissions(deny),
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_private_staff_channel() {
+        let default_role_id = RoleId(Uuid::new_v4());
+        let staff_role_id = RoleId(Uuid::new_v4());
+        let member_id = MemberId(Uuid::new_v4());
+        let base = Permissions(0x40 | 0x80);
+        let levels = vec![vec![
+            overwrite(OverwriteTarget::Role(default_role_id), 0, 0x40),
+            overwrite(OverwriteTarget::Role(staff_role_id), 0x40, 0),
+        ]];
+
+        let member = OverwriteSubject {
+            member_id,
+            default_role_id,
+            role_ids: &[default_role_id],
+        };
+        assert_eq!(compute_channel_permissions(&base, &levels, &member).0, 0x80);
+
+        let staff = OverwriteSubject {
+            member_id,
+            default_role_id,
+            role_ids: &[default_role_id, staff_role_id],
+        };
+        assert_eq!(
+            compute_channel_permissions(&base, &levels, &staff).0,
+            0x40 | 0x80
+        );
+    }
+
+    #[test]
+    fn test_channel_overrides_its_folder_and_member_overrides_roles() {
+        let default_role_id = RoleId(Uuid::new_v4());
+        let member_id = MemberId(Uuid::new_v4());
+        let base = Permissions(0x40 | 0x80);
+        let subject = OverwriteSubject {
+            member_id,
+            default_role_id,
+            role_ids: &[default_role_id],
+        };
+
+        // The folder hides itself, the channel opens back up
+        let folder = vec![overwrite(OverwriteTarget::Role(default_role_id), 0, 0x40)];
+        let channel = vec![overwrite(OverwriteTarget::Role(default_role_id), 0x40, 0)];
+        assert_eq!(
+            compute_channel_permissions(&base, &[folder.clone()], &subject).0,
+            0x80
+        );
+        assert_eq!(
+            compute_channel_permissions(&base, &[folder, channel], &subject).0,
+            0x40 | 0x80
+        );
+
+        // A member overwrite wins over role overwrites of the same level
+        let levels = vec![vec![
+            overwrite(OverwriteTarget::Role(default_role_id), 0, 0x80),
+            overwrite(OverwriteTarget::Member(member_id), 0x80, 0),
+        ]];
+        assert_eq!(
+            compute_channel_permissions(&base, &levels, &subject).0,
+            0x40 | 0x80
+        );
+    }
+
+    #[test]
+    fn test_administrators_are_never_restricted() {
+        let default_role_id = RoleId(Uuid::new_v4());
+        let subject = OverwriteSubject {
+            member_id: MemberId(Uuid::new_v4()),
+            default_role_id,
+            role_ids: &[default_role_id],
+        };
+        let levels = vec![vec![overwrite(
+            OverwriteTarget::Role(default_role_id),
+            0,
+            0x40,
+        )]];
+        assert_eq!(
+            compute_channel_permissions(&Permissions(0x1), &levels, &subject).0,
+            0x1
+        );
+    }
+
+    #[test]
+    fn test_overwrite_request_rejects_overlapping_bits() {
+        let request = PermissionOverwriteRequest {
+            allow: 0x40,
+            deny: 0x40 | 0x80,
+        };
+        let result = request.into_input(
+            ChannelId(Uuid::new_v4()),
+            OverwriteTarget::Member(MemberId(Uuid::new_v4())),
+        );
+        assert!(matches!(
+            result,
+            Err(CoreError::InvalidPermissionOverwrite { .. })
+        ));
+    }
+}
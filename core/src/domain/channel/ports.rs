@@ -4,12 +4,15 @@ use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::domain::channel::entities::{
-    CreateChannelRepoInput, CreatePrivateChannelInput, CreateServerChannelInput,
-    UpdateChannelInput, UpdateChannelRepoInput,
+    CreateChannelRepoInput, CreatePrivateChannelInput, CreateServerChannelInput, OverwriteTarget,
+    PermissionOverwrite, PermissionOverwriteRequest, UpdateChannelInput, UpdateChannelRepoInput,
+    UpsertPermissionOverwriteInput,
 };
 use crate::domain::{
     channel::entities::{Channel, ChannelId},
     common::CoreError,
+    friend::entities::UserId,
+    role::entities::Permissions,
     server::entities::ServerId,
 };
 
@@ -31,6 +34,20 @@ pub trait ChannelRepository: Send + Sync {
         &self,
         channel_id: ChannelId,
     ) -> impl Future<Output = Result<Channel, CoreError>> + Send;
+    fn list_permission_overwrites(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<Vec<PermissionOverwrite>, CoreError>> + Send;
+    /// Create or replace the overwrite of a role or member in a channel
+    fn upsert_permission_overwrite(
+        &self,
+        input: UpsertPermissionOverwriteInput,
+    ) -> impl Future<Output = Result<PermissionOverwrite, CoreError>> + Send;
+    fn delete_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
 }
 
 pub trait ChannelService: Send + Sync {
@@ -58,24 +75,53 @@ pub trait ChannelService: Send + Sync {
         &self,
         channel_id: ChannelId,
     ) -> impl Future<Output = Result<Channel, CoreError>> + Send;
+    fn list_permission_overwrites(
+        &self,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<Vec<PermissionOverwrite>, CoreError>> + Send;
+    /// Set the overwrite of a role or member of the channel server.
+    /// Like role management, the actor only targets roles below their highest one
+    /// and only allows or denies bits they hold
+    fn upsert_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+        request: PermissionOverwriteRequest,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<PermissionOverwrite, CoreError>> + Send;
+    fn delete_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+    /// Permissions of a user in a server channel, overwrites of the channel and its folders applied
+    fn get_channel_permissions(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<Permissions, CoreError>> + Send;
 }
 
 /// Mock implementation of ChannelRepository for testing
 #[derive(Clone)]
 pub struct MockChannelRepository {
     channels: Arc<Mutex<Vec<Channel>>>,
+    overwrites: Arc<Mutex<Vec<PermissionOverwrite>>>,
 }
 
 impl MockChannelRepository {
     pub fn new() -> Self {
         Self {
             channels: Arc::new(Mutex::new(Vec::new())),
+            overwrites: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
     pub fn with_channels(channels: Vec<Channel>) -> Self {
         Self {
             channels: Arc::new(Mutex::new(channels)),
+            overwrites: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -142,6 +188,10 @@ impl ChannelRepository for MockChannelRepository {
         if channels.len() == initial_len {
             Err(CoreError::ChannelNotFound { id: channel_id })
         } else {
+            self.overwrites
+                .lock()
+                .unwrap()
+                .retain(|o| o.channel_id != channel_id);
             Ok(())
         }
     }
@@ -154,4 +204,60 @@ impl ChannelRepository for MockChannelRepository {
             .cloned()
             .ok_or(CoreError::ChannelNotFound { id: channel_id })
     }
+
+    async fn list_permission_overwrites(
+        &self,
+        channel_id: ChannelId,
+    ) -> Result<Vec<PermissionOverwrite>, CoreError> {
+        let overwrites = self.overwrites.lock().unwrap();
+        Ok(overwrites
+            .iter()
+            .filter(|o| o.channel_id == channel_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_permission_overwrite(
+        &self,
+        input: UpsertPermissionOverwriteInput,
+    ) -> Result<PermissionOverwrite, CoreError> {
+        use chrono::Utc;
+        let mut overwrites = self.overwrites.lock().unwrap();
+        if let Some(existing) = overwrites
+            .iter_mut()
+            .find(|o| o.channel_id == input.channel_id && o.target == input.target)
+        {
+            existing.allow = input.allow;
+            existing.deny = input.deny;
+            existing.updated_at = Some(Utc::now());
+            return Ok(existing.clone());
+        }
+
+        let overwrite = PermissionOverwrite {
+            channel_id: input.channel_id,
+            target: input.target,
+            allow: input.allow,
+            deny: input.deny,
+            created_at: Utc::now(),
+            updated_at: None,
+        };
+        overwrites.push(overwrite.clone());
+        Ok(overwrite)
+    }
+
+    async fn delete_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+    ) -> Result<(), CoreError> {
+        let mut overwrites = self.overwrites.lock().unwrap();
+        let initial_len = overwrites.len();
+        overwrites.retain(|o| !(o.channel_id == channel_id && o.target == target));
+
+        if overwrites.len() == initial_len {
+            Err(CoreError::PermissionOverwriteNotFound { channel_id })
+        } else {
+            Ok(())
+        }
+    }
 }
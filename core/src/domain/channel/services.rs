@@ -5,27 +5,37 @@ use crate::{
         channel::{
             entities::{
                 Channel, ChannelError, ChannelId, ChannelType, CreateChannelRepoInput,
-                CreatePrivateChannelInput, CreateServerChannelInput, UpdateChannelInput,
+                CreatePrivateChannelInput, CreateServerChannelInput, OverwriteSubject,
+                OverwriteTarget, PermissionOverwrite, PermissionOverwriteRequest,
+                UpdateChannelInput, compute_channel_permissions,
             },
             ports::{ChannelRepository, ChannelService},
         },
         channel_member::ports::ChannelMemberRepository,
         common::CoreError,
-        friend::ports::FriendshipRepository,
+        friend::{entities::UserId, ports::FriendshipRepository},
         health::port::HealthRepository,
         member_role::ports::MemberRoleRepository,
         outbox::ports::OutboxRepository,
-        role::ports::RoleRepository,
+        role::{
+            entities::{Permissions, RoleAuthority, RoleId},
+            ports::{RoleRepository, RoleService},
+        },
         server::{entities::ServerId, ports::ServerRepository},
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
+        server_join_request::ports::ServerJoinRequestRepository,
         server_member::MemberRepository,
         server_pictures::ServerPicturesRepository,
         user::port::UserRepository,
     },
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> ChannelService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+/// Bound on the folders walked up from a channel, guarding against parent cycles
+const MAX_CHANNEL_DEPTH: usize = 8;
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> ChannelService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -40,6 +50,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn create_private_channel(
         &self,
@@ -103,4 +115,154 @@ where
     async fn get_channel_by_id(&self, channel_id: ChannelId) -> Result<Channel, CoreError> {
         self.channel_repository.find_by_id(channel_id).await
     }
+
+    async fn list_permission_overwrites(
+        &self,
+        channel_id: ChannelId,
+    ) -> Result<Vec<PermissionOverwrite>, CoreError> {
+        self.channel_repository
+            .list_permission_overwrites(channel_id)
+            .await
+    }
+
+    async fn upsert_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+        request: PermissionOverwriteRequest,
+        actor_id: UserId,
+    ) -> Result<PermissionOverwrite, CoreError> {
+        let input = request.into_input(channel_id, target)?;
+        let server_id = self.overwrite_server(channel_id).await?;
+        let authority = self.role_authority(server_id, actor_id).await?;
+        self.check_overwrite_target(server_id, target, &authority)
+            .await?;
+        authority.check_permissions(&Permissions(*input.allow | *input.deny))?;
+        self.channel_repository
+            .upsert_permission_overwrite(input)
+            .await
+    }
+
+    async fn delete_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+        actor_id: UserId,
+    ) -> Result<(), CoreError> {
+        let server_id = self.overwrite_server(channel_id).await?;
+        let authority = self.role_authority(server_id, actor_id).await?;
+        self.check_overwrite_target(server_id, target, &authority)
+            .await?;
+        self.channel_repository
+            .delete_permission_overwrite(channel_id, target)
+            .await
+    }
+
+    async fn get_channel_permissions(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<Permissions, CoreError> {
+        let server_id = self.overwrite_server(channel_id).await?;
+        let effective = self.get_effective_permissions(server_id, user_id).await?;
+        if effective.is_owner {
+            return Ok(effective.permissions);
+        }
+
+        let member = self
+            .member_repository
+            .find_by_server_and_user(&server_id, &user_id)
+            .await?;
+        let roles = self
+            .member_role_repository
+            .list_roles_by_user_and_server(user_id, server_id)
+            .await?;
+        let role_ids: Vec<RoleId> = roles.iter().map(|role| role.id).collect();
+
+        // Walk up the folders, the outermost one ending first
+        let mut levels = Vec::new();
+        let mut next = Some(channel_id);
+        while let Some(current) = next {
+            if levels.len() > MAX_CHANNEL_DEPTH {
+                break;
+            }
+            levels.push(
+                self.channel_repository
+                    .list_permission_overwrites(current)
+                    .await?,
+            );
+            next = self.channel_repository.find_by_id(current).await?.parent_id;
+        }
+        levels.reverse();
+
+        Ok(compute_channel_permissions(
+            &effective.permissions,
+            &levels,
+            &OverwriteSubject {
+                member_id: member.id,
+                default_role_id: RoleId(*server_id),
+                role_ids: &role_ids,
+            },
+        ))
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// Server of a channel holding overwrites, private channels having none
+    async fn overwrite_server(&self, channel_id: ChannelId) -> Result<ServerId, CoreError> {
+        self.channel_repository
+            .find_by_id(channel_id)
+            .await?
+            .server_id
+            .ok_or_else(|| CoreError::InvalidPermissionOverwrite {
+                msg: "private channels have no permission overwrites".to_string(),
+            })
+    }
+
+    /// The target must belong to the channel server and rank below the actor
+    async fn check_overwrite_target(
+        &self,
+        server_id: ServerId,
+        target: OverwriteTarget,
+        authority: &RoleAuthority,
+    ) -> Result<(), CoreError> {
+        match target {
+            OverwriteTarget::Role(role_id) => {
+                let role = self.role_repository.find_by_id(&role_id).await?;
+                if role.server_id != server_id {
+                    return Err(CoreError::InvalidPermissionOverwrite {
+                        msg: format!("role {} is not a role of the server", role_id),
+                    });
+                }
+                authority.check_position(role.position)
+            }
+            OverwriteTarget::Member(member_id) => {
+                let member = self.member_repository.find_by_id(member_id).await?;
+                if member.server_id != server_id {
+                    return Err(CoreError::InvalidPermissionOverwrite {
+                        msg: format!("member {} is not a member of the server", member_id),
+                    });
+                }
+                authority.check_position(self.member_rank(server_id, member.user_id).await?)
+            }
+        }
+    }
 }
@@ -1,11 +1,15 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use thiserror::Error;
 use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
 
 use crate::domain::channel::entities::{ChannelError, ChannelId};
 use crate::domain::friend::entities::UserId;
 use crate::domain::role::entities::RoleId;
-use crate::domain::server::entities::ServerId;
+use crate::domain::server::entities::{ServerId, ServerTemplateId};
+use crate::domain::server_join_request::entities::ServerJoinRequestId;
 use crate::domain::server_member::MemberId;
 
 pub mod services;
@@ -91,11 +95,107 @@ pub enum CoreError {
     #[error("Default role basic user cannot be deleted")]
     DefaultRoleDeletion,
 
+    #[error("The default role is held by every member and cannot be assigned or unassigned")]
+    DefaultRoleAssignment,
+
+    #[error("Only the permissions of the default role can be edited")]
+    DefaultRoleUpdate,
+
+    #[error("Roles can only be managed below your highest role")]
+    RoleHierarchy,
+
+    #[error("Cannot grant permissions you do not hold")]
+    PermissionEscalation,
+
+    #[error("Invalid role order: {msg}")]
+    InvalidRoleOrder { msg: String },
+
+    #[error("Invalid role color {color}: expected an RGB value between 0 and 0xFFFFFF")]
+    InvalidRoleColor { color: i32 },
+
+    #[error("Invalid role expiry: {msg}")]
+    InvalidRoleExpiry { msg: String },
+
+    #[error("Invalid permission overwrite: {msg}")]
+    InvalidPermissionOverwrite { msg: String },
+
+    #[error("Permission overwrite not found in channel {channel_id}")]
+    PermissionOverwriteNotFound { channel_id: ChannelId },
+
     #[error("Could not parse content url: {part}")]
     ParseContentUrl { part: String },
 
     #[error("Could not parse content url: {err}")]
     FailedToGetSignedUrl { err: String },
+
+    #[error("Server template with id {id} not found")]
+    ServerTemplateNotFound { id: ServerTemplateId },
+
+    #[error("Invalid server template: {msg}")]
+    InvalidServerTemplate { msg: String },
+
+    #[error("Invalid server export: {msg}")]
+    InvalidServerExport { msg: String },
+
+    #[error("Invalid vanity slug: {msg}")]
+    InvalidVanitySlug { msg: String },
+
+    #[error("Vanity slug {slug} is already taken")]
+    VanitySlugAlreadyTaken { slug: String },
+
+    #[error("Vanity slug {slug} not found")]
+    VanitySlugNotFound { slug: String },
+
+    #[error("Server {server_id} has no vanity slug")]
+    ServerVanityNotFound { server_id: ServerId },
+
+    #[error("Invalid server settings: {msg}")]
+    InvalidServerSettings { msg: String },
+
+    #[error("Invalid server ban: {msg}")]
+    InvalidServerBan { msg: String },
+
+    #[error("User {user_id} is not banned from server {server_id}")]
+    ServerBanNotFound {
+        server_id: ServerId,
+        user_id: UserId,
+    },
+
+    #[error("User {user_id} is banned from server {server_id}")]
+    UserBanned {
+        server_id: ServerId,
+        user_id: UserId,
+    },
+
+    #[error("Invalid member profile: {msg}")]
+    InvalidMemberProfile { msg: String },
+
+    #[error("Invalid member timeout: {msg}")]
+    InvalidMemberTimeout { msg: String },
+
+    #[error("Invalid member removal: {msg}")]
+    InvalidMemberRemoval { msg: String },
+
+    #[error("Invalid pagination cursor")]
+    InvalidCursor,
+
+    #[error("Invalid bulk request: {msg}")]
+    InvalidBulkRequest { msg: String },
+
+    #[error("Invalid join request: {msg}")]
+    InvalidJoinRequest { msg: String },
+
+    #[error("Join request not found")]
+    JoinRequestNotFound,
+
+    #[error("User {user_id} already has a pending join request for server {server_id}")]
+    JoinRequestAlreadyPending {
+        server_id: ServerId,
+        user_id: UserId,
+    },
+
+    #[error("Join request with id {id} has already been reviewed")]
+    JoinRequestAlreadyReviewed { id: ServerJoinRequestId },
 }
 
 impl From<ChannelError> for CoreError {
@@ -169,3 +269,77 @@ impl Default for GetPaginated {
 }
 
 pub type TotalPaginatedElements = u64;
+
+/// Keyset position of the last element of a page, ordered by timestamp then id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Cursor {
+    pub at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { at, id }
+    }
+
+    /// Opaque representation handed to clients
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", self.at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(value: &str) -> Result<Self, CoreError> {
+        let decoded = URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| CoreError::InvalidCursor)?;
+        let decoded = String::from_utf8(decoded).map_err(|_| CoreError::InvalidCursor)?;
+        let (at, id) = decoded.split_once(':').ok_or(CoreError::InvalidCursor)?;
+        let at = at
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or(CoreError::InvalidCursor)?;
+        let id = Uuid::parse_str(id).map_err(|_| CoreError::InvalidCursor)?;
+        Ok(Self { at, id })
+    }
+}
+
+/// Cursor pagination, used instead of the page when `cursor` is present
+#[derive(Debug, Deserialize, Default, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct GetCursor {
+    /// `next_cursor` of the previous response, an empty value starts from the first element
+    pub cursor: Option<String>,
+}
+
+impl GetCursor {
+    /// `None` in page mode, `Some(None)` for the first cursor page
+    pub fn decode(&self) -> Result<Option<Option<Cursor>>, CoreError> {
+        match self.cursor.as_deref() {
+            None => Ok(None),
+            Some("") => Ok(Some(None)),
+            Some(cursor) => Cursor::decode(cursor).map(|cursor| Some(Some(cursor))),
+        }
+    }
+}
+
+/// Elements of a cursor page with the position to resume from, if any element is left
+pub type CursorPage<T> = (Vec<T>, TotalPaginatedElements, Option<Cursor>);
+
+/// Build a cursor page from up to `limit + 1` fetched elements, the extra one only
+/// telling that another page exists
+pub fn next_page<T>(
+    mut elements: Vec<T>,
+    limit: u32,
+    cursor: impl Fn(&T) -> Cursor,
+    total: TotalPaginatedElements,
+) -> CursorPage<T> {
+    let has_more = elements.len() > limit as usize;
+    elements.truncate(limit as usize);
+    let next_cursor = if has_more {
+        elements.last().map(cursor)
+    } else {
+        None
+    };
+    (elements, total, next_cursor)
+}
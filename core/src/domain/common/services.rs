@@ -3,12 +3,14 @@ use crate::domain::{
     channel_member::ports::ChannelMemberRepository, friend::ports::FriendshipRepository,
     health::port::HealthRepository, member_role::ports::MemberRoleRepository,
     outbox::ports::OutboxRepository, role::ports::RoleRepository, server::ports::ServerRepository,
-    server_invitation::ports::ServerInvitationRepository, server_member::ports::MemberRepository,
-    server_pictures::ServerPicturesRepository, user::port::UserRepository,
+    server_ban::ports::ServerBanRepository, server_invitation::ports::ServerInvitationRepository,
+    server_join_request::ports::ServerJoinRequestRepository,
+    server_member::ports::MemberRepository, server_pictures::ServerPicturesRepository,
+    user::port::UserRepository,
 };
 
 #[derive(Clone, Debug)]
-pub struct Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+pub struct Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -23,6 +25,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     pub(crate) server_repository: S,
     pub(crate) friendship_repository: F,
@@ -37,9 +41,12 @@ where
     pub(crate) server_invitation_repository: SI,
     pub(crate) authorization_repository: A,
     pub(crate) server_pictures_repository: SC,
+    pub(crate) server_ban_repository: SB,
+    pub(crate) server_join_request_repository: JR,
 }
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -54,6 +61,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     pub fn new(
         server_repository: S,
@@ -69,6 +78,8 @@ where
         server_invitation_repository: SI,
         authorization_repository: A,
         server_pictures_repository: SC,
+        server_ban_repository: SB,
+        server_join_request_repository: JR,
     ) -> Self {
         Self {
             server_repository,
@@ -84,6 +95,8 @@ where
             server_invitation_repository,
             authorization_repository,
             server_pictures_repository,
+            server_ban_repository,
+            server_join_request_repository,
         }
     }
 }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::common::CoreError;
+use crate::domain::common::{CoreError, Cursor};
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[schema(value_type = String)]
@@ -68,6 +68,22 @@ pub struct Friend {
     pub created_at: DateTime<Utc>,
 }
 
+impl Friend {
+    /// The other user of the friendship, seen from `user_id`
+    pub fn friend_of(&self, user_id: &UserId) -> UserId {
+        if &self.user_id_1 == user_id {
+            self.user_id_2
+        } else {
+            self.user_id_1
+        }
+    }
+
+    /// Keyset position in the friend list of `user_id`
+    pub fn cursor_for(&self, user_id: &UserId) -> Cursor {
+        Cursor::new(self.created_at, self.friend_of(user_id).0)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateFriendInput {
     pub user_id_1: UserId,
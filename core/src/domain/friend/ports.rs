@@ -4,7 +4,7 @@ use chrono::Utc;
 
 use crate::{
     domain::{
-        common::{GetPaginated, TotalPaginatedElements},
+        common::{Cursor, CursorPage, GetPaginated, TotalPaginatedElements, next_page},
         friend::entities::{DeleteFriendInput, Friend, FriendRequest, UserId},
     },
     infrastructure::friend::repositories::error::FriendshipError,
@@ -18,6 +18,14 @@ pub trait FriendshipRepository: Send + Sync {
         user_id: &UserId,
     ) -> impl Future<Output = Result<(Vec<Friend>, TotalPaginatedElements), FriendshipError>> + Send;
 
+    /// Friends after the cursor, keyed by friendship date then friend id
+    fn list_friends_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<CursorPage<Friend>, FriendshipError>> + Send;
+
     fn get_friend(
         &self,
         user_id_1: &UserId,
@@ -94,6 +102,13 @@ pub trait FriendService: Send + Sync {
         user_id: &UserId,
     ) -> impl Future<Output = Result<(Vec<Friend>, TotalPaginatedElements), FriendshipError>> + Send;
 
+    fn get_friends_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<CursorPage<Friend>, FriendshipError>> + Send;
+
     fn delete_friend(
         &self,
         input: DeleteFriendInput,
@@ -178,6 +193,36 @@ impl FriendshipRepository for MockFriendshipRepository {
         Ok((paginated_friends, total))
     }
 
+    async fn list_friends_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: &UserId,
+    ) -> Result<CursorPage<Friend>, FriendshipError> {
+        let friends = self.friends.lock().unwrap();
+
+        let mut filtered_friends: Vec<Friend> = friends
+            .iter()
+            .filter(|friend| &friend.user_id_1 == user_id || &friend.user_id_2 == user_id)
+            .cloned()
+            .collect();
+        let total = filtered_friends.len() as TotalPaginatedElements;
+        filtered_friends.sort_by_key(|friend| std::cmp::Reverse(friend.cursor_for(user_id)));
+
+        let page: Vec<Friend> = filtered_friends
+            .into_iter()
+            .filter(|friend| after.is_none_or(|after| friend.cursor_for(user_id) < after))
+            .take(limit as usize + 1)
+            .collect();
+
+        Ok(next_page(
+            page,
+            limit,
+            |friend| friend.cursor_for(user_id),
+            total,
+        ))
+    }
+
     async fn get_friend(
         &self,
         user_id_1: &UserId,
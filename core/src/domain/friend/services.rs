@@ -3,7 +3,7 @@ use crate::{
         authorization::ports::AuthorizationRepository,
         channel::ports::ChannelRepository,
         channel_member::ports::ChannelMemberRepository,
-        common::{GetPaginated, TotalPaginatedElements, services::Service},
+        common::{Cursor, CursorPage, GetPaginated, TotalPaginatedElements, services::Service},
         friend::{
             entities::{DeleteFriendInput, Friend, FriendRequest, UserId},
             ports::{FriendRequestService, FriendService, FriendshipRepository},
@@ -13,7 +13,9 @@ use crate::{
         outbox::ports::OutboxRepository,
         role::ports::RoleRepository,
         server::ports::ServerRepository,
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
+        server_join_request::ports::ServerJoinRequestRepository,
         server_member::ports::MemberRepository,
         server_pictures::ServerPicturesRepository,
         user::port::UserRepository,
@@ -23,8 +25,8 @@ use crate::{
 
 use tracing::error;
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> FriendService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> FriendService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -39,6 +41,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn get_friends(
         &self,
@@ -50,13 +54,24 @@ where
             .await
     }
 
+    async fn get_friends_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: &UserId,
+    ) -> Result<CursorPage<Friend>, FriendshipError> {
+        self.friendship_repository
+            .list_friends_after(after, limit, user_id)
+            .await
+    }
+
     async fn delete_friend(&self, input: DeleteFriendInput) -> Result<(), FriendshipError> {
         self.friendship_repository.remove_friend(input).await
     }
 }
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> FriendRequestService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> FriendRequestService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -71,6 +86,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn get_friend_requests(
         &self,
@@ -12,14 +12,16 @@ use crate::domain::{
     outbox::ports::OutboxRepository,
     role::ports::RoleRepository,
     server::ports::ServerRepository,
+    server_ban::ports::ServerBanRepository,
     server_invitation::ports::ServerInvitationRepository,
+    server_join_request::ports::ServerJoinRequestRepository,
     server_member::ports::MemberRepository,
     server_pictures::ServerPicturesRepository,
     user::port::UserRepository,
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> HealthService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> HealthService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -34,6 +36,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn check_health(&self) -> Result<IsHealthy, CoreError> {
         self.health_repository.ping().await.to_result()
@@ -1,8 +1,14 @@
 use chrono::{DateTime, Utc};
 use events_protobuf::communities_events::{MemberAssignedToRole, MemberRemovedFromRole};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-use crate::domain::{friend::entities::UserId, role::entities::RoleId, server_member::MemberId};
+use crate::domain::{
+    common::CoreError,
+    friend::entities::UserId,
+    role::entities::{Role, RoleId},
+    server_member::MemberId,
+};
 
 #[derive(Debug, Serialize, Clone)]
 pub struct MemberRole {
@@ -10,12 +16,97 @@ pub struct MemberRole {
     pub role_id: RoleId,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// The role is unassigned once this date is past, held indefinitely when absent
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for MemberRole {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            member_id: MemberId(row.get("member_id")),
+            role_id: RoleId(row.get("role_id")),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            expires_at: row.get("expires_at"),
+        }
+    }
+}
+
+/// Expired assignments unassigned by one pass of the sweeper
+pub const EXPIRED_ROLES_BATCH_SIZE: u32 = 100;
+
+/// Options of the member list of a role
+#[derive(Debug, Deserialize, Clone, Default, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct ListRoleMembersQuery {
+    /// Add the display name and profile picture of each member from the User service
+    pub include_profiles: bool,
+}
+
+/// Request body for assigning a role, the assignment lasting until `expires_at` if set
+#[derive(Debug, Deserialize, Clone, Default, ToSchema)]
+#[serde(default)]
+pub struct AssignRoleRequest {
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AssignRoleRequest {
+    pub fn validate(&self) -> Result<(), CoreError> {
+        match self.expires_at {
+            Some(expires_at) if expires_at <= Utc::now() => Err(CoreError::InvalidRoleExpiry {
+                msg: "role assignment must expire in the future".to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A role held by a member, along with the end of the assignment
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct AssignedRole {
+    #[serde(flatten)]
+    pub role: Role,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for AssignedRole {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            role: Role::from(row),
+            expires_at: row.get("expires_at"),
+        }
+    }
+}
+
+/// The full set of roles a member should hold, the default role being always kept
+#[derive(Debug, Deserialize, Clone, ToSchema)]
+pub struct SetMemberRolesRequest {
+    pub role_ids: Vec<RoleId>,
+}
+
+/// Roles to assign to and remove from a member
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct MemberRolesChange {
+    pub assigned: Vec<RoleId>,
+    pub removed: Vec<RoleId>,
+}
+
+impl MemberRolesChange {
+    pub fn is_empty(&self) -> bool {
+        self.assigned.is_empty() && self.removed.is_empty()
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
 pub struct AssignMemberRole {
     pub member_id: MemberId,
     pub role_id: RoleId,
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
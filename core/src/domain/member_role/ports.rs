@@ -1,55 +1,135 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use crate::domain::{
     common::{CoreError, GetPaginated, TotalPaginatedElements},
     friend::entities::UserId,
-    member_role::entities::{AssignMemberRole, MemberRole, UnassignMemberRole},
+    member_role::entities::{
+        AssignMemberRole, AssignRoleRequest, AssignedRole, ListRoleMembersQuery, MemberRole,
+        MemberRolesChange, SetMemberRolesRequest, UnassignMemberRole,
+    },
     role::entities::{Role, RoleId},
     server::entities::ServerId,
-    server_member::{MemberId, ServerMember},
+    server_member::{
+        MemberId, ServerMember,
+        entities::{BulkMemberResult, BulkMembersRequest, ListedMember},
+    },
 };
 pub trait MemberRoleRepository: Send + Sync {
     fn assign(
         &self,
         member_role: AssignMemberRole,
-    ) -> impl Future<Output = Result<MemberRole, CoreError>>;
+    ) -> impl Future<Output = Result<MemberRole, CoreError>> + Send;
     fn unassign(
         &self,
         member_role: UnassignMemberRole,
-    ) -> impl Future<Output = Result<(), CoreError>>;
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+    /// Assign a role to several members in one transaction, skipping those already holding it.
+    /// Returns the assignments that were made
+    fn assign_many(
+        &self,
+        role_id: &RoleId,
+        members: &[ServerMember],
+    ) -> impl Future<Output = Result<Vec<MemberRole>, CoreError>> + Send;
+    /// Unassign a role from several members in one transaction.
+    /// Returns the members that held the role
+    fn unassign_many(
+        &self,
+        role_id: &RoleId,
+        members: &[ServerMember],
+    ) -> impl Future<Output = Result<Vec<MemberId>, CoreError>> + Send;
+    /// Assign and remove roles of a member in one transaction.
+    /// Returns the changes that were made, skipping roles already (not) held
+    fn change_member_roles(
+        &self,
+        member: &ServerMember,
+        change: &MemberRolesChange,
+    ) -> impl Future<Output = Result<MemberRolesChange, CoreError>> + Send;
     fn list_members_by_role(
         &self,
         role_id: &RoleId,
         pagination: &GetPaginated,
-    ) -> impl Future<Output = Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError>>;
+    ) -> impl Future<Output = Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError>> + Send;
+    /// Number of members holding each role of the server, in one grouped query.
+    /// Roles without members are left out
+    fn count_members_by_role(
+        &self,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<HashMap<RoleId, u64>, CoreError>> + Send;
+    /// Roles currently held by the user in the server, expired assignments left out
     fn list_roles_by_user_and_server(
         &self,
         user_id: UserId,
         server_id: ServerId,
-    ) -> impl Future<Output = Result<Vec<Role>, CoreError>>;
+    ) -> impl Future<Output = Result<Vec<Role>, CoreError>> + Send;
+    /// Same as `list_roles_by_user_and_server`, with the expiry of each assignment
+    fn list_assigned_roles_by_user_and_server(
+        &self,
+        user_id: UserId,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<Vec<AssignedRole>, CoreError>> + Send;
+    /// Assignments whose expiry date is past, oldest first
+    fn list_expired_assignments(
+        &self,
+        limit: u32,
+    ) -> impl Future<Output = Result<Vec<MemberRole>, CoreError>> + Send;
 }
 
+/// Roles are only (un)assigned by actors ranking above them, see `RoleService`
 pub trait MemberRoleService: Send + Sync {
     fn assign_member_to_role(
         &self,
         role_id: RoleId,
         member_id: MemberId,
+        request: AssignRoleRequest,
+        actor_id: UserId,
     ) -> impl Future<Output = Result<MemberRole, CoreError>>;
     fn unassign_member_from_role(
         &self,
         role_id: RoleId,
         member_id: MemberId,
+        actor_id: UserId,
     ) -> impl Future<Output = Result<(), CoreError>>;
+    /// Assign a role to several users of its server in one transaction,
+    /// reporting non members and members already holding the role as failed items
+    fn bulk_assign_role(
+        &self,
+        role_id: RoleId,
+        request: BulkMembersRequest,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<Vec<BulkMemberResult>, CoreError>>;
+    /// Unassign a role from several users of its server in one transaction
+    fn bulk_unassign_role(
+        &self,
+        role_id: RoleId,
+        request: BulkMembersRequest,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<Vec<BulkMemberResult>, CoreError>>;
+    /// Replace the roles of a member with the requested set in one transaction,
+    /// the actor ranking above every role assigned or removed
+    fn set_member_roles(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        request: SetMemberRolesRequest,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<MemberRolesChange, CoreError>>;
     fn list_members_by_role(
         &self,
         role_id: &RoleId,
         pagination: &GetPaginated,
-    ) -> impl Future<Output = Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError>>;
+        query: ListRoleMembersQuery,
+    ) -> impl Future<Output = Result<(Vec<ListedMember>, TotalPaginatedElements), CoreError>>;
     fn list_roles_by_user_and_server(
         &self,
         user_id: UserId,
         server_id: ServerId,
-    ) -> impl Future<Output = Result<Vec<Role>, CoreError>>;
+    ) -> impl Future<Output = Result<Vec<AssignedRole>, CoreError>>;
+    /// Unassign a batch of expired roles through the regular unassignment, emitting its events.
+    /// Returns how many were unassigned
+    fn unassign_expired_roles(&self) -> impl Future<Output = Result<usize, CoreError>>;
 }
 
 /// Mock implementation of MemberRoleRepository for testing
@@ -79,6 +159,7 @@ impl MemberRoleRepository for MockMemberRoleRepository {
             role_id: member_role.role_id,
             created_at: chrono::Utc::now(),
             updated_at: None,
+            expires_at: member_role.expires_at,
         };
         member_roles.push(member_role.clone());
         Ok(member_role)
@@ -90,6 +171,82 @@ impl MemberRoleRepository for MockMemberRoleRepository {
         });
         Ok(())
     }
+    async fn assign_many(
+        &self,
+        role_id: &RoleId,
+        members: &[ServerMember],
+    ) -> Result<Vec<MemberRole>, CoreError> {
+        let mut member_roles = self.member_role.lock().unwrap();
+        let mut assigned = Vec::new();
+        for member in members {
+            if member_roles
+                .iter()
+                .any(|mr| mr.member_id == member.id && mr.role_id == *role_id)
+            {
+                continue;
+            }
+            let member_role = MemberRole {
+                member_id: member.id,
+                role_id: *role_id,
+                created_at: chrono::Utc::now(),
+                updated_at: None,
+                expires_at: None,
+            };
+            member_roles.push(member_role.clone());
+            assigned.push(member_role);
+        }
+        Ok(assigned)
+    }
+    async fn unassign_many(
+        &self,
+        role_id: &RoleId,
+        members: &[ServerMember],
+    ) -> Result<Vec<MemberId>, CoreError> {
+        let mut member_roles = self.member_role.lock().unwrap();
+        let unassigned: Vec<MemberId> = members
+            .iter()
+            .filter(|member| {
+                member_roles
+                    .iter()
+                    .any(|mr| mr.member_id == member.id && mr.role_id == *role_id)
+            })
+            .map(|member| member.id)
+            .collect();
+        member_roles.retain(|mr| !(mr.role_id == *role_id && unassigned.contains(&mr.member_id)));
+        Ok(unassigned)
+    }
+    async fn change_member_roles(
+        &self,
+        member: &ServerMember,
+        change: &MemberRolesChange,
+    ) -> Result<MemberRolesChange, CoreError> {
+        let mut member_roles = self.member_role.lock().unwrap();
+        let mut applied = MemberRolesChange::default();
+        for role_id in &change.removed {
+            let held = member_roles.len();
+            member_roles.retain(|mr| !(mr.member_id == member.id && mr.role_id == *role_id));
+            if member_roles.len() != held {
+                applied.removed.push(*role_id);
+            }
+        }
+        for role_id in &change.assigned {
+            if member_roles
+                .iter()
+                .any(|mr| mr.member_id == member.id && mr.role_id == *role_id)
+            {
+                continue;
+            }
+            member_roles.push(MemberRole {
+                member_id: member.id,
+                role_id: *role_id,
+                created_at: chrono::Utc::now(),
+                updated_at: None,
+                expires_at: None,
+            });
+            applied.assigned.push(*role_id);
+        }
+        Ok(applied)
+    }
     async fn list_members_by_role(
         &self,
         _role_id: &RoleId,
@@ -97,6 +254,22 @@ impl MemberRoleRepository for MockMemberRoleRepository {
     ) -> Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError> {
         Ok((Vec::new(), 0))
     }
+    async fn count_members_by_role(
+        &self,
+        _server_id: ServerId,
+    ) -> Result<HashMap<RoleId, u64>, CoreError> {
+        // Roles are not known here, every assignment is counted
+        let member_roles = self.member_role.lock().unwrap();
+        let now = chrono::Utc::now();
+        let mut counts = HashMap::new();
+        for member_role in member_roles
+            .iter()
+            .filter(|mr| mr.expires_at.is_none_or(|expires_at| expires_at > now))
+        {
+            *counts.entry(member_role.role_id).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
     async fn list_roles_by_user_and_server(
         &self,
         _user_id: UserId,
@@ -104,4 +277,23 @@ impl MemberRoleRepository for MockMemberRoleRepository {
     ) -> Result<Vec<Role>, CoreError> {
         Ok(Vec::new())
     }
+    async fn list_assigned_roles_by_user_and_server(
+        &self,
+        _user_id: UserId,
+        _server_id: ServerId,
+    ) -> Result<Vec<AssignedRole>, CoreError> {
+        Ok(Vec::new())
+    }
+    async fn list_expired_assignments(&self, limit: u32) -> Result<Vec<MemberRole>, CoreError> {
+        let member_roles = self.member_role.lock().unwrap();
+        let now = chrono::Utc::now();
+        let mut expired: Vec<MemberRole> = member_roles
+            .iter()
+            .filter(|mr| mr.expires_at.is_some_and(|expires_at| expires_at <= now))
+            .cloned()
+            .collect();
+        expired.sort_by_key(|mr| mr.expires_at);
+        expired.truncate(limit as usize);
+        Ok(expired)
+    }
 }
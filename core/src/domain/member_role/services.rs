@@ -5,10 +5,14 @@ use crate::{
         channel::ports::ChannelRepository,
         channel_member::ports::ChannelMemberRepository,
         common::{CoreError, GetPaginated, TotalPaginatedElements},
-        friend::ports::FriendshipRepository,
+        friend::{entities::UserId, ports::FriendshipRepository},
         health::port::HealthRepository,
         member_role::{
-            entities::{AssignMemberRole, MemberRole, UnassignMemberRole},
+            entities::{
+                AssignMemberRole, AssignRoleRequest, AssignedRole, EXPIRED_ROLES_BATCH_SIZE,
+                ListRoleMembersQuery, MemberRole, MemberRolesChange, SetMemberRolesRequest,
+                UnassignMemberRole,
+            },
             ports::{MemberRoleRepository, MemberRoleService},
         },
         outbox::ports::OutboxRepository,
@@ -16,16 +20,23 @@ use crate::{
             entities::{Role, RoleId},
             ports::RoleRepository,
         },
-        server::ports::ServerRepository,
+        server::{entities::ServerId, ports::ServerRepository},
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
-        server_member::{MemberId, MemberRepository, ServerMember},
+        server_join_request::ports::ServerJoinRequestRepository,
+        server_member::{
+            MemberId, MemberRepository, ServerMember,
+            entities::{
+                BulkMemberResult, BulkMembersRequest, ListedMember, validate_bulk_user_ids,
+            },
+        },
         server_pictures::ServerPicturesRepository,
         user::port::UserRepository,
     },
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> MemberRoleService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> MemberRoleService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -40,20 +51,36 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn assign_member_to_role(
         &self,
         role_id: RoleId,
         member_id: MemberId,
+        request: AssignRoleRequest,
+        actor_id: UserId,
     ) -> Result<MemberRole, CoreError> {
+        request.validate()?;
         let role: Role = self.role_repository.find_by_id(&role_id).await?;
+        if role.is_default {
+            return Err(CoreError::DefaultRoleAssignment);
+        }
         let member: ServerMember = self.member_repository.find_by_id(member_id).await?;
         if member.server_id != role.server_id {
             return Err(CoreError::BadRoleMemberAssignation);
         }
+        // Granting a role must not hand out permissions the actor does not hold
+        let authority = self.role_authority(role.server_id, actor_id).await?;
+        authority.check_position(role.position)?;
+        authority.check_permissions(&role.permissions)?;
         let member_role = self
             .member_role_repository
-            .assign(AssignMemberRole { role_id, member_id })
+            .assign(AssignMemberRole {
+                role_id,
+                member_id,
+                expires_at: request.expires_at,
+            })
             .await?;
         Ok(member_role)
     }
@@ -62,30 +89,252 @@ where
         &self,
         role_id: crate::domain::role::entities::RoleId,
         member_id: crate::domain::server_member::MemberId,
+        actor_id: UserId,
     ) -> Result<(), CoreError> {
+        let role: Role = self.role_repository.find_by_id(&role_id).await?;
+        if role.is_default {
+            return Err(CoreError::DefaultRoleAssignment);
+        }
+        self.role_authority(role.server_id, actor_id)
+            .await?
+            .check_position(role.position)?;
         self.member_role_repository
             .unassign(UnassignMemberRole { role_id, member_id })
             .await?;
         Ok(())
     }
 
+    async fn bulk_assign_role(
+        &self,
+        role_id: RoleId,
+        request: BulkMembersRequest,
+        actor_id: UserId,
+    ) -> Result<Vec<BulkMemberResult>, CoreError> {
+        let user_ids = validate_bulk_user_ids(&request.user_ids)?;
+        let role: Role = self.role_repository.find_by_id(&role_id).await?;
+        if role.is_default {
+            return Err(CoreError::DefaultRoleAssignment);
+        }
+        let authority = self.role_authority(role.server_id, actor_id).await?;
+        authority.check_position(role.position)?;
+        authority.check_permissions(&role.permissions)?;
+        let members = self.find_bulk_members(role.server_id, &user_ids).await?;
+
+        let assigned = self
+            .member_role_repository
+            .assign_many(&role_id, &members)
+            .await?;
+
+        Ok(user_ids
+            .into_iter()
+            .map(|user_id| {
+                let Some(member) = members.iter().find(|m| m.user_id == user_id) else {
+                    return BulkMemberResult::failure(
+                        user_id,
+                        &CoreError::MemberNotFound {
+                            server_id: role.server_id,
+                            user_id,
+                        },
+                    );
+                };
+                if assigned.iter().any(|mr| mr.member_id == member.id) {
+                    BulkMemberResult::success(user_id)
+                } else {
+                    // Already holding the role
+                    BulkMemberResult::failure(
+                        user_id,
+                        &CoreError::AssignMemberRoleError {
+                            member_id: member.id,
+                            role_id,
+                        },
+                    )
+                }
+            })
+            .collect())
+    }
+
+    async fn bulk_unassign_role(
+        &self,
+        role_id: RoleId,
+        request: BulkMembersRequest,
+        actor_id: UserId,
+    ) -> Result<Vec<BulkMemberResult>, CoreError> {
+        let user_ids = validate_bulk_user_ids(&request.user_ids)?;
+        let role: Role = self.role_repository.find_by_id(&role_id).await?;
+        if role.is_default {
+            return Err(CoreError::DefaultRoleAssignment);
+        }
+        self.role_authority(role.server_id, actor_id)
+            .await?
+            .check_position(role.position)?;
+        let members = self.find_bulk_members(role.server_id, &user_ids).await?;
+
+        // Like a single unassignment, members who did not hold the role are left as they were
+        self.member_role_repository
+            .unassign_many(&role_id, &members)
+            .await?;
+
+        Ok(user_ids
+            .into_iter()
+            .map(|user_id| {
+                if members.iter().any(|m| m.user_id == user_id) {
+                    BulkMemberResult::success(user_id)
+                } else {
+                    BulkMemberResult::failure(
+                        user_id,
+                        &CoreError::MemberNotFound {
+                            server_id: role.server_id,
+                            user_id,
+                        },
+                    )
+                }
+            })
+            .collect())
+    }
+
+    async fn set_member_roles(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        request: SetMemberRolesRequest,
+        actor_id: UserId,
+    ) -> Result<MemberRolesChange, CoreError> {
+        let member = self
+            .member_repository
+            .find_by_server_and_user(&server_id, &user_id)
+            .await?;
+        let server_roles = self.role_repository.list_in_server(*server_id).await?;
+        for role_id in &request.role_ids {
+            if !server_roles.iter().any(|role| role.id == *role_id) {
+                return Err(CoreError::RoleNotFound { id: *role_id });
+            }
+        }
+        let held = self
+            .member_role_repository
+            .list_roles_by_user_and_server(user_id, server_id)
+            .await?;
+
+        // The default role is held by every member, it is left as is
+        let mut change = MemberRolesChange::default();
+        for role in server_roles.iter().filter(|role| !role.is_default) {
+            let wanted = request.role_ids.contains(&role.id);
+            let holds = held.iter().any(|held_role| held_role.id == role.id);
+            if wanted && !holds {
+                change.assigned.push(role.id);
+            } else if !wanted && holds {
+                change.removed.push(role.id);
+            }
+        }
+        if change.is_empty() {
+            return Ok(change);
+        }
+
+        let authority = self.role_authority(server_id, actor_id).await?;
+        for role in server_roles
+            .iter()
+            .filter(|role| change.assigned.contains(&role.id) || change.removed.contains(&role.id))
+        {
+            authority.check_position(role.position)?;
+            if change.assigned.contains(&role.id) {
+                authority.check_permissions(&role.permissions)?;
+            }
+        }
+
+        self.member_role_repository
+            .change_member_roles(&member, &change)
+            .await
+    }
+
     async fn list_members_by_role(
         &self,
         role_id: &RoleId,
         pagination: &GetPaginated,
-    ) -> Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError> {
-        self.member_role_repository
+        query: ListRoleMembersQuery,
+    ) -> Result<(Vec<ListedMember>, TotalPaginatedElements), CoreError> {
+        let (members, total) = self
+            .member_role_repository
             .list_members_by_role(role_id, pagination)
-            .await
+            .await?;
+        let members = self.listed_members(members, query.include_profiles).await;
+        Ok((members, total))
     }
 
     async fn list_roles_by_user_and_server(
         &self,
         user_id: crate::domain::friend::entities::UserId,
         server_id: crate::domain::server::entities::ServerId,
-    ) -> Result<Vec<Role>, CoreError> {
+    ) -> Result<Vec<AssignedRole>, CoreError> {
         self.member_role_repository
-            .list_roles_by_user_and_server(user_id, server_id)
+            .list_assigned_roles_by_user_and_server(user_id, server_id)
             .await
     }
+
+    async fn unassign_expired_roles(&self) -> Result<usize, CoreError> {
+        let expired = self
+            .member_role_repository
+            .list_expired_assignments(EXPIRED_ROLES_BATCH_SIZE)
+            .await?;
+        // A failing assignment is retried on the next sweep without holding back the others
+        let mut unassigned = 0;
+        for member_role in &expired {
+            match self
+                .member_role_repository
+                .unassign(UnassignMemberRole {
+                    role_id: member_role.role_id,
+                    member_id: member_role.member_id,
+                })
+                .await
+            {
+                Ok(()) => unassigned += 1,
+                Err(e) => tracing::error!(
+                    "Failed to unassign expired role {} from member {}: {}",
+                    member_role.role_id,
+                    member_role.member_id,
+                    e
+                ),
+            }
+        }
+        Ok(unassigned)
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// The members of the server among the users of a bulk operation, non members are left out
+    async fn find_bulk_members(
+        &self,
+        server_id: ServerId,
+        user_ids: &[UserId],
+    ) -> Result<Vec<ServerMember>, CoreError> {
+        let mut members = Vec::with_capacity(user_ids.len());
+        for user_id in user_ids {
+            match self
+                .member_repository
+                .find_by_server_and_user(&server_id, user_id)
+                .await
+            {
+                Ok(member) => members.push(member),
+                Err(CoreError::MemberNotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(members)
+    }
 }
@@ -15,15 +15,17 @@ use crate::{
         },
         role::ports::RoleRepository,
         server::ports::ServerRepository,
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
+        server_join_request::ports::ServerJoinRequestRepository,
         server_member::MemberRepository,
         server_pictures::ServerPicturesRepository,
         user::port::UserRepository,
     },
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> OutboxService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> OutboxService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -38,6 +40,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn get(
         &self,
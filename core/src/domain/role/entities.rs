@@ -2,13 +2,15 @@ use std::{fmt::Display, ops::Deref};
 
 use chrono::{DateTime, Utc};
 use events_protobuf::communities_events::{self, PermissionBitmask, UpsertRole};
-use permission_translation::models::CapilityHexValue;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::{common::CoreError, server::entities::ServerId};
+use crate::domain::{common::CoreError, friend::entities::UserId, server::entities::ServerId};
+
+/// Name given to the default role created along with every server
+pub const DEFAULT_ROLE_NAME: &str = "BasicUser";
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct RoleId(pub Uuid);
@@ -36,10 +38,10 @@ impl Deref for RoleId {
 //
 #[derive(Debug, Clone, sqlx::Type, Serialize, Deserialize, ToSchema)]
 #[sqlx(transparent)]
-pub struct Permissions(pub i32);
+pub struct Permissions(pub i64);
 
 impl Deref for Permissions {
-    type Target = i32;
+    type Target = i64;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -60,10 +62,122 @@ pub struct Role {
     pub server_id: ServerId,
     pub name: String,
     pub permissions: Permissions,
+    /// Permissions decoded from the bitmask
+    #[serde(default, skip_deserializing)]
+    pub permission_names: Vec<Permission>,
+    /// Rank in the server hierarchy, the default role sitting at 0
+    #[serde(default)]
+    pub position: i32,
+    /// Whether this is the role implicitly held by every member of the server
+    #[serde(default)]
+    pub is_default: bool,
+    #[serde(flatten)]
+    pub appearance: RoleAppearance,
+    /// Presigned URL of the icon: to upload it right after a create or update asking for
+    /// an icon, to download it otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// Members holding the role, given when listing the roles of a server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<u64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for Role {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        let permissions = Permissions(row.get("permissions"));
+        Self {
+            id: RoleId(row.get("id")),
+            server_id: ServerId(row.get("server_id")),
+            name: row.get("name"),
+            permission_names: permissions.names(),
+            permissions,
+            position: row.get("position"),
+            is_default: row.get("is_default"),
+            appearance: RoleAppearance {
+                color: row.get("color"),
+                hoist: row.get("hoist"),
+                mentionable: row.get("mentionable"),
+                has_icon: row.get("has_icon"),
+            },
+            icon_url: None,
+            member_count: None,
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+/// Largest RGB color a role can take
+pub const MAX_ROLE_COLOR: i32 = 0xFFFFFF;
+
+/// How a role is displayed to the members of its server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RoleAppearance {
+    /// RGB color given to the names of the members, 0 leaving them uncolored
+    #[serde(default)]
+    pub color: i32,
+    /// Whether the members are listed under the role in the sidebar
+    #[serde(default)]
+    pub hoist: bool,
+    /// Whether members can mention the role
+    #[serde(default)]
+    pub mentionable: bool,
+    /// Whether the role has an icon, stored through the content service
+    #[serde(default)]
+    pub has_icon: bool,
+}
+
+impl RoleAppearance {
+    pub fn validate(&self) -> Result<(), CoreError> {
+        validate_role_color(self.color)
+    }
+}
+
+pub fn validate_role_color(color: i32) -> Result<(), CoreError> {
+    if !(0..=MAX_ROLE_COLOR).contains(&color) {
+        return Err(CoreError::InvalidRoleColor { color });
+    }
+    Ok(())
+}
+
+/// Changes to the appearance of a role, absent fields being left untouched
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateRoleAppearance {
+    pub color: Option<i32>,
+    pub hoist: Option<bool>,
+    pub mentionable: Option<bool>,
+    /// `true` hands out a new upload URL, `false` removes the icon
+    pub has_icon: Option<bool>,
+}
+
+impl UpdateRoleAppearance {
+    pub fn validate(&self) -> Result<(), CoreError> {
+        self.color.map_or(Ok(()), validate_role_color)
+    }
+
+    /// Whether the update leaves the appearance untouched
+    pub fn is_empty(&self) -> bool {
+        self.color.is_none()
+            && self.hoist.is_none()
+            && self.mentionable.is_none()
+            && self.has_icon.is_none()
+    }
+
+    pub fn apply(&self, current: &RoleAppearance) -> RoleAppearance {
+        RoleAppearance {
+            color: self.color.unwrap_or(current.color),
+            hoist: self.hoist.unwrap_or(current.hoist),
+            mentionable: self.mentionable.unwrap_or(current.mentionable),
+            has_icon: self.has_icon.unwrap_or(current.has_icon),
+        }
+    }
+}
+
+// `UpsertRole` has no field for the appearance, it is published as a `RoleAppearanceEvent`
 impl Into<UpsertRole> for Role {
     fn into(self) -> UpsertRole {
         UpsertRole {
@@ -74,10 +188,51 @@ impl Into<UpsertRole> for Role {
     }
 }
 
+/// Event emitted when a role is created or its appearance changes
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RoleAppearanceEvent {
+    pub role_id: RoleId,
+    pub server_id: ServerId,
+    #[serde(flatten)]
+    pub appearance: RoleAppearance,
+}
+
+impl From<&Role> for RoleAppearanceEvent {
+    fn from(role: &Role) -> Self {
+        Self {
+            role_id: role.id,
+            server_id: role.server_id,
+            appearance: role.appearance.clone(),
+        }
+    }
+}
+
+/// Permissions of a role as sent by clients: a bitmask, a list of permission names or a preset
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum PermissionsInput {
+    Bitmask(i64),
+    Names(Vec<Permission>),
+    Preset(RolePreset),
+}
+
+impl PermissionsInput {
+    /// The bitmask, left unchecked so unknown bits are rejected by `Permissions::try_from`
+    pub fn bits(&self) -> i64 {
+        match self {
+            PermissionsInput::Bitmask(bits) => *bits,
+            PermissionsInput::Names(names) => *Permissions::from(names.clone()),
+            PermissionsInput::Preset(preset) => *Permissions::from(*preset),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct CreateRoleRequest {
     pub name: String,
-    pub permissions: i32,
+    pub permissions: PermissionsInput,
+    #[serde(flatten)]
+    pub appearance: RoleAppearance,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -85,6 +240,7 @@ pub struct CreateRoleInput {
     pub server_id: Uuid,
     pub name: String,
     pub permissions: Permissions,
+    pub appearance: RoleAppearance,
 }
 
 #[derive(Debug, Error)]
@@ -104,14 +260,17 @@ impl Into<CoreError> for RoleError {
 #[derive(Clone, ToSchema, Serialize, Deserialize)]
 pub struct UpdateRoleRequest {
     pub name: Option<String>,
-    pub permissions: Option<i32>,
+    pub permissions: Option<PermissionsInput>,
+    #[serde(flatten)]
+    pub appearance: UpdateRoleAppearance,
 }
 
 #[derive(Clone, ToSchema, Serialize)]
 pub struct UpdateRoleInput {
     pub id: RoleId,
     pub name: Option<String>,
-    pub permissions: Option<i32>,
+    pub permissions: Option<i64>,
+    pub appearance: UpdateRoleAppearance,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -119,6 +278,7 @@ pub struct UpdateRoleRepoInput {
     pub id: RoleId,
     pub name: Option<String>,
     pub permissions: Option<Permissions>,
+    pub appearance: UpdateRoleAppearance,
 }
 
 impl TryFrom<UpdateRoleInput> for UpdateRoleRepoInput {
@@ -134,10 +294,60 @@ impl TryFrom<UpdateRoleInput> for UpdateRoleRepoInput {
             id: value.id,
             name: value.name,
             permissions,
+            appearance: value.appearance,
         })
     }
 }
 
+/// New hierarchy of a server, listing every role but the default one from lowest to highest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReorderRolesRequest {
+    pub role_ids: Vec<RoleId>,
+}
+
+/// What an actor may do on the roles of a server
+#[derive(Debug, Clone)]
+pub enum RoleAuthority {
+    /// The server owner manages every role
+    Owner,
+    /// A member manages roles below their highest one, granting only the bits they hold
+    Member {
+        highest_position: i32,
+        permissions: Permissions,
+    },
+}
+
+impl RoleAuthority {
+    pub fn from_roles(roles: &[Role]) -> Self {
+        Self::Member {
+            highest_position: roles.iter().map(|role| role.position).max().unwrap_or(0),
+            permissions: Permissions(roles.iter().fold(0, |acc, role| acc | *role.permissions)),
+        }
+    }
+
+    /// Whether a role at `position` is strictly below the actor's highest role
+    pub fn check_position(&self, position: i32) -> Result<(), CoreError> {
+        match self {
+            RoleAuthority::Member {
+                highest_position, ..
+            } if position >= *highest_position => Err(CoreError::RoleHierarchy),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the actor holds every bit of `permissions`, administrators holding them all
+    pub fn check_permissions(&self, permissions: &Permissions) -> Result<(), CoreError> {
+        match self {
+            RoleAuthority::Member {
+                permissions: held, ..
+            } if !held.has(Permission::Administrator) && **permissions & !**held != 0 => {
+                Err(CoreError::PermissionEscalation)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct DeleteRole {
     pub role_id: RoleId,
@@ -151,71 +361,233 @@ impl Into<communities_events::DeleteRole> for DeleteRole {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub enum Permission {
-    Administrator,    // Can do any action on any subject (channel, webhooks…) in a server.
-    ManageServer,     // Can update a server (all CRUD except delete).
-    ManageRoles,      // Can do all CRUD operations on all roles.
-    CreateInvitation, // Can create server invites.
-    ManageChannels,   // Can do all CRUD operations on every channel.
-    ManageWebhooks,   // Can do all CRUD operations on every webhook.
-    ViewChannels,     // Can see the channel and its contents (messages).
-    SendMessages,     // Can send a message on the channel.
-    ManageNicknames,  // Can update other users' nicknames.
-    ChangeNickname,   // Can update your own nickname.
-    ManageMessages,   // Can delete other users' messages.
-    AttachFiles,      // Can upload images and files.
-}
-
-impl Into<CapilityHexValue> for Permission {
-    fn into(self) -> CapilityHexValue {
+    Administrator,
+    ManageServer,
+    ManageRoles,
+    CreateInvitation,
+    ManageChannels,
+    ManageWebhooks,
+    ViewChannels,
+    SendMessages,
+    ManageNicknames,
+    ChangeNickname,
+    ManageMessages,
+    AttachFiles,
+    ModerateMembers,
+    BanMembers,
+    KickMembers,
+    Connect,
+    Speak,
+    MuteMembers,
+}
+
+/// Named sets of permissions offered when creating a role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum RolePreset {
+    Admin,
+    Moderator,
+    Member,
+    /// Role permissions add up, so this preset only withholds writing and speaking from
+    /// members holding no other role: pair it with channel overwrites denying those
+    /// permissions to the role to silence everyone holding it
+    Muted,
+    ReadOnly,
+}
+
+impl RolePreset {
+    /// Permissions granted by the preset, in the order of the registry
+    pub fn permissions(self) -> Vec<Permission> {
         match self {
-            Permission::Administrator => 0x1,
-            Permission::ManageServer => 0x2,
-            Permission::ManageRoles => 0x4,
-            Permission::CreateInvitation => 0x8,
-            Permission::ManageChannels => 0x10,
-            Permission::ManageWebhooks => 0x20,
-            Permission::ViewChannels => 0x40,
-            Permission::SendMessages => 0x80,
-            Permission::ManageNicknames => 0x100,
-            Permission::ChangeNickname => 0x200,
-            Permission::ManageMessages => 0x400,
-            Permission::AttachFiles => 0x800,
+            RolePreset::Admin => vec![Permission::Administrator],
+            RolePreset::Moderator => vec![
+                Permission::CreateInvitation,
+                Permission::ViewChannels,
+                Permission::SendMessages,
+                Permission::ManageNicknames,
+                Permission::ChangeNickname,
+                Permission::ManageMessages,
+                Permission::AttachFiles,
+                Permission::ModerateMembers,
+                Permission::KickMembers,
+                Permission::Connect,
+                Permission::Speak,
+                Permission::MuteMembers,
+            ],
+            RolePreset::Member => vec![
+                Permission::CreateInvitation,
+                Permission::ViewChannels,
+                Permission::SendMessages,
+                Permission::ChangeNickname,
+                Permission::AttachFiles,
+                Permission::Connect,
+                Permission::Speak,
+            ],
+            // Members can follow the conversations but neither write nor speak
+            RolePreset::Muted => vec![
+                Permission::ViewChannels,
+                Permission::ChangeNickname,
+                Permission::Connect,
+            ],
+            RolePreset::ReadOnly => vec![Permission::ViewChannels],
         }
     }
 }
 
-impl TryFrom<i32> for Permission {
-    type Error = PermissionError;
+impl From<RolePreset> for Permissions {
+    fn from(value: RolePreset) -> Self {
+        Permissions::from(value.permissions())
+    }
+}
 
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            0x1 => Ok(Permission::Administrator),
-            0x2 => Ok(Permission::ManageServer),
-            0x4 => Ok(Permission::ManageRoles),
-            0x8 => Ok(Permission::CreateInvitation),
-            0x10 => Ok(Permission::ManageChannels),
-            0x20 => Ok(Permission::ManageWebhooks),
-            0x40 => Ok(Permission::ViewChannels),
-            0x80 => Ok(Permission::SendMessages),
-            0x100 => Ok(Permission::ManageNicknames),
-            0x200 => Ok(Permission::ChangeNickname),
-            0x400 => Ok(Permission::ManageMessages),
-            0x800 => Ok(Permission::AttachFiles),
-            _ => Err(PermissionError::BadFormat),
-        }
+/// A bit of the permission bitmask
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub struct PermissionDefinition {
+    pub permission: Permission,
+    pub bit: i64,
+    pub description: &'static str,
+}
+
+const fn definition(
+    permission: Permission,
+    bit: i64,
+    description: &'static str,
+) -> PermissionDefinition {
+    PermissionDefinition {
+        permission,
+        bit,
+        description,
+    }
+}
+
+/// Every permission with its bit, the single source of truth for the bitmask.
+/// Bits are stored in the database, so a bit is never reused once released
+pub const PERMISSIONS: &[PermissionDefinition] = &[
+    definition(
+        Permission::Administrator,
+        0x1,
+        "Can do any action on any subject (channel, webhooks…) in a server",
+    ),
+    definition(
+        Permission::ManageServer,
+        0x2,
+        "Can update a server (all CRUD except delete)",
+    ),
+    definition(
+        Permission::ManageRoles,
+        0x4,
+        "Can do all CRUD operations on all roles",
+    ),
+    definition(
+        Permission::CreateInvitation,
+        0x8,
+        "Can create server invites",
+    ),
+    definition(
+        Permission::ManageChannels,
+        0x10,
+        "Can do all CRUD operations on every channel",
+    ),
+    definition(
+        Permission::ManageWebhooks,
+        0x20,
+        "Can do all CRUD operations on every webhook",
+    ),
+    definition(
+        Permission::ViewChannels,
+        0x40,
+        "Can see the channel and its contents (messages)",
+    ),
+    definition(
+        Permission::SendMessages,
+        0x80,
+        "Can send a message on the channel",
+    ),
+    definition(
+        Permission::ManageNicknames,
+        0x100,
+        "Can update other users' nicknames",
+    ),
+    definition(
+        Permission::ChangeNickname,
+        0x200,
+        "Can update your own nickname",
+    ),
+    definition(
+        Permission::ManageMessages,
+        0x400,
+        "Can delete other users' messages",
+    ),
+    definition(
+        Permission::AttachFiles,
+        0x800,
+        "Can upload images and files",
+    ),
+    definition(
+        Permission::ModerateMembers,
+        0x1000,
+        "Can time out members of the server",
+    ),
+    definition(
+        Permission::BanMembers,
+        0x2000,
+        "Can ban and unban members of the server",
+    ),
+    definition(
+        Permission::KickMembers,
+        0x4000,
+        "Can kick members out of the server",
+    ),
+    definition(Permission::Connect, 0x8000, "Can join voice channels"),
+    definition(Permission::Speak, 0x10000, "Can speak in voice channels"),
+    definition(
+        Permission::MuteMembers,
+        0x20000,
+        "Can mute other members in voice channels",
+    ),
+];
+
+/// All valid permission bits combined
+pub const ALL_PERMISSIONS: i64 = {
+    let mut bits = 0;
+    let mut index = 0;
+    while index < PERMISSIONS.len() {
+        bits |= PERMISSIONS[index].bit;
+        index += 1;
+    }
+    bits
+};
+
+impl Permission {
+    pub fn definition(self) -> &'static PermissionDefinition {
+        PERMISSIONS
+            .iter()
+            .find(|definition| definition.permission == self)
+            .expect("every permission is registered")
+    }
+
+    pub fn bit(self) -> i64 {
+        self.definition().bit
     }
 }
 
-impl TryFrom<i32> for Permissions {
+impl TryFrom<i64> for Permission {
     type Error = PermissionError;
 
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        // All valid permission bits combined
-        const ALL_PERMISSIONS: i32 =
-            0x1 | 0x2 | 0x4 | 0x8 | 0x10 | 0x20 | 0x40 | 0x80 | 0x100 | 0x200 | 0x400 | 0x800;
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        PERMISSIONS
+            .iter()
+            .find(|definition| definition.bit == value)
+            .map(|definition| definition.permission)
+            .ok_or(PermissionError::BadFormat)
+    }
+}
 
+impl TryFrom<i64> for Permissions {
+    type Error = PermissionError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
         // Check if value contains only valid permission bits
         if value & !ALL_PERMISSIONS != 0 {
             return Err(PermissionError::BadFormat);
@@ -225,13 +597,66 @@ impl TryFrom<i32> for Permissions {
     }
 }
 
+impl Permissions {
+    /// Whether the bitmask grants `permission`, the administrator bit granting everything
+    pub fn has(&self, permission: Permission) -> bool {
+        let permission = permission.bit();
+        self.0 & Permission::Administrator.bit() != 0 || self.0 & permission == permission
+    }
+
+    /// Permissions whose bit is set, without expanding the administrator bit
+    pub fn names(&self) -> Vec<Permission> {
+        PERMISSIONS
+            .iter()
+            .filter(|definition| self.0 & definition.bit != 0)
+            .map(|definition| definition.permission)
+            .collect()
+    }
+}
+
 impl From<Vec<Permission>> for Permissions {
     fn from(value: Vec<Permission>) -> Self {
-        let mut all_permissions: CapilityHexValue = 0x0;
-        for permission in value {
-            all_permissions = all_permissions | Into::<CapilityHexValue>::into(permission);
+        Permissions(
+            value
+                .into_iter()
+                .fold(0, |acc, permission| acc | permission.bit()),
+        )
+    }
+}
+
+/// Permissions a user holds in a server, every source combined
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EffectivePermissions {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    /// Bitmask of the granted permissions
+    pub permissions: Permissions,
+    /// Granted permissions, decoded from the bitmask
+    pub names: Vec<Permission>,
+    pub is_owner: bool,
+    pub is_administrator: bool,
+}
+
+impl EffectivePermissions {
+    /// Union of the bits of `roles`, which should include the default role. The owner and
+    /// the holders of `Administrator` are granted every permission
+    pub fn compute(server_id: ServerId, user_id: UserId, is_owner: bool, roles: &[Role]) -> Self {
+        let granted = Permissions(roles.iter().fold(0, |acc, role| acc | *role.permissions));
+        let is_administrator = granted.has(Permission::Administrator);
+        let permissions = if is_owner || is_administrator {
+            Permissions(ALL_PERMISSIONS)
+        } else {
+            granted
+        };
+
+        Self {
+            server_id,
+            user_id,
+            names: permissions.names(),
+            permissions,
+            is_owner,
+            is_administrator,
         }
-        Permissions(all_permissions)
     }
 }
 
@@ -296,6 +721,30 @@ mod tests {
             Permission::try_from(0x800),
             Ok(Permission::AttachFiles)
         ));
+        assert!(matches!(
+            Permission::try_from(0x1000),
+            Ok(Permission::ModerateMembers)
+        ));
+        assert!(matches!(
+            Permission::try_from(0x2000),
+            Ok(Permission::BanMembers)
+        ));
+        assert!(matches!(
+            Permission::try_from(0x4000),
+            Ok(Permission::KickMembers)
+        ));
+        assert!(matches!(
+            Permission::try_from(0x8000),
+            Ok(Permission::Connect)
+        ));
+        assert!(matches!(
+            Permission::try_from(0x10000),
+            Ok(Permission::Speak)
+        ));
+        assert!(matches!(
+            Permission::try_from(0x20000),
+            Ok(Permission::MuteMembers)
+        ));
     }
 
     #[test]
@@ -305,7 +754,8 @@ mod tests {
         assert!(Permission::try_from(0x5).is_err()); // Administrator | ManageRoles
 
         // Invalid bits should fail
-        assert!(Permission::try_from(0x1000).is_err());
+        assert!(Permission::try_from(0x40000).is_err());
+        assert!(Permission::try_from(1 << 40).is_err());
         assert!(Permission::try_from(0xFFFF).is_err());
         assert!(Permission::try_from(-1).is_err());
     }
@@ -324,7 +774,8 @@ mod tests {
         assert!(Permissions::try_from(0x3).is_ok()); // Administrator | ManageServer
         assert!(Permissions::try_from(0x803).is_ok()); // AttachFiles | ManageServer | Administrator
         assert!(Permissions::try_from(0xFF).is_ok()); // First 8 permissions
-        assert!(Permissions::try_from(0xFFF).is_ok()); // All permissions
+        assert!(Permissions::try_from(0x1FFF).is_ok()); // Permissions predating the voice and moderation bits
+        assert!(Permissions::try_from(0x3FFFF).is_ok()); // All permissions
     }
 
     #[test]
@@ -336,32 +787,220 @@ mod tests {
     #[test]
     fn test_permissions_try_from_invalid_bits() {
         // Invalid bits should fail
-        assert!(Permissions::try_from(0x1000).is_err()); // Invalid bit
-        assert!(Permissions::try_from(0x2000).is_err()); // Invalid bit
-        assert!(Permissions::try_from(0x10000).is_err()); // Invalid bit
-        assert!(Permissions::try_from(0x1003).is_err()); // Valid bits + invalid bit
+        assert!(Permissions::try_from(0x40000).is_err()); // Invalid bit
+        assert!(Permissions::try_from(1 << 32).is_err()); // Invalid bit beyond 32 bits
+        assert!(Permissions::try_from(1 << 62).is_err()); // Invalid bit
+        assert!(Permissions::try_from(0x40003).is_err()); // Valid bits + invalid bit
         assert!(Permissions::try_from(-1).is_err()); // Negative value with invalid bits
     }
 
     #[test]
     fn test_permissions_try_from_all_permissions() {
         // All valid permissions combined should work
-        let all_perms =
-            0x1 | 0x2 | 0x4 | 0x8 | 0x10 | 0x20 | 0x40 | 0x80 | 0x100 | 0x200 | 0x400 | 0x800;
+        let all_perms = 0x1
+            | 0x2
+            | 0x4
+            | 0x8
+            | 0x10
+            | 0x20
+            | 0x40
+            | 0x80
+            | 0x100
+            | 0x200
+            | 0x400
+            | 0x800
+            | 0x1000
+            | 0x2000
+            | 0x4000
+            | 0x8000
+            | 0x10000
+            | 0x20000;
         assert!(Permissions::try_from(all_perms).is_ok());
-        assert_eq!(all_perms, 0xFFF);
+        assert_eq!(all_perms, ALL_PERMISSIONS);
     }
 
     #[test]
-    fn test_permission_into_capability_hex() {
-        // Test conversion to hex values
-        let admin: CapilityHexValue = Permission::Administrator.into();
-        assert_eq!(admin, 0x1);
+    fn test_permission_bit() {
+        assert_eq!(Permission::Administrator.bit(), 0x1);
+        assert_eq!(Permission::ManageServer.bit(), 0x2);
+        assert_eq!(Permission::AttachFiles.bit(), 0x800);
+        assert_eq!(Permission::MuteMembers.bit(), 0x20000);
+    }
 
-        let manage_server: CapilityHexValue = Permission::ManageServer.into();
-        assert_eq!(manage_server, 0x2);
+    #[test]
+    fn test_permission_registry_bits_are_distinct_single_bits() {
+        for (index, definition) in PERMISSIONS.iter().enumerate() {
+            assert_eq!(definition.bit.count_ones(), 1);
+            assert!(
+                PERMISSIONS[..index]
+                    .iter()
+                    .all(|other| other.bit != definition.bit
+                        && other.permission != definition.permission)
+            );
+        }
+    }
 
-        let attach_files: CapilityHexValue = Permission::AttachFiles.into();
-        assert_eq!(attach_files, 0x800);
+    #[test]
+    fn test_permissions_input_accepts_bitmask_names_and_preset() {
+        let bitmask: PermissionsInput = serde_json::from_value(serde_json::json!(0xC0)).unwrap();
+        let names: PermissionsInput =
+            serde_json::from_value(serde_json::json!(["ViewChannels", "SendMessages"])).unwrap();
+        let preset: PermissionsInput = serde_json::from_value(serde_json::json!("Admin")).unwrap();
+
+        assert_eq!(bitmask.bits(), 0xC0);
+        assert_eq!(names.bits(), 0xC0);
+        assert_eq!(preset.bits(), Permission::Administrator.bit());
+        assert!(serde_json::from_value::<PermissionsInput>(serde_json::json!("Owner")).is_err());
+    }
+
+    #[test]
+    fn test_role_presets_only_hold_registered_bits() {
+        for preset in [
+            RolePreset::Admin,
+            RolePreset::Moderator,
+            RolePreset::Member,
+            RolePreset::Muted,
+            RolePreset::ReadOnly,
+        ] {
+            let permissions = Permissions::from(preset);
+            assert!(Permissions::try_from(*permissions).is_ok());
+            assert_eq!(permissions.names(), preset.permissions());
+        }
+        let muted = Permissions::from(RolePreset::Muted);
+        for permission in [Permission::SendMessages, Permission::Speak] {
+            assert!(!muted.has(permission));
+        }
+        assert!(Permissions::from(RolePreset::Moderator).has(Permission::KickMembers));
+    }
+
+    fn role_at(position: i32, permissions: i64) -> Role {
+        Role {
+            id: RoleId(Uuid::new_v4()),
+            server_id: ServerId(Uuid::new_v4()),
+            name: format!("role-{position}"),
+            permissions: Permissions(permissions),
+            permission_names: Permissions(permissions).names(),
+            position,
+            is_default: false,
+            appearance: RoleAppearance::default(),
+            icon_url: None,
+            member_count: None,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_role_authority_only_manages_lower_roles() {
+        let authority = RoleAuthority::from_roles(&[role_at(0, 0x40), role_at(3, 0x4)]);
+        assert!(authority.check_position(2).is_ok());
+        assert!(matches!(
+            authority.check_position(3),
+            Err(CoreError::RoleHierarchy)
+        ));
+        assert!(matches!(
+            authority.check_position(4),
+            Err(CoreError::RoleHierarchy)
+        ));
+
+        // Holding only the default role manages nothing
+        let authority = RoleAuthority::from_roles(&[role_at(0, 0x40)]);
+        assert!(authority.check_position(0).is_err());
+
+        assert!(RoleAuthority::Owner.check_position(i32::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_role_authority_never_grants_unheld_bits() {
+        let authority = RoleAuthority::from_roles(&[role_at(0, 0x40), role_at(3, 0x4 | 0x80)]);
+        assert!(
+            authority
+                .check_permissions(&Permissions(0x40 | 0x80))
+                .is_ok()
+        );
+        assert!(matches!(
+            authority.check_permissions(&Permissions(0x1)),
+            Err(CoreError::PermissionEscalation)
+        ));
+
+        let administrator = RoleAuthority::from_roles(&[role_at(2, 0x1)]);
+        assert!(
+            administrator
+                .check_permissions(&Permissions(0x1FFF))
+                .is_ok()
+        );
+        assert!(
+            RoleAuthority::Owner
+                .check_permissions(&Permissions(0x1))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_permissions_has() {
+        let moderator = Permissions(0x1000 | 0x40);
+        assert!(moderator.has(Permission::ModerateMembers));
+        assert!(moderator.has(Permission::ViewChannels));
+        assert!(!moderator.has(Permission::ManageRoles));
+
+        // Administrator grants every permission
+        let administrator = Permissions(0x1);
+        assert!(administrator.has(Permission::ModerateMembers));
+    }
+
+    #[test]
+    fn test_permissions_names() {
+        assert_eq!(
+            Permissions(0x40 | 0x80 | 0x1000).names(),
+            vec![
+                Permission::ViewChannels,
+                Permission::SendMessages,
+                Permission::ModerateMembers
+            ]
+        );
+        assert!(Permissions(0).names().is_empty());
+        assert_eq!(
+            Permissions(ALL_PERMISSIONS).names().len(),
+            PERMISSIONS.len()
+        );
+    }
+
+    #[test]
+    fn test_effective_permissions_union_of_roles() {
+        let server_id = ServerId(Uuid::new_v4());
+        let user_id = UserId(Uuid::new_v4());
+
+        let member = EffectivePermissions::compute(
+            server_id,
+            user_id,
+            false,
+            &[role_at(0, 0x40 | 0x80), role_at(2, 0x200)],
+        );
+        assert_eq!(member.permissions.0, 0x40 | 0x80 | 0x200);
+        assert_eq!(
+            member.names,
+            vec![
+                Permission::ViewChannels,
+                Permission::SendMessages,
+                Permission::ChangeNickname
+            ]
+        );
+        assert!(!member.is_administrator);
+    }
+
+    #[test]
+    fn test_effective_permissions_overrides() {
+        let server_id = ServerId(Uuid::new_v4());
+        let user_id = UserId(Uuid::new_v4());
+
+        let administrator =
+            EffectivePermissions::compute(server_id, user_id, false, &[role_at(1, 0x1)]);
+        assert!(administrator.is_administrator);
+        assert_eq!(administrator.permissions.0, ALL_PERMISSIONS);
+
+        let owner = EffectivePermissions::compute(server_id, user_id, true, &[]);
+        assert!(owner.is_owner);
+        assert!(!owner.is_administrator);
+        assert_eq!(owner.names.len(), PERMISSIONS.len());
     }
 }
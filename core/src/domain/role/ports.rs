@@ -5,7 +5,11 @@ use uuid::Uuid;
 
 use crate::domain::{
     common::{CoreError, GetPaginated, TotalPaginatedElements},
-    role::entities::{CreateRoleInput, Role, RoleId, UpdateRoleInput, UpdateRoleRepoInput},
+    friend::entities::UserId,
+    role::entities::{
+        CreateRoleInput, EffectivePermissions, Permissions, ReorderRolesRequest, Role,
+        RoleAppearance, RoleId, UpdateRoleInput, UpdateRoleRepoInput,
+    },
     server::entities::ServerId,
 };
 
@@ -20,17 +24,33 @@ pub trait RoleRepository: Send + Sync {
         pagination: &GetPaginated,
         server_id: Uuid,
     ) -> impl Future<Output = Result<(Vec<Role>, TotalPaginatedElements), CoreError>> + Send;
+    /// Every role of a server, highest first
+    fn list_in_server(
+        &self,
+        server_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Role>, CoreError>> + Send;
     fn update(
         &self,
         update_role_input: UpdateRoleRepoInput,
     ) -> impl Future<Output = Result<Role, CoreError>> + Send;
     fn delete(&self, id: &RoleId) -> impl Future<Output = Result<(), CoreError>> + Send;
+    /// Give the roles, listed from lowest to highest, the positions 1 to n in one transaction.
+    /// Returns every role of the server, highest first
+    fn reorder(
+        &self,
+        server_id: Uuid,
+        role_ids: &[RoleId],
+    ) -> impl Future<Output = Result<Vec<Role>, CoreError>> + Send;
 }
 
+/// Role management is limited by the hierarchy: besides the server owner, an actor only
+/// creates, edits, deletes or assigns roles below their highest role and never grants
+/// permission bits they do not hold
 pub trait RoleService: Send + Sync {
     fn create_role(
         &self,
         create_role_input: CreateRoleInput,
+        actor_id: UserId,
     ) -> impl Future<Output = Result<Role, CoreError>> + Send;
     fn get_role(&self, role_id: &RoleId) -> impl Future<Output = Result<Role, CoreError>> + Send;
     fn list_roles_by_server(
@@ -41,8 +61,27 @@ pub trait RoleService: Send + Sync {
     fn update_role(
         &self,
         update_role_input: UpdateRoleInput,
+        actor_id: UserId,
     ) -> impl Future<Output = Result<Role, CoreError>> + Send;
-    fn delete_role(&self, role_id: &RoleId) -> impl Future<Output = Result<(), CoreError>> + Send;
+    fn delete_role(
+        &self,
+        role_id: &RoleId,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+    /// Reorder the roles of a server, the actor only moving roles below their highest one
+    fn reorder_roles(
+        &self,
+        server_id: ServerId,
+        request: ReorderRolesRequest,
+        actor_id: UserId,
+    ) -> impl Future<Output = Result<Vec<Role>, CoreError>> + Send;
+    /// What a member may do in a server: the union of the default role and their roles, the
+    /// owner and administrators being granted every permission
+    fn get_effective_permissions(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<EffectivePermissions, CoreError>> + Send;
 }
 
 #[derive(Clone)]
@@ -56,17 +95,50 @@ impl MockRoleRepository {
             roles: Arc::new(Mutex::new(Vec::new())),
         }
     }
+
+    /// Adds the default role of a server, as the server creation does
+    pub fn insert_default_role(&self, server_id: ServerId) -> Role {
+        let default_role = Role {
+            id: RoleId(*server_id),
+            server_id,
+            name: "BasicUser".to_string(),
+            permission_names: Vec::new(),
+            permissions: Permissions(0),
+            position: 0,
+            is_default: true,
+            appearance: RoleAppearance::default(),
+            icon_url: None,
+            member_count: None,
+            created_at: Utc::now(),
+            updated_at: None,
+        };
+        self.roles.lock().unwrap().push(default_role.clone());
+        default_role
+    }
 }
 
 impl RoleRepository for MockRoleRepository {
     async fn create(&self, create_role_input: CreateRoleInput) -> Result<Role, CoreError> {
         let mut roles = self.roles.lock().unwrap();
 
+        for role in roles
+            .iter_mut()
+            .filter(|role| *role.server_id == create_role_input.server_id && role.position >= 1)
+        {
+            role.position += 1;
+        }
+
         let new_role = Role {
             id: Uuid::new_v4().into(),
             server_id: ServerId(create_role_input.server_id),
             name: create_role_input.name,
+            permission_names: create_role_input.permissions.names(),
             permissions: create_role_input.permissions,
+            position: 1,
+            is_default: false,
+            appearance: create_role_input.appearance,
+            icon_url: None,
+            member_count: None,
             created_at: Utc::now(),
             updated_at: None,
         };
@@ -94,11 +166,12 @@ impl RoleRepository for MockRoleRepository {
     ) -> Result<(Vec<Role>, TotalPaginatedElements), CoreError> {
         let roles = self.roles.lock().unwrap();
 
-        let filtered_roles: Vec<Role> = roles
+        let mut filtered_roles: Vec<Role> = roles
             .iter()
             .filter(|role| *role.server_id == server_id)
             .cloned()
             .collect();
+        filtered_roles.sort_by(|a, b| b.position.cmp(&a.position));
 
         let total = filtered_roles.len() as TotalPaginatedElements;
         let start = pagination.page.saturating_sub(1) * pagination.limit;
@@ -112,6 +185,18 @@ impl RoleRepository for MockRoleRepository {
         Ok((paginated_roles, total))
     }
 
+    async fn list_in_server(&self, server_id: Uuid) -> Result<Vec<Role>, CoreError> {
+        let roles = self.roles.lock().unwrap();
+
+        let mut filtered_roles: Vec<Role> = roles
+            .iter()
+            .filter(|role| *role.server_id == server_id)
+            .cloned()
+            .collect();
+        filtered_roles.sort_by(|a, b| b.position.cmp(&a.position));
+        Ok(filtered_roles)
+    }
+
     async fn update(&self, update_role_input: UpdateRoleRepoInput) -> Result<Role, CoreError> {
         let mut roles = self.roles.lock().unwrap();
 
@@ -127,9 +212,11 @@ impl RoleRepository for MockRoleRepository {
         }
 
         if let Some(permissions) = update_role_input.permissions {
+            role.permission_names = permissions.names();
             role.permissions = permissions;
         }
 
+        role.appearance = update_role_input.appearance.apply(&role.appearance);
         role.updated_at = Some(Utc::now());
 
         Ok(role.clone())
@@ -138,15 +225,37 @@ impl RoleRepository for MockRoleRepository {
     async fn delete(&self, id: &RoleId) -> Result<(), CoreError> {
         let mut roles = self.roles.lock().unwrap();
 
-        let count_before = roles.len();
+        let deleted = roles
+            .iter()
+            .find(|role| &role.id == id)
+            .cloned()
+            .ok_or_else(|| CoreError::Error {
+                msg: format!("Role with id {} not found", id),
+            })?;
         roles.retain(|role| &role.id != id);
 
-        if roles.len() == count_before {
-            return Err(CoreError::Error {
-                msg: format!("Role with id {} not found", id),
-            });
+        for role in roles
+            .iter_mut()
+            .filter(|role| role.server_id == deleted.server_id && role.position > deleted.position)
+        {
+            role.position -= 1;
         }
 
         Ok(())
     }
+
+    async fn reorder(&self, server_id: Uuid, role_ids: &[RoleId]) -> Result<Vec<Role>, CoreError> {
+        {
+            let mut roles = self.roles.lock().unwrap();
+            for (index, role_id) in role_ids.iter().enumerate() {
+                if let Some(role) = roles
+                    .iter_mut()
+                    .find(|role| &role.id == role_id && *role.server_id == server_id)
+                {
+                    role.position = index as i32 + 1;
+                }
+            }
+        }
+        self.list_in_server(server_id).await
+    }
 }
@@ -1,3 +1,5 @@
+use futures_util::future::join_all;
+
 use crate::{
     Service,
     domain::{
@@ -5,26 +7,29 @@ use crate::{
         channel::ports::ChannelRepository,
         channel_member::ports::ChannelMemberRepository,
         common::{CoreError, GetPaginated, TotalPaginatedElements},
-        friend::ports::FriendshipRepository,
+        friend::{entities::UserId, ports::FriendshipRepository},
         health::port::HealthRepository,
         member_role::ports::MemberRoleRepository,
         outbox::ports::OutboxRepository,
         role::{
             entities::{
-                CreateRoleInput, Role, RoleError, RoleId, UpdateRoleInput, UpdateRoleRepoInput,
+                CreateRoleInput, EffectivePermissions, ReorderRolesRequest, Role, RoleAuthority,
+                RoleError, RoleId, UpdateRoleInput, UpdateRoleRepoInput,
             },
             ports::{RoleRepository, RoleService},
         },
-        server::ports::ServerRepository,
+        server::{entities::ServerId, ports::ServerRepository},
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
+        server_join_request::ports::ServerJoinRequestRepository,
         server_member::MemberRepository,
         server_pictures::ServerPicturesRepository,
         user::port::UserRepository,
     },
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> RoleService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> RoleService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -39,13 +44,32 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
-    async fn create_role(&self, create_role_input: CreateRoleInput) -> Result<Role, CoreError> {
-        self.role_repository.create(create_role_input).await
+    async fn create_role(
+        &self,
+        create_role_input: CreateRoleInput,
+        actor_id: UserId,
+    ) -> Result<Role, CoreError> {
+        let authority = self
+            .role_authority(ServerId(create_role_input.server_id), actor_id)
+            .await?;
+        // New roles are created right above the default role
+        authority.check_position(1)?;
+        authority.check_permissions(&create_role_input.permissions)?;
+        create_role_input.appearance.validate()?;
+
+        let role = self.role_repository.create(create_role_input).await?;
+        if role.appearance.has_icon {
+            return Ok(self.with_icon_upload_url(role).await);
+        }
+        Ok(role)
     }
 
     async fn get_role(&self, role_id: &RoleId) -> Result<Role, CoreError> {
-        self.role_repository.find_by_id(role_id).await
+        let role = self.role_repository.find_by_id(role_id).await?;
+        Ok(self.with_icon_download_url(role).await)
     }
 
     async fn list_roles_by_server(
@@ -53,23 +77,221 @@ where
         pagination: &GetPaginated,
         server_id: uuid::Uuid,
     ) -> Result<(Vec<Role>, TotalPaginatedElements), CoreError> {
-        self.role_repository
+        let (mut roles, total) = self
+            .role_repository
             .list_by_server(pagination, server_id)
-            .await
+            .await?;
+        let member_counts = self
+            .member_role_repository
+            .count_members_by_role(ServerId(server_id))
+            .await?;
+        for role in &mut roles {
+            role.member_count = Some(member_counts.get(&role.id).copied().unwrap_or(0));
+        }
+        let roles = join_all(
+            roles
+                .into_iter()
+                .map(|role| self.with_icon_download_url(role)),
+        )
+        .await;
+        Ok((roles, total))
     }
 
-    async fn update_role(&self, update_role_input: UpdateRoleInput) -> Result<Role, CoreError> {
+    async fn update_role(
+        &self,
+        update_role_input: UpdateRoleInput,
+        actor_id: UserId,
+    ) -> Result<Role, CoreError> {
         let repo_input = UpdateRoleRepoInput::try_from(update_role_input).map_err(|e| {
             Into::<CoreError>::into(RoleError::BadRolePayload { msg: e.to_string() })
         })?;
-        self.role_repository.update(repo_input).await
+        let role = self.role_repository.find_by_id(&repo_input.id).await?;
+        if role.is_default && (repo_input.name.is_some() || !repo_input.appearance.is_empty()) {
+            return Err(CoreError::DefaultRoleUpdate);
+        }
+        let authority = self.role_authority(role.server_id, actor_id).await?;
+        authority.check_position(role.position)?;
+        if let Some(permissions) = &repo_input.permissions {
+            authority.check_permissions(permissions)?;
+        }
+        repo_input.appearance.validate()?;
+
+        let new_icon = repo_input.appearance.has_icon == Some(true);
+        let role = self.role_repository.update(repo_input).await?;
+        if new_icon {
+            return Ok(self.with_icon_upload_url(role).await);
+        }
+        Ok(self.with_icon_download_url(role).await)
     }
 
-    async fn delete_role(&self, role_id: &RoleId) -> Result<(), CoreError> {
+    async fn delete_role(&self, role_id: &RoleId, actor_id: UserId) -> Result<(), CoreError> {
         let role = self.role_repository.find_by_id(role_id).await?;
-        if *role.id == *role.server_id {
+        if role.is_default {
             return Err(CoreError::DefaultRoleDeletion);
         }
+        self.role_authority(role.server_id, actor_id)
+            .await?
+            .check_position(role.position)?;
         self.role_repository.delete(role_id).await
     }
+
+    async fn reorder_roles(
+        &self,
+        server_id: ServerId,
+        request: ReorderRolesRequest,
+        actor_id: UserId,
+    ) -> Result<Vec<Role>, CoreError> {
+        let roles: Vec<Role> = self
+            .role_repository
+            .list_in_server(*server_id)
+            .await?
+            .into_iter()
+            .filter(|role| !role.is_default)
+            .collect();
+
+        if request.role_ids.len() != roles.len() {
+            return Err(CoreError::InvalidRoleOrder {
+                msg: format!(
+                    "expected the {} roles of the server, default role excluded",
+                    roles.len()
+                ),
+            });
+        }
+        for (index, role_id) in request.role_ids.iter().enumerate() {
+            if request.role_ids[..index].contains(role_id) {
+                return Err(CoreError::InvalidRoleOrder {
+                    msg: format!("role {} is listed twice", role_id),
+                });
+            }
+            if !roles.iter().any(|role| &role.id == role_id) {
+                return Err(CoreError::InvalidRoleOrder {
+                    msg: format!("role {} is not a role of the server", role_id),
+                });
+            }
+        }
+
+        // Roles the actor cannot manage must keep their position
+        let authority = self.role_authority(server_id, actor_id).await?;
+        for role in &roles {
+            let position = request
+                .role_ids
+                .iter()
+                .position(|role_id| role_id == &role.id)
+                .map_or(0, |index| index as i32 + 1);
+            if position != role.position {
+                authority.check_position(role.position)?;
+                authority.check_position(position)?;
+            }
+        }
+
+        self.role_repository
+            .reorder(*server_id, &request.role_ids)
+            .await
+    }
+
+    async fn get_effective_permissions(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> Result<EffectivePermissions, CoreError> {
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        let is_owner = server.owner_id == user_id;
+        if !is_owner {
+            self.member_repository
+                .find_by_server_and_user(&server_id, &user_id)
+                .await?;
+        }
+
+        let mut roles = self
+            .member_role_repository
+            .list_roles_by_user_and_server(user_id, server_id)
+            .await?;
+        // Every member holds the default role, assigned or not
+        if !roles.iter().any(|role| role.is_default) {
+            let default_role = self
+                .role_repository
+                .list_in_server(*server_id)
+                .await?
+                .into_iter()
+                .find(|role| role.is_default);
+            roles.extend(default_role);
+        }
+
+        Ok(EffectivePermissions::compute(
+            server_id, user_id, is_owner, &roles,
+        ))
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// How far an actor may manage the roles of a server, the owner being above the hierarchy
+    pub(crate) async fn role_authority(
+        &self,
+        server_id: ServerId,
+        actor_id: UserId,
+    ) -> Result<RoleAuthority, CoreError> {
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        if server.owner_id == actor_id {
+            return Ok(RoleAuthority::Owner);
+        }
+
+        let roles = self
+            .member_role_repository
+            .list_roles_by_user_and_server(actor_id, server_id)
+            .await?;
+        Ok(RoleAuthority::from_roles(&roles))
+    }
+
+    /// Position of the highest role of a user in a server, 0 when only holding the default one.
+    /// Moderation only reaches users ranked strictly below the actor's highest role.
+    pub(crate) async fn member_rank(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> Result<i32, CoreError> {
+        let roles = self
+            .member_role_repository
+            .list_roles_by_user_and_server(user_id, server_id)
+            .await?;
+        Ok(roles.iter().map(|role| role.position).max().unwrap_or(0))
+    }
+
+    /// Attach the URL to upload the icon of the role to the content service
+    async fn with_icon_upload_url(&self, mut role: Role) -> Role {
+        match self.server_pictures_repository.put_role_icon(role.id).await {
+            Ok(url) => role.icon_url = Some(url.to_string()),
+            Err(e) => tracing::error!("{}", e.to_string()),
+        }
+        role
+    }
+
+    /// Attach the URL to download the icon of the role, if it has one
+    async fn with_icon_download_url(&self, mut role: Role) -> Role {
+        if !role.appearance.has_icon {
+            return role;
+        }
+        match self.server_pictures_repository.get_role_icon(role.id).await {
+            Ok(url) => role.icon_url = Some(url.to_string()),
+            Err(e) => tracing::error!("{}", e.to_string()),
+        }
+        role
+    }
 }
@@ -1,12 +1,21 @@
-use std::ops::Deref;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+};
 
 use chrono::{DateTime, Utc};
 use events_protobuf::communities_events::{CreateServer, DeleteServer};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::domain::{common::GetPaginated, friend::entities::UserId};
+use crate::domain::{
+    channel::entities::{Channel, ChannelError, ChannelId, ChannelName, ChannelType},
+    common::{CoreError, GetPaginated},
+    friend::entities::UserId,
+    role::entities::{Permissions, Role, RoleId},
+    server_invitation::entities::ServerInvitationId,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct ServerId(pub Uuid);
@@ -65,6 +74,24 @@ impl Server {
     }
 }
 
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for Server {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            id: ServerId(row.get("id")),
+            name: row.get("name"),
+            banner_url: row.get("banner_url"),
+            picture_url: row.get("picture_url"),
+            description: row.get("description"),
+            owner_id: UserId(row.get("owner_id")),
+            visibility: row.get("visibility"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
 impl Into<CreateServer> for Server {
     fn into(self) -> CreateServer {
         CreateServer {
@@ -74,6 +101,146 @@ impl Into<CreateServer> for Server {
     }
 }
 
+/// Limited view of a server shown to people who are not members yet.
+/// It never exposes channels, roles or members.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ServerPreview {
+    pub id: ServerId,
+    pub name: String,
+    pub description: Option<String>,
+    pub picture_url: Option<String>,
+    pub member_count: u64,
+    pub visibility: ServerVisibility,
+}
+
+impl ServerPreview {
+    pub fn new(server: Server, member_count: u64) -> Self {
+        Self {
+            id: server.id,
+            name: server.name,
+            description: server.description,
+            picture_url: server.picture_url,
+            member_count,
+            visibility: server.visibility,
+        }
+    }
+}
+
+/// Private servers can only be previewed by holding one of their invitations.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ServerPreviewQuery {
+    pub invitation_id: Option<ServerInvitationId>,
+}
+
+/// How users can become members of a server
+#[derive(
+    Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type, Default, ToSchema,
+)]
+#[sqlx(type_name = "server_join_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ServerJoinMode {
+    /// Anyone can join, as long as the server is public
+    #[default]
+    Open,
+    /// Users can only join through an invitation
+    InviteOnly,
+    /// Users must apply and be approved by a moderator
+    RequestToJoin,
+}
+
+pub const MAX_JOIN_QUESTIONS: usize = 5;
+
+pub const MAX_JOIN_QUESTION_SIZE: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct ServerSettings {
+    pub server_id: ServerId,
+    pub join_mode: ServerJoinMode,
+    /// Channel receiving system and welcome messages
+    pub system_channel_id: Option<ChannelId>,
+    /// Roles assigned to every member when they join, on top of the default role
+    pub default_role_ids: Vec<RoleId>,
+    /// Whether members may change their own nickname
+    pub allow_nickname_change: bool,
+    /// Questions applicants answer when requesting to join
+    pub join_questions: Vec<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ServerSettings {
+    /// Settings of a server that never changed them
+    pub fn default_for(server_id: ServerId) -> Self {
+        Self {
+            server_id,
+            join_mode: ServerJoinMode::default(),
+            system_channel_id: None,
+            default_role_ids: Vec::new(),
+            allow_nickname_change: true,
+            join_questions: Vec::new(),
+            updated_at: None,
+        }
+    }
+
+    /// Whether users can join without an invitation or approval
+    pub fn allows_open_join(&self, visibility: &ServerVisibility) -> bool {
+        self.join_mode == ServerJoinMode::Open && visibility == &ServerVisibility::Public
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for ServerSettings {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            server_id: ServerId(row.get("server_id")),
+            join_mode: row.get("join_mode"),
+            system_channel_id: row
+                .get::<Option<Uuid>, _>("system_channel_id")
+                .map(ChannelId::from),
+            default_role_ids: row
+                .get::<Vec<Uuid>, _>("default_role_ids")
+                .into_iter()
+                .map(RoleId::from)
+                .collect(),
+            allow_nickname_change: row.get("allow_nickname_change"),
+            join_questions: row.get("join_questions"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct UpdateServerSettingsRequest {
+    pub join_mode: ServerJoinMode,
+    pub system_channel_id: Option<ChannelId>,
+    #[serde(default)]
+    pub default_role_ids: Vec<RoleId>,
+    pub allow_nickname_change: bool,
+    #[serde(default)]
+    pub join_questions: Vec<String>,
+}
+
+impl UpdateServerSettingsRequest {
+    pub fn into_settings(self, server_id: ServerId) -> ServerSettings {
+        let mut seen = HashSet::new();
+        let mut default_role_ids = self.default_role_ids;
+        default_role_ids.retain(|role_id| seen.insert(*role_id));
+        ServerSettings {
+            server_id,
+            join_mode: self.join_mode,
+            system_channel_id: self.system_channel_id,
+            default_role_ids,
+            allow_nickname_change: self.allow_nickname_change,
+            join_questions: self
+                .join_questions
+                .into_iter()
+                .map(|question| question.trim().to_string())
+                .collect(),
+            updated_at: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct InsertServerInput {
     pub name: String,
@@ -206,6 +373,428 @@ impl SearchServerQuery {
     }
 }
 
+/// Version of the template snapshot format produced by this build.
+pub const SERVER_TEMPLATE_VERSION: u32 = 1;
+
+pub const MAX_TEMPLATE_NAME_SIZE: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct ServerTemplateId(pub Uuid);
+
+impl std::fmt::Display for ServerTemplateId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Deref for ServerTemplateId {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Uuid> for ServerTemplateId {
+    fn from(uuid: Uuid) -> Self {
+        ServerTemplateId(uuid)
+    }
+}
+
+/// Server-wide settings captured by a template
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TemplateSettings {
+    pub description: Option<String>,
+    pub visibility: ServerVisibility,
+}
+
+/// A role as stored in a template.
+/// The default role is flagged with `is_default`
+/// so its permissions are applied to the default role of the instantiated server.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TemplateRole {
+    pub name: String,
+    pub permissions: Permissions,
+    pub is_default: bool,
+}
+
+/// A channel as stored in a template.
+/// Channels reference their parent through `parent_key`, the `key` of another channel
+/// of the same snapshot, since channel ids are not kept.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TemplateChannel {
+    pub key: u32,
+    pub name: String,
+    pub channel_type: ChannelType,
+    pub parent_key: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ServerTemplateSnapshot {
+    pub version: u32,
+    pub settings: TemplateSettings,
+    pub roles: Vec<TemplateRole>,
+    pub channels: Vec<TemplateChannel>,
+}
+
+impl ServerTemplateSnapshot {
+    /// Builds a snapshot from the current state of a server.
+    /// Roles are ordered from lowest to highest and channels so that a parent
+    /// always comes before its children.
+    pub fn from_server(server: &Server, roles: &[Role], channels: &[Channel]) -> Self {
+        let mut roles: Vec<&Role> = roles.iter().collect();
+        roles.sort_by_key(|role| role.position);
+        let roles = roles
+            .into_iter()
+            .map(|role| TemplateRole {
+                name: role.name.clone(),
+                permissions: role.permissions.clone(),
+                is_default: role.is_default,
+            })
+            .collect();
+
+        let mut ordered: Vec<&Channel> = channels
+            .iter()
+            .filter(|channel| channel.parent_id.is_none())
+            .collect();
+        let mut index = 0;
+        while index < ordered.len() {
+            let parent_id = ordered[index].id;
+            ordered.extend(
+                channels
+                    .iter()
+                    .filter(|channel| channel.parent_id == Some(parent_id)),
+            );
+            index += 1;
+        }
+
+        let keys: HashMap<ChannelId, u32> = ordered
+            .iter()
+            .enumerate()
+            .map(|(key, channel)| (channel.id, key as u32))
+            .collect();
+
+        let channels = ordered
+            .iter()
+            .map(|channel| TemplateChannel {
+                key: keys[&channel.id],
+                name: channel.name.clone(),
+                channel_type: channel.channel_type,
+                parent_key: channel.parent_id.and_then(|id| keys.get(&id).copied()),
+            })
+            .collect();
+
+        Self {
+            version: SERVER_TEMPLATE_VERSION,
+            settings: TemplateSettings {
+                description: server.description.clone(),
+                visibility: server.visibility.clone(),
+            },
+            roles,
+            channels,
+        }
+    }
+
+    /// Checks that the snapshot can be instantiated as is
+    pub fn validate(&self) -> Result<(), CoreError> {
+        if self.version != SERVER_TEMPLATE_VERSION {
+            return Err(CoreError::InvalidServerTemplate {
+                msg: format!("unsupported template version {}", self.version),
+            });
+        }
+
+        let mut role_names = HashSet::new();
+        for role in &self.roles {
+            if role.name.trim().is_empty() {
+                return Err(CoreError::InvalidServerTemplate {
+                    msg: "role name cannot be empty".to_string(),
+                });
+            }
+            if !role_names.insert(role.name.as_str()) {
+                return Err(CoreError::InvalidServerTemplate {
+                    msg: format!("duplicated role name {}", role.name),
+                });
+            }
+            Permissions::try_from(*role.permissions)
+                .map_err(|e| CoreError::InvalidServerTemplate { msg: e.to_string() })?;
+        }
+        if self.roles.iter().filter(|role| role.is_default).count() > 1 {
+            return Err(CoreError::InvalidServerTemplate {
+                msg: "a template can only have one default role".to_string(),
+            });
+        }
+
+        let mut seen: HashMap<u32, ChannelType> = HashMap::new();
+        for channel in &self.channels {
+            if channel.channel_type == ChannelType::Private {
+                return Err(ChannelError::WrongChannelType.into());
+            }
+            ChannelName::new(channel.name.clone()).check()?;
+            if let Some(parent_key) = channel.parent_key {
+                // Parents must be declared before their children and be folders
+                match seen.get(&parent_key) {
+                    Some(ChannelType::ServerFolder) => {}
+                    _ => {
+                        return Err(CoreError::InvalidServerTemplate {
+                            msg: format!("channel {} has an invalid parent", channel.name),
+                        });
+                    }
+                }
+            }
+            if seen.insert(channel.key, channel.channel_type).is_some() {
+                return Err(CoreError::InvalidServerTemplate {
+                    msg: format!("duplicated channel key {}", channel.key),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Permissions of the default role, if the template overrides them
+    pub fn default_role(&self) -> Option<&TemplateRole> {
+        self.roles.iter().find(|role| role.is_default)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ServerTemplate {
+    pub id: ServerTemplateId,
+    pub name: String,
+    pub description: Option<String>,
+    pub source_server_id: Option<ServerId>,
+    pub creator_id: UserId,
+    pub snapshot: ServerTemplateSnapshot,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for ServerTemplate {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        let snapshot: sqlx::types::Json<ServerTemplateSnapshot> = row.get("snapshot");
+        Self {
+            id: ServerTemplateId(row.get("id")),
+            name: row.get("name"),
+            description: row.get("description"),
+            source_server_id: row.get::<Option<Uuid>, _>("source_server_id").map(ServerId),
+            creator_id: UserId(row.get("creator_id")),
+            snapshot: snapshot.0,
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InsertServerTemplateInput {
+    pub name: String,
+    pub description: Option<String>,
+    pub source_server_id: Option<ServerId>,
+    pub creator_id: UserId,
+    pub snapshot: ServerTemplateSnapshot,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateServerTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateServerTemplateInput {
+    pub server_id: ServerId,
+    pub creator_id: UserId,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl CreateServerTemplateRequest {
+    pub fn into_input(self, server_id: ServerId, creator_id: UserId) -> CreateServerTemplateInput {
+        CreateServerTemplateInput {
+            server_id,
+            creator_id,
+            name: self.name,
+            description: self.description,
+        }
+    }
+}
+
+/// Request to create a server from a template.
+/// `description` and `visibility` fall back to the template settings when omitted.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateServerFromTemplateRequest {
+    pub name: String,
+    pub picture_url: Option<String>,
+    pub banner_url: Option<String>,
+    pub description: Option<String>,
+    pub visibility: Option<ServerVisibility>,
+}
+
+impl CreateServerFromTemplateRequest {
+    pub fn into_input(self, owner_id: UserId, settings: &TemplateSettings) -> InsertServerInput {
+        InsertServerInput {
+            name: self.name,
+            owner_id,
+            picture_url: self.picture_url,
+            banner_url: self.banner_url,
+            description: self.description.or_else(|| settings.description.clone()),
+            visibility: self
+                .visibility
+                .unwrap_or_else(|| settings.visibility.clone()),
+        }
+    }
+}
+
+/// Version of the export bundle format produced by this build.
+pub const SERVER_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ExportedServer {
+    pub id: ServerId,
+    pub name: String,
+    pub banner_url: Option<String>,
+    pub picture_url: Option<String>,
+    pub description: Option<String>,
+    pub visibility: ServerVisibility,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ExportedRole {
+    pub id: RoleId,
+    pub name: String,
+    pub permissions: Permissions,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ExportedChannel {
+    pub id: ChannelId,
+    pub name: String,
+    pub channel_type: ChannelType,
+    pub parent_id: Option<ChannelId>,
+}
+
+/// A member of an exported server.
+/// `role_ids` reference `ExportedRole::id` and never contain the default role,
+/// which every member holds implicitly.
+/// On import only the importer keeps their nickname and roles: the other members are
+/// invited and join with the default role and no nickname.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ExportedMember {
+    pub user_id: UserId,
+    pub nickname: Option<String>,
+    pub joined_at: DateTime<Utc>,
+    pub role_ids: Vec<RoleId>,
+}
+
+/// Portable, versioned JSON document describing a whole server.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ServerExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub server: ExportedServer,
+    pub roles: Vec<ExportedRole>,
+    pub channels: Vec<ExportedChannel>,
+    pub members: Vec<ExportedMember>,
+}
+
+impl ServerExport {
+    /// Converts the bundle structure into a template snapshot, ordering channels
+    /// parent first and replacing channel ids by snapshot keys.
+    pub fn to_snapshot(&self) -> Result<ServerTemplateSnapshot, CoreError> {
+        if self.version != SERVER_EXPORT_VERSION {
+            return Err(CoreError::InvalidServerExport {
+                msg: format!("unsupported export version {}", self.version),
+            });
+        }
+
+        let mut keys: HashMap<ChannelId, u32> = HashMap::new();
+        let mut channels = Vec::with_capacity(self.channels.len());
+        // Each pass places the channels whose parent is already placed,
+        // a pass without progress means a missing parent or a cycle
+        while channels.len() < self.channels.len() {
+            let placed = channels.len();
+            for channel in &self.channels {
+                if keys.contains_key(&channel.id) {
+                    continue;
+                }
+                let parent_key = match channel.parent_id {
+                    Some(parent_id) => match keys.get(&parent_id) {
+                        Some(key) => Some(*key),
+                        None => continue,
+                    },
+                    None => None,
+                };
+                let key = channels.len() as u32;
+                keys.insert(channel.id, key);
+                channels.push(TemplateChannel {
+                    key,
+                    name: channel.name.clone(),
+                    channel_type: channel.channel_type,
+                    parent_key,
+                });
+            }
+            if channels.len() == placed {
+                return Err(CoreError::InvalidServerExport {
+                    msg: "channel hierarchy references unknown parents".to_string(),
+                });
+            }
+        }
+
+        let snapshot = ServerTemplateSnapshot {
+            version: SERVER_TEMPLATE_VERSION,
+            settings: TemplateSettings {
+                description: self.server.description.clone(),
+                visibility: self.server.visibility.clone(),
+            },
+            roles: self
+                .roles
+                .iter()
+                .map(|role| TemplateRole {
+                    name: role.name.clone(),
+                    permissions: role.permissions.clone(),
+                    is_default: role.is_default,
+                })
+                .collect(),
+            channels,
+        };
+        snapshot
+            .validate()
+            .map_err(|e| CoreError::InvalidServerExport { msg: e.to_string() })?;
+
+        let role_ids: HashSet<RoleId> = self.roles.iter().map(|role| role.id).collect();
+        let mut user_ids = HashSet::new();
+        for member in &self.members {
+            if !user_ids.insert(member.user_id) {
+                return Err(CoreError::InvalidServerExport {
+                    msg: format!("duplicated member {}", member.user_id),
+                });
+            }
+            if let Some(role_id) = member.role_ids.iter().find(|id| !role_ids.contains(id)) {
+                return Err(CoreError::InvalidServerExport {
+                    msg: format!(
+                        "member {} references unknown role {}",
+                        member.user_id, role_id
+                    ),
+                });
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+/// Input for recreating an exported server under a new owner.
+/// `snapshot` is the validated structure computed from `bundle`.
+#[derive(Debug, Clone)]
+pub struct ImportServerInput {
+    pub owner_id: UserId,
+    pub bundle: ServerExport,
+    pub snapshot: ServerTemplateSnapshot,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +915,151 @@ mod tests {
         let safe = query.safe_pagination();
         assert_eq!(safe.limit, 50);
     }
+
+    fn template_with_channels(channels: Vec<TemplateChannel>) -> ServerTemplateSnapshot {
+        ServerTemplateSnapshot {
+            version: SERVER_TEMPLATE_VERSION,
+            settings: TemplateSettings {
+                description: None,
+                visibility: ServerVisibility::Public,
+            },
+            roles: vec![],
+            channels,
+        }
+    }
+
+    #[test]
+    fn test_template_validate_accepts_nested_channels() {
+        let snapshot = template_with_channels(vec![
+            TemplateChannel {
+                key: 0,
+                name: "General".to_string(),
+                channel_type: ChannelType::ServerFolder,
+                parent_key: None,
+            },
+            TemplateChannel {
+                key: 1,
+                name: "chat".to_string(),
+                channel_type: ChannelType::ServerText,
+                parent_key: Some(0),
+            },
+        ]);
+
+        assert!(snapshot.validate().is_ok());
+    }
+
+    #[test]
+    fn test_template_validate_rejects_unknown_parent() {
+        let snapshot = template_with_channels(vec![TemplateChannel {
+            key: 0,
+            name: "chat".to_string(),
+            channel_type: ChannelType::ServerText,
+            parent_key: Some(42),
+        }]);
+
+        assert!(matches!(
+            snapshot.validate(),
+            Err(CoreError::InvalidServerTemplate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_template_validate_rejects_non_folder_parent() {
+        let snapshot = template_with_channels(vec![
+            TemplateChannel {
+                key: 0,
+                name: "chat".to_string(),
+                channel_type: ChannelType::ServerText,
+                parent_key: None,
+            },
+            TemplateChannel {
+                key: 1,
+                name: "voice".to_string(),
+                channel_type: ChannelType::ServerVoice,
+                parent_key: Some(0),
+            },
+        ]);
+
+        assert!(snapshot.validate().is_err());
+    }
+
+    fn export_with(channels: Vec<ExportedChannel>, members: Vec<ExportedMember>) -> ServerExport {
+        let server_id = ServerId(Uuid::new_v4());
+        ServerExport {
+            version: SERVER_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            server: ExportedServer {
+                id: server_id,
+                name: "exported".to_string(),
+                banner_url: None,
+                picture_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            },
+            roles: vec![ExportedRole {
+                id: RoleId(server_id.0),
+                name: "BasicUser".to_string(),
+                permissions: Permissions(0x40),
+                is_default: true,
+            }],
+            channels,
+            members,
+        }
+    }
+
+    #[test]
+    fn test_export_to_snapshot_orders_parents_first() {
+        let folder_id = ChannelId(Uuid::new_v4());
+        let bundle = export_with(
+            vec![
+                ExportedChannel {
+                    id: ChannelId(Uuid::new_v4()),
+                    name: "chat".to_string(),
+                    channel_type: ChannelType::ServerText,
+                    parent_id: Some(folder_id),
+                },
+                ExportedChannel {
+                    id: folder_id,
+                    name: "General".to_string(),
+                    channel_type: ChannelType::ServerFolder,
+                    parent_id: None,
+                },
+            ],
+            vec![],
+        );
+
+        let snapshot = bundle.to_snapshot().expect("bundle should be valid");
+
+        assert_eq!(snapshot.channels[0].name, "General");
+        assert_eq!(
+            snapshot.channels[1].parent_key,
+            Some(snapshot.channels[0].key)
+        );
+    }
+
+    #[test]
+    fn test_export_to_snapshot_rejects_unknown_member_role() {
+        let bundle = export_with(
+            vec![],
+            vec![ExportedMember {
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+                joined_at: Utc::now(),
+                role_ids: vec![RoleId(Uuid::new_v4())],
+            }],
+        );
+
+        assert!(matches!(
+            bundle.to_snapshot(),
+            Err(CoreError::InvalidServerExport { .. })
+        ));
+    }
+
+    #[test]
+    fn test_export_to_snapshot_rejects_unsupported_version() {
+        let mut bundle = export_with(vec![], vec![]);
+        bundle.version = SERVER_EXPORT_VERSION + 1;
+
+        assert!(bundle.to_snapshot().is_err());
+    }
 }
@@ -1,9 +1,26 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use crate::domain::{
-    common::{CoreError, GetPaginated, TotalPaginatedElements},
+    channel::{
+        entities::{ChannelId, CreateChannelRepoInput},
+        ports::{ChannelRepository, MockChannelRepository},
+    },
+    common::{CoreError, Cursor, CursorPage, GetPaginated, TotalPaginatedElements, next_page},
     friend::entities::UserId,
-    server::entities::{InsertServerInput, Server, ServerId, UpdateServerInput},
+    role::{
+        entities::{CreateRoleInput, RoleAppearance},
+        ports::{MockRoleRepository, RoleRepository},
+    },
+    server::entities::{
+        CreateServerFromTemplateRequest, CreateServerTemplateInput, ExportedServer,
+        ImportServerInput, InsertServerInput, InsertServerTemplateInput, SERVER_EXPORT_VERSION,
+        Server, ServerExport, ServerId, ServerPreview, ServerSettings, ServerTemplate,
+        ServerTemplateId, ServerTemplateSnapshot, UpdateServerInput, UpdateServerSettingsRequest,
+    },
+    server_invitation::entities::ServerInvitationId,
 };
 
 pub trait ServerRepository: Send + Sync {
@@ -21,6 +38,13 @@ pub trait ServerRepository: Send + Sync {
         pagination: &GetPaginated,
         user_id: UserId,
     ) -> impl Future<Output = Result<(Vec<Server>, TotalPaginatedElements), CoreError>> + Send;
+    /// Servers of the user after the cursor, keyed by the user's join date then server id
+    fn list_user_servers_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<CursorPage<Server>, CoreError>> + Send;
     fn update(
         &self,
         input: UpdateServerInput,
@@ -31,6 +55,38 @@ pub trait ServerRepository: Send + Sync {
         query: Option<String>,
         pagination: &GetPaginated,
     ) -> impl Future<Output = Result<(Vec<Server>, TotalPaginatedElements), CoreError>> + Send;
+    /// Creates the server, its owner membership, and every role and channel of the
+    /// snapshot in a single transaction, writing the matching outbox events.
+    fn insert_from_template(
+        &self,
+        input: InsertServerInput,
+        snapshot: ServerTemplateSnapshot,
+    ) -> impl Future<Output = Result<Server, CoreError>> + Send;
+    fn insert_template(
+        &self,
+        input: InsertServerTemplateInput,
+    ) -> impl Future<Output = Result<ServerTemplate, CoreError>> + Send;
+    fn find_template_by_id(
+        &self,
+        id: &ServerTemplateId,
+    ) -> impl Future<Output = Result<ServerTemplate, CoreError>> + Send;
+    /// Reads the server, its roles, channels and members from a single consistent snapshot
+    fn export(&self, id: &ServerId)
+    -> impl Future<Output = Result<ServerExport, CoreError>> + Send;
+    /// Recreates an exported server under a new owner with fresh ids, in a single transaction
+    fn import(
+        &self,
+        input: ImportServerInput,
+    ) -> impl Future<Output = Result<Server, CoreError>> + Send;
+    /// Returns the stored settings, or the defaults when the server never changed them
+    fn find_settings(
+        &self,
+        id: &ServerId,
+    ) -> impl Future<Output = Result<ServerSettings, CoreError>> + Send;
+    fn upsert_settings(
+        &self,
+        settings: ServerSettings,
+    ) -> impl Future<Output = Result<ServerSettings, CoreError>> + Send;
 }
 
 /// A service for managing server operations in the application.
@@ -156,22 +212,167 @@ pub trait ServerService: Send + Sync {
         user_id: UserId,
     ) -> impl Future<Output = Result<(Vec<Server>, TotalPaginatedElements), CoreError>> + Send;
 
+    /// List the servers of a user after a cursor, most recently joined first
+    fn list_user_servers_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<CursorPage<Server>, CoreError>> + Send;
+
     fn search_or_discover(
         &self,
         query: Option<String>,
         pagination: &GetPaginated,
     ) -> impl Future<Output = Result<(Vec<Server>, TotalPaginatedElements), CoreError>> + Send;
+
+    /// Saves a snapshot of a server's roles, channel tree and settings as a template.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The source server, the creator and the template name
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Future` that resolves to:
+    /// - `Ok(ServerTemplate)` - The saved template
+    /// - `Err(CoreError::ServerNotFound)` - The source server does not exist
+    /// - `Err(CoreError)` - If the snapshot is invalid or repository operation fails
+    fn create_template(
+        &self,
+        input: CreateServerTemplateInput,
+    ) -> impl Future<Output = Result<ServerTemplate, CoreError>> + Send;
+
+    fn get_template(
+        &self,
+        template_id: &ServerTemplateId,
+    ) -> impl Future<Output = Result<ServerTemplate, CoreError>> + Send;
+
+    /// Creates a new server from a template.
+    ///
+    /// The server, the owner membership, the template roles and the channel tree are
+    /// created atomically, so a failure never leaves a half-built server behind.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Future` that resolves to:
+    /// - `Ok(Server)` - The newly created server
+    /// - `Err(CoreError::ServerTemplateNotFound)` - No template exists with the given ID
+    /// - `Err(CoreError)` - If validation fails or repository operation fails
+    fn create_server_from_template(
+        &self,
+        template_id: &ServerTemplateId,
+        owner_id: UserId,
+        request: CreateServerFromTemplateRequest,
+    ) -> impl Future<Output = Result<Server, CoreError>> + Send;
+
+    /// Exports a server as a portable, versioned JSON bundle.
+    ///
+    /// The bundle contains the server fields, roles with their permission bitmasks,
+    /// the channel hierarchy and the members with their nicknames and role assignments.
+    fn export_server(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<ServerExport, CoreError>> + Send;
+
+    /// Recreates a server from an export bundle, owned by the owner of the exported server.
+    ///
+    /// Every id of the bundle is remapped, and the same outbox events as a regular
+    /// creation are emitted for the server, its roles and channels. The caller owns the
+    /// copy and is the only one to join: the other exported members receive a pending
+    /// invitation, except those banned from the exported server when it still exists.
+    /// Their nicknames and roles are not restored when they accept.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Future` that resolves to:
+    /// - `Ok(Server)` - The newly created server
+    /// - `Err(CoreError::InvalidServerExport)` - The bundle is malformed or has an unsupported version
+    /// - `Err(CoreError)` - If repository operation fails
+    fn import_server(
+        &self,
+        owner_id: UserId,
+        bundle: ServerExport,
+    ) -> impl Future<Output = Result<Server, CoreError>> + Send;
+
+    fn get_server_settings(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<ServerSettings, CoreError>> + Send;
+
+    /// Replaces the settings of a server.
+    ///
+    /// The system channel and the default roles must belong to the server, and the
+    /// default role itself cannot be listed since every member already has it.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Future` that resolves to:
+    /// - `Ok(ServerSettings)` - The updated settings
+    /// - `Err(CoreError::InvalidServerSettings)` - A channel or role does not belong to the server
+    /// - `Err(CoreError::ServerNotFound)` - No server exists with the given ID
+    fn update_server_settings(
+        &self,
+        server_id: &ServerId,
+        request: UpdateServerSettingsRequest,
+    ) -> impl Future<Output = Result<ServerSettings, CoreError>> + Send;
+
+    /// Builds the limited preview of a server shown to non-members.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Future` that resolves to:
+    /// - `Ok(ServerPreview)` - Name, description, picture, member count and visibility
+    /// - `Err(CoreError::ServerNotFound)` - No server exists with the given ID
+    fn get_server_preview(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<ServerPreview, CoreError>> + Send;
+
+    /// Returns the preview of a server on behalf of a user who may not be a member.
+    ///
+    /// Public servers can be previewed by anyone. Private servers require membership
+    /// or an invitation to that server the user can still accept.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Future` that resolves to:
+    /// - `Ok(ServerPreview)` - The viewer is allowed to see the preview
+    /// - `Err(CoreError::Forbidden)` - The server is private and the viewer has no valid invitation
+    /// - `Err(CoreError::ServerNotFound)` - No server exists with the given ID
+    fn preview_server(
+        &self,
+        server_id: &ServerId,
+        viewer_id: &UserId,
+        invitation_id: Option<ServerInvitationId>,
+    ) -> impl Future<Output = Result<ServerPreview, CoreError>> + Send;
 }
 
 #[derive(Clone)]
 pub struct MockServerRepository {
     servers: Arc<Mutex<Vec<Server>>>,
+    templates: Arc<Mutex<Vec<ServerTemplate>>>,
+    settings: Arc<Mutex<Vec<ServerSettings>>>,
+    role_repository: MockRoleRepository,
+    channel_repository: MockChannelRepository,
 }
 
 impl MockServerRepository {
     pub fn new() -> Self {
+        Self::with_repositories(MockRoleRepository::new(), MockChannelRepository::new())
+    }
+
+    /// Shares the role and channel mocks the servers created from a template are filled into
+    pub fn with_repositories(
+        role_repository: MockRoleRepository,
+        channel_repository: MockChannelRepository,
+    ) -> Self {
         Self {
             servers: Arc::new(Mutex::new(Vec::new())),
+            templates: Arc::new(Mutex::new(Vec::new())),
+            settings: Arc::new(Mutex::new(Vec::new())),
+            role_repository,
+            channel_repository,
         }
     }
 }
@@ -285,6 +486,27 @@ impl ServerRepository for MockServerRepository {
         Ok((paginated_servers, total))
     }
 
+    async fn list_user_servers_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        _user_id: UserId,
+    ) -> Result<CursorPage<Server>, CoreError> {
+        // Memberships are not tracked here, creation order stands in for join order
+        let cursor = |server: &Server| Cursor::new(server.created_at, server.id.0);
+        let mut servers = self.servers.lock().unwrap().clone();
+        let total = servers.len() as u64;
+        servers.sort_by_key(|server| std::cmp::Reverse(cursor(server)));
+
+        let page: Vec<Server> = servers
+            .into_iter()
+            .filter(|server| after.is_none_or(|after| cursor(server) < after))
+            .take(limit as usize + 1)
+            .collect();
+
+        Ok(next_page(page, limit, cursor, total))
+    }
+
     async fn search_or_discover(
         &self,
         query: Option<String>,
@@ -310,4 +532,136 @@ impl ServerRepository for MockServerRepository {
 
         Ok((paginated_servers, total))
     }
+
+    async fn insert_from_template(
+        &self,
+        input: InsertServerInput,
+        snapshot: ServerTemplateSnapshot,
+    ) -> Result<Server, CoreError> {
+        let server = self.insert(input).await?;
+
+        // New roles enter at the bottom, so the highest ones are created first
+        for template_role in snapshot.roles.iter().filter(|role| !role.is_default).rev() {
+            self.role_repository
+                .create(CreateRoleInput {
+                    server_id: *server.id,
+                    name: template_role.name.clone(),
+                    permissions: template_role.permissions.clone(),
+                    appearance: RoleAppearance::default(),
+                })
+                .await?;
+        }
+
+        let mut channel_ids: HashMap<u32, ChannelId> = HashMap::new();
+        for template_channel in &snapshot.channels {
+            let channel = self
+                .channel_repository
+                .create(CreateChannelRepoInput {
+                    name: template_channel.name.clone(),
+                    server_id: Some(server.id),
+                    parent_id: template_channel
+                        .parent_key
+                        .and_then(|key| channel_ids.get(&key).copied()),
+                    channel_type: template_channel.channel_type,
+                })
+                .await?;
+            channel_ids.insert(template_channel.key, channel.id);
+        }
+
+        Ok(server)
+    }
+
+    async fn insert_template(
+        &self,
+        input: InsertServerTemplateInput,
+    ) -> Result<ServerTemplate, CoreError> {
+        let mut templates = self.templates.lock().unwrap();
+
+        let template = ServerTemplate {
+            id: ServerTemplateId::from(uuid::Uuid::new_v4()),
+            name: input.name,
+            description: input.description,
+            source_server_id: input.source_server_id,
+            creator_id: input.creator_id,
+            snapshot: input.snapshot,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+        };
+
+        templates.push(template.clone());
+
+        Ok(template)
+    }
+
+    async fn find_template_by_id(
+        &self,
+        id: &ServerTemplateId,
+    ) -> Result<ServerTemplate, CoreError> {
+        let templates = self.templates.lock().unwrap();
+
+        templates
+            .iter()
+            .find(|t| &t.id == id)
+            .cloned()
+            .ok_or_else(|| CoreError::ServerTemplateNotFound { id: *id })
+    }
+
+    async fn export(&self, id: &ServerId) -> Result<ServerExport, CoreError> {
+        let server = self.find_by_id(id).await?;
+
+        Ok(ServerExport {
+            version: SERVER_EXPORT_VERSION,
+            exported_at: chrono::Utc::now(),
+            server: ExportedServer {
+                id: server.id,
+                name: server.name,
+                banner_url: server.banner_url,
+                picture_url: server.picture_url,
+                description: server.description,
+                visibility: server.visibility,
+            },
+            roles: vec![],
+            channels: vec![],
+            members: vec![],
+        })
+    }
+
+    async fn import(&self, input: ImportServerInput) -> Result<Server, CoreError> {
+        let server = input.bundle.server;
+        self.insert_from_template(
+            InsertServerInput {
+                name: server.name,
+                owner_id: input.owner_id,
+                picture_url: server.picture_url,
+                banner_url: server.banner_url,
+                description: server.description,
+                visibility: server.visibility,
+            },
+            input.snapshot,
+        )
+        .await
+    }
+
+    async fn find_settings(&self, id: &ServerId) -> Result<ServerSettings, CoreError> {
+        let settings = self.settings.lock().unwrap();
+
+        Ok(settings
+            .iter()
+            .find(|s| &s.server_id == id)
+            .cloned()
+            .unwrap_or_else(|| ServerSettings::default_for(*id)))
+    }
+
+    async fn upsert_settings(&self, settings: ServerSettings) -> Result<ServerSettings, CoreError> {
+        let mut all_settings = self.settings.lock().unwrap();
+
+        let settings = ServerSettings {
+            updated_at: Some(chrono::Utc::now()),
+            ..settings
+        };
+        all_settings.retain(|s| s.server_id != settings.server_id);
+        all_settings.push(settings.clone());
+
+        Ok(settings)
+    }
 }
@@ -1,25 +1,36 @@
 use crate::domain::{
     authorization::ports::AuthorizationRepository,
-    channel::ports::ChannelRepository,
+    channel::{entities::ChannelType, ports::ChannelRepository},
     channel_member::ports::ChannelMemberRepository,
-    common::{CoreError, GetPaginated, TotalPaginatedElements, services::Service},
+    common::{
+        CoreError, Cursor, CursorPage, GetPaginated, TotalPaginatedElements, services::Service,
+    },
     friend::{entities::UserId, ports::FriendshipRepository},
     health::port::HealthRepository,
     member_role::ports::MemberRoleRepository,
     outbox::ports::OutboxRepository,
     role::ports::RoleRepository,
     server::{
-        entities::{InsertServerInput, Server, ServerId, UpdateServerInput},
+        entities::{
+            CreateServerFromTemplateRequest, CreateServerTemplateInput, ImportServerInput,
+            InsertServerInput, InsertServerTemplateInput, MAX_JOIN_QUESTION_SIZE,
+            MAX_JOIN_QUESTIONS, MAX_TEMPLATE_NAME_SIZE, Server, ServerExport, ServerId,
+            ServerPreview, ServerSettings, ServerTemplate, ServerTemplateId,
+            ServerTemplateSnapshot, ServerVisibility, UpdateServerInput,
+            UpdateServerSettingsRequest,
+        },
         ports::{ServerRepository, ServerService},
     },
-    server_invitation::ports::ServerInvitationRepository,
-    server_member::MemberRepository,
+    server_ban::ports::ServerBanRepository,
+    server_invitation::{entities::ServerInvitationId, ports::ServerInvitationRepository},
+    server_join_request::ports::ServerJoinRequestRepository,
+    server_member::{MemberRepository, entities::MemberFilter},
     server_pictures::ServerPicturesRepository,
     user::port::UserRepository,
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> ServerService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> ServerService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -34,6 +45,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn create_server(&self, input: InsertServerInput) -> Result<Server, CoreError> {
         if input.name.trim().is_empty() {
@@ -127,29 +140,26 @@ where
         pagination: &GetPaginated,
         user_id: UserId,
     ) -> Result<(Vec<Server>, TotalPaginatedElements), CoreError> {
-        let (mut servers, total) = self
+        let (servers, total) = self
             .server_repository
             .list_user_servers(pagination, user_id)
             .await?;
 
-        let server_ids = servers.iter().map(|server| return server.id).collect();
-        let server_urls_map = self
-            .server_pictures_repository
-            .get_all_for_servers(server_ids)
-            .await;
+        Ok((self.with_server_pictures(servers).await, total))
+    }
 
-        let servers: Vec<Server> = servers
-            .iter_mut()
-            .map(|server| {
-                if let Some(server_urls) = server_urls_map.get(&server.id) {
-                    server.banner_url = Some(server_urls.banner.to_string());
-                    server.picture_url = Some(server_urls.picture.to_string());
-                }
-                return server.to_owned();
-            })
-            .collect();
+    async fn list_user_servers_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: UserId,
+    ) -> Result<CursorPage<Server>, CoreError> {
+        let (servers, total, next_cursor) = self
+            .server_repository
+            .list_user_servers_after(after, limit, user_id)
+            .await?;
 
-        Ok((servers, total))
+        Ok((self.with_server_pictures(servers).await, total, next_cursor))
     }
 
     async fn search_or_discover(
@@ -181,4 +191,283 @@ where
 
         Ok((servers, total))
     }
+
+    async fn create_template(
+        &self,
+        input: CreateServerTemplateInput,
+    ) -> Result<ServerTemplate, CoreError> {
+        let name = input.name.trim().to_string();
+        if name.is_empty() || name.len() > MAX_TEMPLATE_NAME_SIZE {
+            return Err(CoreError::InvalidServerTemplate {
+                msg: format!(
+                    "template name must be between 1 and {} characters",
+                    MAX_TEMPLATE_NAME_SIZE
+                ),
+            });
+        }
+
+        let server = self.server_repository.find_by_id(&input.server_id).await?;
+
+        let roles = self.role_repository.list_in_server(*server.id).await?;
+
+        let channels = self.channel_repository.list_in_server(server.id).await?;
+
+        let snapshot = ServerTemplateSnapshot::from_server(&server, &roles, &channels);
+        snapshot.validate()?;
+
+        self.server_repository
+            .insert_template(InsertServerTemplateInput {
+                name,
+                description: input.description,
+                source_server_id: Some(server.id),
+                creator_id: input.creator_id,
+                snapshot,
+            })
+            .await
+    }
+
+    async fn get_template(
+        &self,
+        template_id: &ServerTemplateId,
+    ) -> Result<ServerTemplate, CoreError> {
+        self.server_repository
+            .find_template_by_id(template_id)
+            .await
+    }
+
+    async fn create_server_from_template(
+        &self,
+        template_id: &ServerTemplateId,
+        owner_id: UserId,
+        request: CreateServerFromTemplateRequest,
+    ) -> Result<Server, CoreError> {
+        let template = self
+            .server_repository
+            .find_template_by_id(template_id)
+            .await?;
+        template.snapshot.validate()?;
+
+        let input = request.into_input(owner_id, &template.snapshot.settings);
+        if input.name.trim().is_empty() {
+            return Err(CoreError::InvalidServerName);
+        }
+
+        let mut server = self
+            .server_repository
+            .insert_from_template(input, template.snapshot)
+            .await?;
+
+        match self.server_pictures_repository.put_all(server.id).await {
+            Ok(server_urls) => {
+                server.banner_url = Some(server_urls.banner.to_string());
+                server.picture_url = Some(server_urls.picture.to_string());
+            }
+            Err(e) => tracing::error!("{}", e.to_string()),
+        }
+
+        Ok(server)
+    }
+
+    async fn export_server(&self, server_id: &ServerId) -> Result<ServerExport, CoreError> {
+        self.server_repository.export(server_id).await
+    }
+
+    async fn import_server(
+        &self,
+        owner_id: UserId,
+        mut bundle: ServerExport,
+    ) -> Result<Server, CoreError> {
+        if bundle.server.name.trim().is_empty() {
+            return Err(CoreError::InvalidServerName);
+        }
+        let snapshot = bundle.to_snapshot()?;
+
+        // The import is a fresh copy owned by the caller, the exported server may be gone
+        let source_server_id = bundle.server.id;
+        match self.server_repository.find_by_id(&source_server_id).await {
+            Ok(_) => {
+                let mut members = Vec::with_capacity(bundle.members.len());
+                for member in bundle.members {
+                    let banned = self
+                        .server_ban_repository
+                        .find_active(&source_server_id, &member.user_id)
+                        .await?
+                        .is_some();
+                    if !banned {
+                        members.push(member);
+                    }
+                }
+                bundle.members = members;
+            }
+            Err(CoreError::ServerNotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut server = self
+            .server_repository
+            .import(ImportServerInput {
+                owner_id,
+                bundle,
+                snapshot,
+            })
+            .await?;
+
+        match self.server_pictures_repository.put_all(server.id).await {
+            Ok(server_urls) => {
+                server.banner_url = Some(server_urls.banner.to_string());
+                server.picture_url = Some(server_urls.picture.to_string());
+            }
+            Err(e) => tracing::error!("{}", e.to_string()),
+        }
+
+        Ok(server)
+    }
+
+    async fn get_server_preview(&self, server_id: &ServerId) -> Result<ServerPreview, CoreError> {
+        let server = self.get_server(server_id).await?;
+        let (_, member_count) = self
+            .member_repository
+            .list_by_server(
+                server_id,
+                &GetPaginated { page: 1, limit: 1 },
+                &MemberFilter::default(),
+            )
+            .await?;
+
+        Ok(ServerPreview::new(server, member_count))
+    }
+
+    async fn preview_server(
+        &self,
+        server_id: &ServerId,
+        viewer_id: &UserId,
+        invitation_id: Option<ServerInvitationId>,
+    ) -> Result<ServerPreview, CoreError> {
+        let preview = self.get_server_preview(server_id).await?;
+        if preview.visibility == ServerVisibility::Public {
+            return Ok(preview);
+        }
+
+        if self
+            .member_repository
+            .find_by_server_and_user(server_id, viewer_id)
+            .await
+            .is_ok()
+        {
+            return Ok(preview);
+        }
+
+        let Some(invitation_id) = invitation_id else {
+            return Err(CoreError::Forbidden);
+        };
+        let invitation = self
+            .server_invitation_repository
+            .find_by_id(&invitation_id)
+            .await
+            .map_err(|_| CoreError::Forbidden)?;
+        if &invitation.server_id != server_id || !invitation.is_usable_by(viewer_id) {
+            return Err(CoreError::Forbidden);
+        }
+
+        Ok(preview)
+    }
+
+    async fn get_server_settings(&self, server_id: &ServerId) -> Result<ServerSettings, CoreError> {
+        let _server = self.server_repository.find_by_id(server_id).await?;
+        self.server_repository.find_settings(server_id).await
+    }
+
+    async fn update_server_settings(
+        &self,
+        server_id: &ServerId,
+        request: UpdateServerSettingsRequest,
+    ) -> Result<ServerSettings, CoreError> {
+        let _server = self.server_repository.find_by_id(server_id).await?;
+        let settings = request.into_settings(*server_id);
+
+        if let Some(channel_id) = settings.system_channel_id {
+            let channel = self.channel_repository.find_by_id(channel_id).await?;
+            if channel.server_id.as_ref() != Some(server_id) {
+                return Err(CoreError::InvalidServerSettings {
+                    msg: format!("channel {} does not belong to the server", channel_id),
+                });
+            }
+            if channel.channel_type != ChannelType::ServerText {
+                return Err(CoreError::InvalidServerSettings {
+                    msg: "system channel must be a text channel".to_string(),
+                });
+            }
+        }
+
+        for role_id in &settings.default_role_ids {
+            // The default role shares the server id and is already held by every member
+            if role_id.0 == server_id.0 {
+                return Err(CoreError::InvalidServerSettings {
+                    msg: "the default role is always applied".to_string(),
+                });
+            }
+            let role = self.role_repository.find_by_id(role_id).await?;
+            if &role.server_id != server_id {
+                return Err(CoreError::InvalidServerSettings {
+                    msg: format!("role {} does not belong to the server", role_id),
+                });
+            }
+        }
+
+        if settings.join_questions.len() > MAX_JOIN_QUESTIONS {
+            return Err(CoreError::InvalidServerSettings {
+                msg: format!("cannot ask more than {} join questions", MAX_JOIN_QUESTIONS),
+            });
+        }
+        if settings.join_questions.iter().any(|question| {
+            question.is_empty() || question.chars().count() > MAX_JOIN_QUESTION_SIZE
+        }) {
+            return Err(CoreError::InvalidServerSettings {
+                msg: format!(
+                    "join questions must be between 1 and {} characters",
+                    MAX_JOIN_QUESTION_SIZE
+                ),
+            });
+        }
+
+        self.server_repository.upsert_settings(settings).await
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// Replace the stored picture and banner of the servers with signed urls
+    async fn with_server_pictures(&self, mut servers: Vec<Server>) -> Vec<Server> {
+        let server_ids = servers.iter().map(|server| server.id).collect();
+        let server_urls_map = self
+            .server_pictures_repository
+            .get_all_for_servers(server_ids)
+            .await;
+
+        for server in servers.iter_mut() {
+            if let Some(server_urls) = server_urls_map.get(&server.id) {
+                server.banner_url = Some(server_urls.banner.to_string());
+                server.picture_url = Some(server_urls.picture.to_string());
+            }
+        }
+
+        servers
+    }
 }
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::{common::CoreError, friend::entities::UserId, server::entities::ServerId};
+
+pub const MAX_BAN_REASON_SIZE: usize = 512;
+
+/// A user banned from a server.
+/// A ban without expiry is permanent, an expired ban no longer prevents joining.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct ServerBan {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub moderator_id: UserId,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ServerBan {
+    pub fn is_active(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at > Utc::now())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for ServerBan {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            server_id: ServerId(row.get("server_id")),
+            user_id: UserId(row.get("user_id")),
+            moderator_id: UserId(row.get("moderator_id")),
+            reason: row.get("reason"),
+            expires_at: row.get("expires_at"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct BanMemberRequest {
+    pub reason: Option<String>,
+    /// Leave empty for a permanent ban
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl BanMemberRequest {
+    pub fn into_input(
+        self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+    ) -> Result<InsertServerBanInput, CoreError> {
+        let reason = self
+            .reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        if reason
+            .as_ref()
+            .is_some_and(|reason| reason.chars().count() > MAX_BAN_REASON_SIZE)
+        {
+            return Err(CoreError::InvalidServerBan {
+                msg: format!("reason cannot exceed {} characters", MAX_BAN_REASON_SIZE),
+            });
+        }
+        if self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+        {
+            return Err(CoreError::InvalidServerBan {
+                msg: "expiry must be in the future".to_string(),
+            });
+        }
+
+        Ok(InsertServerBanInput {
+            server_id,
+            user_id,
+            moderator_id,
+            reason,
+            expires_at: self.expires_at,
+        })
+    }
+}
+
+/// Request body for banning several users at once with the same reason and expiry
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct BulkBanRequest {
+    pub user_ids: Vec<UserId>,
+    #[serde(flatten)]
+    pub ban: BanMemberRequest,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InsertServerBanInput {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub moderator_id: UserId,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Event emitted when a user is banned from a server
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberBannedEvent {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub moderator_id: UserId,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<&ServerBan> for MemberBannedEvent {
+    fn from(ban: &ServerBan) -> Self {
+        Self {
+            server_id: ban.server_id,
+            user_id: ban.user_id,
+            moderator_id: ban.moderator_id,
+            reason: ban.reason.clone(),
+            expires_at: ban.expires_at,
+        }
+    }
+}
+
+/// Event emitted when a ban is lifted
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberUnbannedEvent {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub moderator_id: UserId,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn request(reason: Option<&str>, expires_at: Option<DateTime<Utc>>) -> BanMemberRequest {
+        BanMemberRequest {
+            reason: reason.map(str::to_string),
+            expires_at,
+        }
+    }
+
+    fn into_input(request: BanMemberRequest) -> Result<InsertServerBanInput, CoreError> {
+        request.into_input(
+            ServerId(Uuid::new_v4()),
+            UserId(Uuid::new_v4()),
+            UserId(Uuid::new_v4()),
+        )
+    }
+
+    #[test]
+    fn test_ban_request_trims_blank_reason() {
+        let input = into_input(request(Some("   "), None)).unwrap();
+        assert_eq!(input.reason, None);
+    }
+
+    #[test]
+    fn test_ban_request_rejects_long_reason_and_past_expiry() {
+        let long_reason = "a".repeat(MAX_BAN_REASON_SIZE + 1);
+        assert!(into_input(request(Some(&long_reason), None)).is_err());
+
+        let past = Utc::now() - Duration::hours(1);
+        assert!(into_input(request(None, Some(past))).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+
+use crate::domain::{
+    common::{CoreError, GetPaginated, TotalPaginatedElements},
+    friend::entities::UserId,
+    server::entities::ServerId,
+    server_ban::entities::{BanMemberRequest, BulkBanRequest, InsertServerBanInput, ServerBan},
+    server_member::entities::BulkMemberResult,
+};
+
+pub trait ServerBanRepository: Send + Sync {
+    /// Bans a user, replacing any previous ban, and removes their membership if any.
+    fn insert(
+        &self,
+        input: InsertServerBanInput,
+    ) -> impl Future<Output = Result<ServerBan, CoreError>> + Send;
+
+    /// Bans several users in one transaction, publishing each ban and removal
+    fn insert_many(
+        &self,
+        inputs: Vec<InsertServerBanInput>,
+    ) -> impl Future<Output = Result<Vec<ServerBan>, CoreError>> + Send;
+
+    /// Returns the ban of a user unless it has expired
+    fn find_active(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<Option<ServerBan>, CoreError>> + Send;
+
+    fn delete(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        moderator_id: &UserId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Lists the active bans of a server, most recent first
+    fn list_by_server(
+        &self,
+        server_id: &ServerId,
+        pagination: &GetPaginated,
+    ) -> impl Future<Output = Result<(Vec<ServerBan>, TotalPaginatedElements), CoreError>> + Send;
+}
+
+pub trait ServerBanService: Send + Sync {
+    /// Bans a user from a server and removes their membership.
+    ///
+    /// The owner of the server cannot be banned, and moderators cannot ban themselves.
+    fn ban_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+        request: BanMemberRequest,
+    ) -> impl Future<Output = Result<ServerBan, CoreError>> + Send;
+
+    /// Bans several users with the same reason and expiry in one transaction.
+    ///
+    /// The owner and the moderator themselves are reported as failed items.
+    fn bulk_ban_members(
+        &self,
+        server_id: ServerId,
+        moderator_id: UserId,
+        request: BulkBanRequest,
+    ) -> impl Future<Output = Result<Vec<BulkMemberResult>, CoreError>> + Send;
+
+    fn unban_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn get_ban(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<ServerBan, CoreError>> + Send;
+
+    fn list_bans(
+        &self,
+        server_id: ServerId,
+        pagination: &GetPaginated,
+    ) -> impl Future<Output = Result<(Vec<ServerBan>, TotalPaginatedElements), CoreError>> + Send;
+}
+
+#[derive(Clone)]
+pub struct MockServerBanRepository {
+    bans: Arc<Mutex<Vec<ServerBan>>>,
+}
+
+impl MockServerBanRepository {
+    pub fn new() -> Self {
+        Self {
+            bans: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl ServerBanRepository for MockServerBanRepository {
+    async fn insert(&self, input: InsertServerBanInput) -> Result<ServerBan, CoreError> {
+        let mut bans = self.bans.lock().unwrap();
+
+        let ban = ServerBan {
+            server_id: input.server_id,
+            user_id: input.user_id,
+            moderator_id: input.moderator_id,
+            reason: input.reason,
+            expires_at: input.expires_at,
+            created_at: Utc::now(),
+        };
+        bans.retain(|b| !(b.server_id == ban.server_id && b.user_id == ban.user_id));
+        bans.push(ban.clone());
+
+        Ok(ban)
+    }
+
+    async fn insert_many(
+        &self,
+        inputs: Vec<InsertServerBanInput>,
+    ) -> Result<Vec<ServerBan>, CoreError> {
+        let mut bans = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            bans.push(self.insert(input).await?);
+        }
+
+        Ok(bans)
+    }
+
+    async fn find_active(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+    ) -> Result<Option<ServerBan>, CoreError> {
+        let bans = self.bans.lock().unwrap();
+
+        Ok(bans
+            .iter()
+            .find(|b| &b.server_id == server_id && &b.user_id == user_id && b.is_active())
+            .cloned())
+    }
+
+    async fn delete(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        _moderator_id: &UserId,
+    ) -> Result<(), CoreError> {
+        let mut bans = self.bans.lock().unwrap();
+
+        let index = bans
+            .iter()
+            .position(|b| &b.server_id == server_id && &b.user_id == user_id)
+            .ok_or(CoreError::ServerBanNotFound {
+                server_id: *server_id,
+                user_id: *user_id,
+            })?;
+        bans.remove(index);
+
+        Ok(())
+    }
+
+    async fn list_by_server(
+        &self,
+        server_id: &ServerId,
+        pagination: &GetPaginated,
+    ) -> Result<(Vec<ServerBan>, TotalPaginatedElements), CoreError> {
+        let bans = self.bans.lock().unwrap();
+
+        let mut active: Vec<ServerBan> = bans
+            .iter()
+            .filter(|b| &b.server_id == server_id && b.is_active())
+            .cloned()
+            .collect();
+        active.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        let total = active.len() as u64;
+        let offset = (pagination.page - 1) * pagination.limit;
+        let page = active
+            .into_iter()
+            .skip(offset as usize)
+            .take(pagination.limit as usize)
+            .collect();
+
+        Ok((page, total))
+    }
+}
@@ -0,0 +1,142 @@
+use crate::domain::{
+    authorization::ports::AuthorizationRepository,
+    channel::ports::ChannelRepository,
+    channel_member::ports::ChannelMemberRepository,
+    common::{CoreError, GetPaginated, TotalPaginatedElements, services::Service},
+    friend::{entities::UserId, ports::FriendshipRepository},
+    health::port::HealthRepository,
+    member_role::ports::MemberRoleRepository,
+    outbox::ports::OutboxRepository,
+    role::ports::RoleRepository,
+    server::{entities::ServerId, ports::ServerRepository},
+    server_ban::{
+        entities::{BanMemberRequest, BulkBanRequest, ServerBan},
+        ports::{ServerBanRepository, ServerBanService},
+    },
+    server_invitation::ports::ServerInvitationRepository,
+    server_join_request::ports::ServerJoinRequestRepository,
+    server_member::{
+        MemberRepository,
+        entities::{BulkMemberResult, validate_bulk_user_ids},
+    },
+    server_pictures::ServerPicturesRepository,
+    user::port::UserRepository,
+};
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> ServerBanService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    async fn ban_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+        request: BanMemberRequest,
+    ) -> Result<ServerBan, CoreError> {
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        if server.owner_id == user_id {
+            return Err(CoreError::Forbidden);
+        }
+        if moderator_id == user_id {
+            return Err(CoreError::InvalidServerBan {
+                msg: "cannot ban yourself".to_string(),
+            });
+        }
+        self.role_authority(server_id, moderator_id)
+            .await?
+            .check_position(self.member_rank(server_id, user_id).await?)?;
+
+        let input = request.into_input(server_id, user_id, moderator_id)?;
+        self.server_ban_repository.insert(input).await
+    }
+
+    async fn bulk_ban_members(
+        &self,
+        server_id: ServerId,
+        moderator_id: UserId,
+        request: BulkBanRequest,
+    ) -> Result<Vec<BulkMemberResult>, CoreError> {
+        let user_ids = validate_bulk_user_ids(&request.user_ids)?;
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        let authority = self.role_authority(server_id, moderator_id).await?;
+
+        // Users that cannot be banned are reported without failing the others
+        let mut results = Vec::with_capacity(user_ids.len());
+        let mut inputs = Vec::new();
+        for user_id in user_ids {
+            if user_id == server.owner_id {
+                results.push(BulkMemberResult::failure(user_id, &CoreError::Forbidden));
+            } else if user_id == moderator_id {
+                results.push(BulkMemberResult::failure(
+                    user_id,
+                    &CoreError::InvalidServerBan {
+                        msg: "cannot ban yourself".to_string(),
+                    },
+                ));
+            } else if let Err(e) =
+                authority.check_position(self.member_rank(server_id, user_id).await?)
+            {
+                results.push(BulkMemberResult::failure(user_id, &e));
+            } else {
+                inputs.push(
+                    request
+                        .ban
+                        .clone()
+                        .into_input(server_id, user_id, moderator_id)?,
+                );
+                results.push(BulkMemberResult::success(user_id));
+            }
+        }
+
+        if !inputs.is_empty() {
+            self.server_ban_repository.insert_many(inputs).await?;
+        }
+
+        Ok(results)
+    }
+
+    async fn unban_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+    ) -> Result<(), CoreError> {
+        self.server_ban_repository
+            .delete(&server_id, &user_id, &moderator_id)
+            .await
+    }
+
+    async fn get_ban(&self, server_id: ServerId, user_id: UserId) -> Result<ServerBan, CoreError> {
+        self.server_ban_repository
+            .find_active(&server_id, &user_id)
+            .await?
+            .ok_or(CoreError::ServerBanNotFound { server_id, user_id })
+    }
+
+    async fn list_bans(
+        &self,
+        server_id: ServerId,
+        pagination: &GetPaginated,
+    ) -> Result<(Vec<ServerBan>, TotalPaginatedElements), CoreError> {
+        let _server = self.server_repository.find_by_id(&server_id).await?;
+        self.server_ban_repository
+            .list_by_server(&server_id, pagination)
+            .await
+    }
+}
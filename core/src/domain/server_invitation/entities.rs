@@ -5,7 +5,11 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::{friend::entities::UserId, server::entities::ServerId};
+use crate::domain::{
+    common::CoreError,
+    friend::entities::UserId,
+    server::entities::{ServerId, ServerPreview},
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct ServerInvitationId(pub Uuid);
@@ -65,6 +69,15 @@ impl ServerInvitation {
             false // No expiration date means it never expires
         }
     }
+
+    /// Whether the given user can still use this invitation to join its server
+    pub fn is_usable_by(&self, user_id: &UserId) -> bool {
+        self.status == ServerInvitationStatus::Pending
+            && !self.is_expired()
+            && self
+                .invitee_id
+                .is_none_or(|invitee_id| &invitee_id == user_id)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
@@ -107,3 +120,159 @@ pub struct AcceptInvitationInput {
     pub user_id: UserId,
     pub invitation_id: ServerInvitationId,
 }
+
+pub const MIN_VANITY_SLUG_SIZE: usize = 3;
+
+pub const MAX_VANITY_SLUG_SIZE: usize = 32;
+
+/// Slugs that would collide with routes or impersonate the platform
+pub const RESERVED_VANITY_SLUGS: &[&str] = &[
+    "admin",
+    "administrator",
+    "api",
+    "app",
+    "beep",
+    "discover",
+    "everyone",
+    "help",
+    "here",
+    "invitation",
+    "invitations",
+    "invite",
+    "invites",
+    "login",
+    "logout",
+    "me",
+    "moderator",
+    "official",
+    "search",
+    "server",
+    "servers",
+    "settings",
+    "staff",
+    "support",
+    "system",
+    "templates",
+    "vanity",
+];
+
+/// A validated vanity slug.
+/// Slugs are normalized to lowercase, so uniqueness is case-insensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, ToSchema)]
+pub struct VanitySlug(String);
+
+impl VanitySlug {
+    pub fn parse(value: &str) -> Result<Self, CoreError> {
+        let slug = value.trim().to_lowercase();
+
+        if slug.len() < MIN_VANITY_SLUG_SIZE || slug.len() > MAX_VANITY_SLUG_SIZE {
+            return Err(CoreError::InvalidVanitySlug {
+                msg: format!(
+                    "slug must be between {} and {} characters",
+                    MIN_VANITY_SLUG_SIZE, MAX_VANITY_SLUG_SIZE
+                ),
+            });
+        }
+        if !slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(CoreError::InvalidVanitySlug {
+                msg: "slug can only contain letters, digits and hyphens".to_string(),
+            });
+        }
+        if slug.starts_with('-') || slug.ends_with('-') || slug.contains("--") {
+            return Err(CoreError::InvalidVanitySlug {
+                msg: "hyphens must separate letters or digits".to_string(),
+            });
+        }
+        if RESERVED_VANITY_SLUGS.contains(&slug.as_str()) {
+            return Err(CoreError::InvalidVanitySlug {
+                msg: format!("slug {} is reserved", slug),
+            });
+        }
+
+        Ok(Self(slug))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for VanitySlug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The vanity URL of a server.
+/// It is backed by a general, non-expiring invitation, so joining through a slug
+/// goes through the regular invitation acceptance.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ServerVanityUrl {
+    pub slug: String,
+    pub server_id: ServerId,
+    pub invitation_id: ServerInvitationId,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for ServerVanityUrl {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            slug: row.get("vanity_slug"),
+            server_id: ServerId(row.get("server_id")),
+            invitation_id: ServerInvitationId(row.get("id")),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct SetVanitySlugRequest {
+    pub slug: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct VanityLookup {
+    pub slug: String,
+    pub invitation_id: ServerInvitationId,
+    pub server: ServerPreview,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vanity_slug_is_normalized() {
+        let slug = VanitySlug::parse("  My-Server42 ").unwrap();
+        assert_eq!(slug.as_str(), "my-server42");
+    }
+
+    #[test]
+    fn test_vanity_slug_rejects_invalid_values() {
+        for value in [
+            "ab",
+            "has space",
+            "emoji🦀",
+            "-edge",
+            "edge-",
+            "dou--ble",
+            "Admin",
+        ] {
+            assert!(
+                matches!(
+                    VanitySlug::parse(value),
+                    Err(CoreError::InvalidVanitySlug { .. })
+                ),
+                "expected {value} to be rejected"
+            );
+        }
+        assert!(VanitySlug::parse(&"a".repeat(MAX_VANITY_SLUG_SIZE + 1)).is_err());
+    }
+}
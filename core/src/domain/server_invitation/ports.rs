@@ -1,10 +1,16 @@
 use std::sync::{Arc, Mutex};
 
+use chrono::Utc;
+use uuid::Uuid;
+
 use crate::domain::{
     common::CoreError,
+    friend::entities::UserId,
+    server::entities::{ServerId, ServerPreview},
     server_invitation::entities::{
         AcceptInvitationInput, InsertServerInvitationInput, ServerInvitation, ServerInvitationId,
-        UpdateServerInvitationInput,
+        ServerInvitationStatus, ServerVanityUrl, SetVanitySlugRequest, UpdateServerInvitationInput,
+        VanityLookup, VanitySlug,
     },
 };
 
@@ -26,6 +32,31 @@ pub trait ServerInvitationRepository: Send + Sync {
 
     fn delete(&self, id: &ServerInvitationId)
     -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Claims a vanity slug for a server, replacing its current one if any.
+    /// Fails with `CoreError::VanitySlugAlreadyTaken` when another server owns the slug.
+    fn upsert_vanity(
+        &self,
+        server_id: &ServerId,
+        inviter_id: &UserId,
+        slug: &VanitySlug,
+    ) -> impl Future<Output = Result<ServerVanityUrl, CoreError>> + Send;
+
+    fn find_vanity_by_slug(
+        &self,
+        slug: &VanitySlug,
+    ) -> impl Future<Output = Result<ServerVanityUrl, CoreError>> + Send;
+
+    fn find_vanity_by_server(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<Option<ServerVanityUrl>, CoreError>> + Send;
+
+    /// Releases the vanity slug of a server along with its backing invitation.
+    fn delete_vanity(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
 }
 
 pub trait ServerInvitationService: Send + Sync {
@@ -43,17 +74,58 @@ pub trait ServerInvitationService: Send + Sync {
         &self,
         accept_input: &AcceptInvitationInput,
     ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Previews the server an invitation points to, as long as the user can still accept it.
+    fn get_invitation_preview(
+        &self,
+        invitation_id: &ServerInvitationId,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<ServerPreview, CoreError>> + Send;
+
+    /// Validates and claims a vanity slug for a public server.
+    fn set_vanity_slug(
+        &self,
+        server_id: &ServerId,
+        inviter_id: &UserId,
+        request: SetVanitySlugRequest,
+    ) -> impl Future<Output = Result<ServerVanityUrl, CoreError>> + Send;
+
+    fn get_server_vanity(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<ServerVanityUrl, CoreError>> + Send;
+
+    fn remove_vanity_slug(
+        &self,
+        server_id: &ServerId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Resolves a vanity slug to the preview of its server.
+    /// Slugs of servers that are no longer public do not resolve.
+    fn resolve_vanity_slug(
+        &self,
+        slug: &str,
+    ) -> impl Future<Output = Result<VanityLookup, CoreError>> + Send;
+
+    /// Joins the server behind a vanity slug through the invitation flow.
+    fn join_by_vanity_slug(
+        &self,
+        slug: &str,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<VanityLookup, CoreError>> + Send;
 }
 
 #[derive(Clone)]
 pub struct MockServerInvitationRepository {
     invitations: Arc<Mutex<Vec<ServerInvitation>>>,
+    vanity_urls: Arc<Mutex<Vec<ServerVanityUrl>>>,
 }
 
 impl MockServerInvitationRepository {
     pub fn new() -> Self {
         Self {
             invitations: Arc::new(Mutex::new(Vec::new())),
+            vanity_urls: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -61,23 +133,160 @@ impl MockServerInvitationRepository {
 impl ServerInvitationRepository for MockServerInvitationRepository {
     async fn insert(
         &self,
-        _input: InsertServerInvitationInput,
+        input: InsertServerInvitationInput,
     ) -> Result<ServerInvitation, CoreError> {
-        todo!()
+        let mut invitations = self.invitations.lock().unwrap();
+
+        let invitation = ServerInvitation {
+            id: ServerInvitationId(Uuid::new_v4()),
+            server_id: input.server_id,
+            inviter_id: input.inviter_id,
+            invitee_id: input.invitee_id,
+            status: ServerInvitationStatus::Pending,
+            created_at: Utc::now(),
+            updated_at: None,
+            expires_at: input.expires_at,
+        };
+
+        invitations.push(invitation.clone());
+        Ok(invitation)
     }
 
-    async fn find_by_id(&self, _id: &ServerInvitationId) -> Result<ServerInvitation, CoreError> {
-        todo!()
+    async fn find_by_id(&self, id: &ServerInvitationId) -> Result<ServerInvitation, CoreError> {
+        let invitations = self.invitations.lock().unwrap();
+
+        invitations
+            .iter()
+            .find(|i| &i.id == id)
+            .cloned()
+            .ok_or_else(|| CoreError::DatabaseError {
+                msg: format!("Server invitation not found with id: {}", id),
+            })
     }
 
     async fn update(
         &self,
-        _input: UpdateServerInvitationInput,
+        input: UpdateServerInvitationInput,
     ) -> Result<ServerInvitation, CoreError> {
-        todo!()
+        let mut invitations = self.invitations.lock().unwrap();
+
+        let invitation = invitations
+            .iter_mut()
+            .find(|i| i.id == input.id)
+            .ok_or_else(|| CoreError::DatabaseError {
+                msg: format!("Server invitation not found with id: {}", input.id),
+            })?;
+        invitation.status = input.status;
+        invitation.updated_at = Some(Utc::now());
+
+        Ok(invitation.clone())
+    }
+
+    async fn delete(&self, id: &ServerInvitationId) -> Result<(), CoreError> {
+        {
+            let mut invitations = self.invitations.lock().unwrap();
+
+            let index = invitations
+                .iter()
+                .position(|i| &i.id == id)
+                .ok_or_else(|| CoreError::DatabaseError {
+                    msg: format!("Server invitation not found with id: {}", id),
+                })?;
+            invitations.remove(index);
+        }
+        self.vanity_urls
+            .lock()
+            .unwrap()
+            .retain(|v| &v.invitation_id != id);
+
+        Ok(())
     }
 
-    async fn delete(&self, _id: &ServerInvitationId) -> Result<(), CoreError> {
-        todo!()
+    async fn upsert_vanity(
+        &self,
+        server_id: &ServerId,
+        inviter_id: &UserId,
+        slug: &VanitySlug,
+    ) -> Result<ServerVanityUrl, CoreError> {
+        let mut vanity_urls = self.vanity_urls.lock().unwrap();
+
+        if vanity_urls
+            .iter()
+            .any(|v| v.slug == slug.as_str() && &v.server_id != server_id)
+        {
+            return Err(CoreError::VanitySlugAlreadyTaken {
+                slug: slug.to_string(),
+            });
+        }
+
+        if let Some(vanity) = vanity_urls.iter_mut().find(|v| &v.server_id == server_id) {
+            vanity.slug = slug.to_string();
+            vanity.updated_at = Some(Utc::now());
+            return Ok(vanity.clone());
+        }
+
+        let invitation = ServerInvitation {
+            id: ServerInvitationId(Uuid::new_v4()),
+            server_id: *server_id,
+            inviter_id: *inviter_id,
+            invitee_id: None,
+            status: ServerInvitationStatus::Pending,
+            created_at: Utc::now(),
+            updated_at: None,
+            expires_at: None,
+        };
+        let vanity = ServerVanityUrl {
+            slug: slug.to_string(),
+            server_id: *server_id,
+            invitation_id: invitation.id,
+            created_at: invitation.created_at,
+            updated_at: None,
+        };
+
+        self.invitations.lock().unwrap().push(invitation);
+        vanity_urls.push(vanity.clone());
+        Ok(vanity)
+    }
+
+    async fn find_vanity_by_slug(&self, slug: &VanitySlug) -> Result<ServerVanityUrl, CoreError> {
+        let vanity_urls = self.vanity_urls.lock().unwrap();
+
+        vanity_urls
+            .iter()
+            .find(|v| v.slug == slug.as_str())
+            .cloned()
+            .ok_or_else(|| CoreError::VanitySlugNotFound {
+                slug: slug.to_string(),
+            })
+    }
+
+    async fn find_vanity_by_server(
+        &self,
+        server_id: &ServerId,
+    ) -> Result<Option<ServerVanityUrl>, CoreError> {
+        let vanity_urls = self.vanity_urls.lock().unwrap();
+
+        Ok(vanity_urls
+            .iter()
+            .find(|v| &v.server_id == server_id)
+            .cloned())
+    }
+
+    async fn delete_vanity(&self, server_id: &ServerId) -> Result<(), CoreError> {
+        let mut vanity_urls = self.vanity_urls.lock().unwrap();
+
+        let index = vanity_urls
+            .iter()
+            .position(|v| &v.server_id == server_id)
+            .ok_or(CoreError::ServerVanityNotFound {
+                server_id: *server_id,
+            })?;
+        let vanity = vanity_urls.remove(index);
+        self.invitations
+            .lock()
+            .unwrap()
+            .retain(|i| i.id != vanity.invitation_id);
+
+        Ok(())
     }
 }
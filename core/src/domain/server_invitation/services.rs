@@ -3,13 +3,20 @@ use crate::domain::channel::ports::ChannelRepository;
 use crate::domain::channel_member::ports::ChannelMemberRepository;
 use crate::domain::common::CoreError;
 use crate::domain::common::services::Service;
+use crate::domain::friend::entities::UserId;
 use crate::domain::friend::ports::FriendshipRepository;
 use crate::domain::health::port::HealthRepository;
 use crate::domain::member_role::ports::MemberRoleRepository;
 use crate::domain::outbox::ports::OutboxRepository;
 use crate::domain::role::ports::RoleRepository;
-use crate::domain::server::ports::ServerRepository;
-use crate::domain::server_invitation::entities::{AcceptInvitationInput, ServerInvitationStatus};
+use crate::domain::server::entities::{ServerId, ServerJoinMode, ServerPreview, ServerVisibility};
+use crate::domain::server::ports::{ServerRepository, ServerService};
+use crate::domain::server_ban::ports::ServerBanRepository;
+use crate::domain::server_invitation::entities::{
+    AcceptInvitationInput, ServerInvitationStatus, ServerVanityUrl, SetVanitySlugRequest,
+    VanityLookup, VanitySlug,
+};
+use crate::domain::server_join_request::ports::ServerJoinRequestRepository;
 use crate::domain::server_member::CreateMemberInput;
 use crate::domain::server_member::ports::MemberRepository;
 use crate::domain::server_pictures::ServerPicturesRepository;
@@ -18,8 +25,8 @@ use crate::domain::user::port::UserRepository;
 use super::entities::{InsertServerInvitationInput, ServerInvitation, ServerInvitationId};
 use super::ports::{ServerInvitationRepository, ServerInvitationService};
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> ServerInvitationService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> ServerInvitationService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -34,6 +41,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn create_invitation(
         &self,
@@ -74,6 +83,12 @@ where
             return Err(CoreError::Forbidden);
         }
 
+        // Invitations are valid whatever the join mode, only the default roles apply
+        let settings = self
+            .server_repository
+            .find_settings(&invitation.server_id)
+            .await?;
+
         // Check invitation type and validate user
         match invitation.invitee_id {
             // Personal invitation - only specific user can accept
@@ -86,13 +101,15 @@ where
                     .is_err()
                 {
                     // User not a member yet, add them
-                    self.member_repository
-                        .insert(CreateMemberInput {
+                    self.join_server(
+                        CreateMemberInput {
                             server_id: invitation.server_id,
                             user_id: invitee_id,
                             nickname: None,
-                        })
-                        .await?;
+                        },
+                        &settings,
+                    )
+                    .await?;
                 }
 
                 // Always delete the personal invitation after acceptance
@@ -110,13 +127,15 @@ where
                     .is_err()
                 {
                     // User not a member yet, add them
-                    self.member_repository
-                        .insert(CreateMemberInput {
+                    self.join_server(
+                        CreateMemberInput {
                             server_id: invitation.server_id,
                             user_id: accept_input.user_id,
                             nickname: None,
-                        })
-                        .await?;
+                        },
+                        &settings,
+                    )
+                    .await?;
                 }
 
                 // General invitations are NOT deleted, they can be reused
@@ -129,4 +148,101 @@ where
 
         Ok(())
     }
+
+    async fn get_invitation_preview(
+        &self,
+        invitation_id: &ServerInvitationId,
+        user_id: &UserId,
+    ) -> Result<ServerPreview, CoreError> {
+        let invitation = self
+            .server_invitation_repository
+            .find_by_id(invitation_id)
+            .await?;
+
+        self.preview_server(&invitation.server_id, user_id, Some(invitation.id))
+            .await
+    }
+
+    async fn set_vanity_slug(
+        &self,
+        server_id: &ServerId,
+        inviter_id: &UserId,
+        request: SetVanitySlugRequest,
+    ) -> Result<ServerVanityUrl, CoreError> {
+        let slug = VanitySlug::parse(&request.slug)?;
+
+        // Vanity slugs let anyone join, so only public servers can claim one
+        let server = self.server_repository.find_by_id(server_id).await?;
+        if server.visibility != ServerVisibility::Public {
+            return Err(CoreError::Forbidden);
+        }
+
+        self.server_invitation_repository
+            .upsert_vanity(server_id, inviter_id, &slug)
+            .await
+    }
+
+    async fn get_server_vanity(&self, server_id: &ServerId) -> Result<ServerVanityUrl, CoreError> {
+        self.server_invitation_repository
+            .find_vanity_by_server(server_id)
+            .await?
+            .ok_or(CoreError::ServerVanityNotFound {
+                server_id: *server_id,
+            })
+    }
+
+    async fn remove_vanity_slug(&self, server_id: &ServerId) -> Result<(), CoreError> {
+        self.server_invitation_repository
+            .delete_vanity(server_id)
+            .await
+    }
+
+    async fn resolve_vanity_slug(&self, slug: &str) -> Result<VanityLookup, CoreError> {
+        // A malformed slug can never have been claimed
+        let slug = VanitySlug::parse(slug).map_err(|_| CoreError::VanitySlugNotFound {
+            slug: slug.to_string(),
+        })?;
+        let vanity = self
+            .server_invitation_repository
+            .find_vanity_by_slug(&slug)
+            .await?;
+
+        let server = self.get_server_preview(&vanity.server_id).await?;
+        if server.visibility != ServerVisibility::Public {
+            return Err(CoreError::VanitySlugNotFound {
+                slug: slug.to_string(),
+            });
+        }
+
+        Ok(VanityLookup {
+            slug: vanity.slug,
+            invitation_id: vanity.invitation_id,
+            server,
+        })
+    }
+
+    async fn join_by_vanity_slug(
+        &self,
+        slug: &str,
+        user_id: UserId,
+    ) -> Result<VanityLookup, CoreError> {
+        let lookup = self.resolve_vanity_slug(slug).await?;
+
+        // A vanity slug is an open door, which request-to-join servers do not have
+        let settings = self
+            .server_repository
+            .find_settings(&lookup.server.id)
+            .await?;
+        if settings.join_mode == ServerJoinMode::RequestToJoin {
+            return Err(CoreError::Forbidden);
+        }
+
+        self.accept_invitation(&AcceptInvitationInput {
+            user_id,
+            invitation_id: lookup.invitation_id,
+        })
+        .await?;
+
+        Ok(lookup)
+    }
 }
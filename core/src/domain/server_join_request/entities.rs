@@ -0,0 +1,204 @@
+use std::ops::Deref;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::domain::{common::CoreError, friend::entities::UserId, server::entities::ServerId};
+
+pub const MAX_JOIN_ANSWER_SIZE: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct ServerJoinRequestId(pub Uuid);
+
+impl std::fmt::Display for ServerJoinRequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Deref for ServerJoinRequestId {
+    type Target = Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Uuid> for ServerJoinRequestId {
+    fn from(uuid: Uuid) -> Self {
+        ServerJoinRequestId(uuid)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "server_join_request_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+/// An answer to one of the questions of a server, kept with the question it answers
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct JoinRequestAnswer {
+    pub question: String,
+    pub answer: String,
+}
+
+/// A request of a user to become a member of a request-to-join server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct ServerJoinRequest {
+    pub id: ServerJoinRequestId,
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub status: JoinRequestStatus,
+    pub answers: Vec<JoinRequestAnswer>,
+    /// The moderator who approved or denied the request
+    pub reviewer_id: Option<UserId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<&sqlx::postgres::PgRow> for ServerJoinRequest {
+    fn from(row: &sqlx::postgres::PgRow) -> Self {
+        use sqlx::Row;
+        Self {
+            id: ServerJoinRequestId(row.get("id")),
+            server_id: ServerId(row.get("server_id")),
+            user_id: UserId(row.get("user_id")),
+            status: row.get("status"),
+            answers: row
+                .get::<sqlx::types::Json<Vec<JoinRequestAnswer>>, _>("answers")
+                .0,
+            reviewer_id: row.get::<Option<Uuid>, _>("reviewer_id").map(UserId),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+/// Request body for applying to a server.
+/// Answers follow the order of the server's questions, and every answer is optional.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct SubmitJoinRequestRequest {
+    #[serde(default)]
+    pub answers: Vec<String>,
+}
+
+impl SubmitJoinRequestRequest {
+    /// Pairs each non empty answer with the question it answers
+    pub fn into_input(
+        self,
+        server_id: ServerId,
+        user_id: UserId,
+        questions: &[String],
+    ) -> Result<InsertJoinRequestInput, CoreError> {
+        if self.answers.len() > questions.len() {
+            return Err(CoreError::InvalidJoinRequest {
+                msg: format!("the server only asks {} questions", questions.len()),
+            });
+        }
+
+        let mut answers = Vec::new();
+        for (question, answer) in questions.iter().zip(self.answers) {
+            let answer = answer.trim().to_string();
+            if answer.is_empty() {
+                continue;
+            }
+            if answer.chars().count() > MAX_JOIN_ANSWER_SIZE {
+                return Err(CoreError::InvalidJoinRequest {
+                    msg: format!("answers cannot exceed {} characters", MAX_JOIN_ANSWER_SIZE),
+                });
+            }
+            answers.push(JoinRequestAnswer {
+                question: question.clone(),
+                answer,
+            });
+        }
+
+        Ok(InsertJoinRequestInput {
+            server_id,
+            user_id,
+            answers,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InsertJoinRequestInput {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub answers: Vec<JoinRequestAnswer>,
+}
+
+/// Filters the join requests of a server by status, pending ones by default
+#[derive(Debug, Deserialize, Clone, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListJoinRequestsQuery {
+    #[serde(default = "default_listed_status")]
+    pub status: JoinRequestStatus,
+}
+
+fn default_listed_status() -> JoinRequestStatus {
+    JoinRequestStatus::Pending
+}
+
+impl Default for ListJoinRequestsQuery {
+    fn default() -> Self {
+        Self {
+            status: default_listed_status(),
+        }
+    }
+}
+
+/// The questions of a request-to-join server, shown to applicants
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct JoinQuestions {
+    pub server_id: ServerId,
+    pub questions: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_are_paired_with_questions() {
+        let questions = vec!["Why?".to_string(), "How?".to_string()];
+        let request = SubmitJoinRequestRequest {
+            answers: vec!["  ".to_string(), " Because ".to_string()],
+        };
+
+        let input = request
+            .into_input(ServerId(Uuid::new_v4()), UserId(Uuid::new_v4()), &questions)
+            .unwrap();
+
+        assert_eq!(
+            input.answers,
+            vec![JoinRequestAnswer {
+                question: "How?".to_string(),
+                answer: "Because".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_answers_must_match_questions() {
+        let questions = vec!["Why?".to_string()];
+        for answers in [
+            vec!["One".to_string(), "Two".to_string()],
+            vec!["a".repeat(MAX_JOIN_ANSWER_SIZE + 1)],
+        ] {
+            let result = SubmitJoinRequestRequest { answers }.into_input(
+                ServerId(Uuid::new_v4()),
+                UserId(Uuid::new_v4()),
+                &questions,
+            );
+            assert!(matches!(result, Err(CoreError::InvalidJoinRequest { .. })));
+        }
+    }
+}
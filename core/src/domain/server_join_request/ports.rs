@@ -0,0 +1,212 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::{
+    common::{CoreError, GetPaginated, TotalPaginatedElements},
+    friend::entities::UserId,
+    server::entities::ServerId,
+    server_join_request::entities::{
+        InsertJoinRequestInput, JoinQuestions, JoinRequestStatus, ListJoinRequestsQuery,
+        ServerJoinRequest, ServerJoinRequestId, SubmitJoinRequestRequest,
+    },
+};
+
+pub trait ServerJoinRequestRepository: Send + Sync {
+    /// Fails with `CoreError::JoinRequestAlreadyPending` when the user already waits for a review
+    fn insert(
+        &self,
+        input: InsertJoinRequestInput,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        id: &ServerJoinRequestId,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+
+    /// Returns the most recent request of a user for a server
+    fn find_latest(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+    ) -> impl Future<Output = Result<Option<ServerJoinRequest>, CoreError>> + Send;
+
+    /// Lists the requests of a server with the given status, oldest first
+    fn list_by_server(
+        &self,
+        server_id: &ServerId,
+        status: JoinRequestStatus,
+        pagination: &GetPaginated,
+    ) -> impl Future<Output = Result<(Vec<ServerJoinRequest>, TotalPaginatedElements), CoreError>> + Send;
+
+    /// Records the decision on a pending request.
+    /// Fails with `CoreError::JoinRequestAlreadyReviewed` when it is no longer pending.
+    fn review(
+        &self,
+        id: &ServerJoinRequestId,
+        status: JoinRequestStatus,
+        reviewer_id: &UserId,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+}
+
+pub trait ServerJoinRequestService: Send + Sync {
+    /// Lists the questions applicants of a request-to-join server can answer
+    fn get_join_questions(
+        &self,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<JoinQuestions, CoreError>> + Send;
+
+    /// Applies to a request-to-join server.
+    ///
+    /// Members, banned users and users with a pending request cannot apply.
+    fn submit_join_request(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        request: SubmitJoinRequestRequest,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+
+    /// Returns the most recent request of the user, so they can follow its status
+    fn get_own_join_request(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+
+    fn list_join_requests(
+        &self,
+        server_id: ServerId,
+        query: ListJoinRequestsQuery,
+        pagination: &GetPaginated,
+    ) -> impl Future<Output = Result<(Vec<ServerJoinRequest>, TotalPaginatedElements), CoreError>> + Send;
+
+    /// Approves a pending request and makes the applicant a member through the regular join path
+    fn approve_join_request(
+        &self,
+        server_id: ServerId,
+        request_id: ServerJoinRequestId,
+        reviewer_id: UserId,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+
+    fn deny_join_request(
+        &self,
+        server_id: ServerId,
+        request_id: ServerJoinRequestId,
+        reviewer_id: UserId,
+    ) -> impl Future<Output = Result<ServerJoinRequest, CoreError>> + Send;
+}
+
+#[derive(Clone)]
+pub struct MockServerJoinRequestRepository {
+    requests: Arc<Mutex<Vec<ServerJoinRequest>>>,
+}
+
+impl MockServerJoinRequestRepository {
+    pub fn new() -> Self {
+        Self {
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl ServerJoinRequestRepository for MockServerJoinRequestRepository {
+    async fn insert(&self, input: InsertJoinRequestInput) -> Result<ServerJoinRequest, CoreError> {
+        let mut requests = self.requests.lock().unwrap();
+
+        if requests.iter().any(|r| {
+            r.server_id == input.server_id
+                && r.user_id == input.user_id
+                && r.status == JoinRequestStatus::Pending
+        }) {
+            return Err(CoreError::JoinRequestAlreadyPending {
+                server_id: input.server_id,
+                user_id: input.user_id,
+            });
+        }
+
+        let request = ServerJoinRequest {
+            id: ServerJoinRequestId(Uuid::new_v4()),
+            server_id: input.server_id,
+            user_id: input.user_id,
+            status: JoinRequestStatus::Pending,
+            answers: input.answers,
+            reviewer_id: None,
+            created_at: Utc::now(),
+            updated_at: None,
+        };
+
+        requests.push(request.clone());
+        Ok(request)
+    }
+
+    async fn find_by_id(&self, id: &ServerJoinRequestId) -> Result<ServerJoinRequest, CoreError> {
+        let requests = self.requests.lock().unwrap();
+
+        requests
+            .iter()
+            .find(|r| &r.id == id)
+            .cloned()
+            .ok_or(CoreError::JoinRequestNotFound)
+    }
+
+    async fn find_latest(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+    ) -> Result<Option<ServerJoinRequest>, CoreError> {
+        let requests = self.requests.lock().unwrap();
+
+        Ok(requests
+            .iter()
+            .filter(|r| &r.server_id == server_id && &r.user_id == user_id)
+            .max_by_key(|r| r.created_at)
+            .cloned())
+    }
+
+    async fn list_by_server(
+        &self,
+        server_id: &ServerId,
+        status: JoinRequestStatus,
+        pagination: &GetPaginated,
+    ) -> Result<(Vec<ServerJoinRequest>, TotalPaginatedElements), CoreError> {
+        let requests = self.requests.lock().unwrap();
+
+        let filtered: Vec<ServerJoinRequest> = requests
+            .iter()
+            .filter(|r| &r.server_id == server_id && r.status == status)
+            .cloned()
+            .collect();
+        let total = filtered.len() as u64;
+        let offset = (pagination.page - 1) * pagination.limit;
+        let page = filtered
+            .into_iter()
+            .skip(offset as usize)
+            .take(pagination.limit as usize)
+            .collect();
+
+        Ok((page, total))
+    }
+
+    async fn review(
+        &self,
+        id: &ServerJoinRequestId,
+        status: JoinRequestStatus,
+        reviewer_id: &UserId,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        let mut requests = self.requests.lock().unwrap();
+
+        let request = requests
+            .iter_mut()
+            .find(|r| &r.id == id)
+            .ok_or(CoreError::JoinRequestNotFound)?;
+        if request.status != JoinRequestStatus::Pending {
+            return Err(CoreError::JoinRequestAlreadyReviewed { id: *id });
+        }
+        request.status = status;
+        request.reviewer_id = Some(*reviewer_id);
+        request.updated_at = Some(Utc::now());
+
+        Ok(request.clone())
+    }
+}
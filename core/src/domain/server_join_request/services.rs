@@ -0,0 +1,213 @@
+use crate::domain::{
+    authorization::ports::AuthorizationRepository,
+    channel::ports::ChannelRepository,
+    channel_member::ports::ChannelMemberRepository,
+    common::{CoreError, GetPaginated, TotalPaginatedElements, services::Service},
+    friend::{entities::UserId, ports::FriendshipRepository},
+    health::port::HealthRepository,
+    member_role::ports::MemberRoleRepository,
+    outbox::ports::OutboxRepository,
+    role::ports::RoleRepository,
+    server::{
+        entities::{ServerId, ServerJoinMode, ServerSettings},
+        ports::ServerRepository,
+    },
+    server_ban::ports::ServerBanRepository,
+    server_invitation::ports::ServerInvitationRepository,
+    server_join_request::{
+        entities::{
+            JoinQuestions, JoinRequestStatus, ListJoinRequestsQuery, ServerJoinRequest,
+            ServerJoinRequestId, SubmitJoinRequestRequest,
+        },
+        ports::{ServerJoinRequestRepository, ServerJoinRequestService},
+    },
+    server_member::{CreateMemberInput, MemberRepository},
+    server_pictures::ServerPicturesRepository,
+    user::port::UserRepository,
+};
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> ServerJoinRequestService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    async fn get_join_questions(&self, server_id: ServerId) -> Result<JoinQuestions, CoreError> {
+        let settings = self.request_to_join_settings(&server_id).await?;
+
+        Ok(JoinQuestions {
+            server_id,
+            questions: settings.join_questions,
+        })
+    }
+
+    async fn submit_join_request(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        request: SubmitJoinRequestRequest,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        let settings = self.request_to_join_settings(&server_id).await?;
+
+        if self
+            .member_repository
+            .find_by_server_and_user(&server_id, &user_id)
+            .await
+            .is_ok()
+        {
+            return Err(CoreError::MemberAlreadyExists { server_id, user_id });
+        }
+        // Banned users would only be turned down once approved
+        if self
+            .server_ban_repository
+            .find_active(&server_id, &user_id)
+            .await?
+            .is_some()
+        {
+            return Err(CoreError::UserBanned { server_id, user_id });
+        }
+
+        let input = request.into_input(server_id, user_id, &settings.join_questions)?;
+        self.server_join_request_repository.insert(input).await
+    }
+
+    async fn get_own_join_request(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        self.server_join_request_repository
+            .find_latest(&server_id, &user_id)
+            .await?
+            .ok_or(CoreError::JoinRequestNotFound)
+    }
+
+    async fn list_join_requests(
+        &self,
+        server_id: ServerId,
+        query: ListJoinRequestsQuery,
+        pagination: &GetPaginated,
+    ) -> Result<(Vec<ServerJoinRequest>, TotalPaginatedElements), CoreError> {
+        let _server = self.server_repository.find_by_id(&server_id).await?;
+        self.server_join_request_repository
+            .list_by_server(&server_id, query.status, pagination)
+            .await
+    }
+
+    async fn approve_join_request(
+        &self,
+        server_id: ServerId,
+        request_id: ServerJoinRequestId,
+        reviewer_id: UserId,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        self.pending_join_request(&server_id, &request_id).await?;
+        let settings = self.server_repository.find_settings(&server_id).await?;
+
+        // Claim the request before joining, so concurrent approvals add the member only once.
+        // Should the join fail the applicant no longer has a pending request and can apply again
+        let request = self
+            .server_join_request_repository
+            .review(&request_id, JoinRequestStatus::Approved, &reviewer_id)
+            .await?;
+
+        // The applicant may have joined through an invitation in the meantime
+        if self
+            .member_repository
+            .find_by_server_and_user(&server_id, &request.user_id)
+            .await
+            .is_err()
+        {
+            self.join_server(
+                CreateMemberInput {
+                    server_id,
+                    user_id: request.user_id,
+                    nickname: None,
+                },
+                &settings,
+            )
+            .await?;
+        }
+
+        Ok(request)
+    }
+
+    async fn deny_join_request(
+        &self,
+        server_id: ServerId,
+        request_id: ServerJoinRequestId,
+        reviewer_id: UserId,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        self.pending_join_request(&server_id, &request_id).await?;
+
+        self.server_join_request_repository
+            .review(&request_id, JoinRequestStatus::Denied, &reviewer_id)
+            .await
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// Settings of a server that accepts join requests
+    async fn request_to_join_settings(
+        &self,
+        server_id: &ServerId,
+    ) -> Result<ServerSettings, CoreError> {
+        let _server = self.server_repository.find_by_id(server_id).await?;
+        let settings = self.server_repository.find_settings(server_id).await?;
+        if settings.join_mode != ServerJoinMode::RequestToJoin {
+            return Err(CoreError::InvalidJoinRequest {
+                msg: "the server does not accept join requests".to_string(),
+            });
+        }
+        Ok(settings)
+    }
+
+    /// A pending request of the server, requests of other servers are not found
+    async fn pending_join_request(
+        &self,
+        server_id: &ServerId,
+        request_id: &ServerJoinRequestId,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        let request = self
+            .server_join_request_repository
+            .find_by_id(request_id)
+            .await?;
+        if &request.server_id != server_id {
+            return Err(CoreError::JoinRequestNotFound);
+        }
+        if request.status != JoinRequestStatus::Pending {
+            return Err(CoreError::JoinRequestAlreadyReviewed { id: *request_id });
+        }
+        Ok(request)
+    }
+}
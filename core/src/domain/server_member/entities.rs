@@ -1,12 +1,14 @@
 use std::ops::Deref;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use events_protobuf::communities_events::{UserJoinServer, UserLeaveServer};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::domain::common::{CoreError, Cursor};
 use crate::domain::friend::entities::UserId;
+use crate::domain::role::entities::RoleId;
 use crate::domain::server::entities::ServerId;
 
 /// Unique identifier for a server member
@@ -54,6 +56,26 @@ pub struct ServerMember {
     pub joined_at: DateTime<Utc>,
     /// Last update timestamp
     pub updated_at: Option<DateTime<Utc>>,
+    /// Until when the member is timed out, empty once the timeout has expired
+    pub timed_out_until: Option<DateTime<Utc>>,
+    /// Per-server profile shown instead of the user's global one
+    #[serde(default)]
+    pub profile: MemberProfile,
+    /// Color of the member's highest role with a color, empty when none has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_color: Option<i32>,
+}
+
+impl ServerMember {
+    /// Keyset position of the member in join date order
+    pub fn cursor(&self) -> Cursor {
+        Cursor::new(self.joined_at, self.id.0)
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out_until
+            .is_some_and(|timed_out_until| timed_out_until > Utc::now())
+    }
 }
 
 impl Into<UserJoinServer> for ServerMember {
@@ -76,8 +98,69 @@ impl From<&sqlx::postgres::PgRow> for ServerMember {
             nickname: row.get("nickname"),
             joined_at: row.get("joined_at"),
             updated_at: row.get("updated_at"),
+            timed_out_until: row.get("timed_out_until"),
+            profile: row.get::<sqlx::types::Json<MemberProfile>, _>("profile").0,
+            display_color: row.get("display_color"),
+        }
+    }
+}
+
+pub const MAX_MEMBER_AVATAR_URL_SIZE: usize = 2048;
+pub const MAX_MEMBER_BIO_SIZE: usize = 190;
+pub const MAX_MEMBER_PRONOUNS_SIZE: usize = 40;
+
+/// Profile of a member specific to one server.
+/// Stored as a document so fields can be added without a migration, every field is optional.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, ToSchema)]
+#[serde(default)]
+pub struct MemberProfile {
+    /// Avatar shown in this server instead of the user's profile picture
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pronouns: Option<String>,
+}
+
+impl MemberProfile {
+    /// Trims every field, drops the empty ones and checks their size
+    pub fn validate(self) -> Result<Self, CoreError> {
+        let avatar_url = profile_field(self.avatar_url, "avatar_url", MAX_MEMBER_AVATAR_URL_SIZE)?;
+        if avatar_url
+            .as_ref()
+            .is_some_and(|url| !url.starts_with("https://") && !url.starts_with("http://"))
+        {
+            return Err(CoreError::InvalidMemberProfile {
+                msg: "avatar_url must be an http or https URL".to_string(),
+            });
         }
+
+        Ok(Self {
+            avatar_url,
+            bio: profile_field(self.bio, "bio", MAX_MEMBER_BIO_SIZE)?,
+            pronouns: profile_field(self.pronouns, "pronouns", MAX_MEMBER_PRONOUNS_SIZE)?,
+        })
+    }
+}
+
+fn profile_field(
+    value: Option<String>,
+    name: &str,
+    max_size: usize,
+) -> Result<Option<String>, CoreError> {
+    let value = value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if value
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > max_size)
+    {
+        return Err(CoreError::InvalidMemberProfile {
+            msg: format!("{} cannot exceed {} characters", name, max_size),
+        });
     }
+    Ok(value)
 }
 
 /// Input for creating a new server member
@@ -88,12 +171,85 @@ pub struct CreateMemberInput {
     pub nickname: Option<String>,
 }
 
+/// Order in which members are listed
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberSortOrder {
+    /// Most recent members first
+    #[default]
+    JoinedAtDesc,
+    /// Oldest members first
+    JoinedAtAsc,
+}
+
+/// Search and filters of the member list
+#[derive(Debug, Deserialize, Clone, Default, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct ListMembersQuery {
+    /// Case insensitive prefix of the nickname or display name of a member
+    pub search: Option<String>,
+    /// Only list members holding this role
+    pub role_id: Option<Uuid>,
+    pub sort: MemberSortOrder,
+    /// Add the display name and profile picture of each member from the User service
+    pub include_profiles: bool,
+}
+
+/// Member as listed, along with the user's public profile when requested
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ListedMember {
+    #[serde(flatten)]
+    pub member: ServerMember,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_picture: Option<String>,
+}
+
+impl From<ServerMember> for ListedMember {
+    fn from(member: ServerMember) -> Self {
+        Self {
+            member,
+            display_name: None,
+            profile_picture: None,
+        }
+    }
+}
+
+/// Member filters resolved for the repository
+#[derive(Debug, Clone, Default)]
+pub struct MemberFilter {
+    pub nickname_prefix: Option<String>,
+    /// Users whose display name starts with the search, listed along the nickname matches
+    pub user_ids: Vec<UserId>,
+    pub role_id: Option<RoleId>,
+    pub sort: MemberSortOrder,
+}
+
+impl MemberFilter {
+    /// Whether the member matches the search, role membership is left to the repository
+    pub fn matches_search(&self, member: &ServerMember) -> bool {
+        let Some(prefix) = &self.nickname_prefix else {
+            return true;
+        };
+        let prefix = prefix.to_lowercase();
+        member
+            .nickname
+            .as_ref()
+            .is_some_and(|nickname| nickname.to_lowercase().starts_with(&prefix))
+            || self.user_ids.contains(&member.user_id)
+    }
+}
+
 /// Input for updating a server member
 #[derive(Debug, Clone, ToSchema)]
 pub struct UpdateMemberInput {
     pub server_id: ServerId,
     pub user_id: UserId,
     pub nickname: Option<String>,
+    /// Replaces the whole profile when set, keeps the current one otherwise
+    pub profile: Option<MemberProfile>,
 }
 
 /// Event emitted when a member is created
@@ -110,6 +266,242 @@ pub struct UpdateMemberEvent {
     pub server_id: ServerId,
     pub user_id: UserId,
     pub nickname: Option<String>,
+    pub profile: MemberProfile,
+}
+
+/// Longest timeout a moderator can give in one go
+pub const MAX_TIMEOUT_DAYS: i64 = 28;
+
+/// Request body for timing out a member
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct TimeoutMemberRequest {
+    /// The member cannot send messages until this date
+    pub until: DateTime<Utc>,
+}
+
+impl TimeoutMemberRequest {
+    pub fn validate(&self) -> Result<(), CoreError> {
+        let now = Utc::now();
+        if self.until <= now {
+            return Err(CoreError::InvalidMemberTimeout {
+                msg: "timeout must end in the future".to_string(),
+            });
+        }
+        if self.until > now + Duration::days(MAX_TIMEOUT_DAYS) {
+            return Err(CoreError::InvalidMemberTimeout {
+                msg: format!("timeout cannot exceed {} days", MAX_TIMEOUT_DAYS),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Event emitted when a member is timed out or their timeout is cleared
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemberTimeoutEvent {
+    pub server_id: ServerId,
+    pub user_id: UserId,
+    pub moderator_id: UserId,
+    /// Empty when the timeout is cleared
+    pub timed_out_until: Option<DateTime<Utc>>,
+}
+
+pub const MAX_REMOVAL_REASON_SIZE: usize = 512;
+
+/// How a member came to leave a server
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberRemovalKind {
+    /// The member left on their own
+    #[default]
+    Leave,
+    /// A moderator removed the member
+    Kick,
+    /// The member was removed because they got banned
+    Ban,
+    /// The member was removed by a prune of inactive members
+    Prune,
+}
+
+/// Who removed a member from a server, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberRemoval {
+    pub kind: MemberRemovalKind,
+    pub actor_id: UserId,
+    pub reason: Option<String>,
+}
+
+impl MemberRemoval {
+    pub fn leave(user_id: UserId) -> Self {
+        Self {
+            kind: MemberRemovalKind::Leave,
+            actor_id: user_id,
+            reason: None,
+        }
+    }
+
+    pub fn kick(moderator_id: UserId, reason: Option<String>) -> Self {
+        Self {
+            kind: MemberRemovalKind::Kick,
+            actor_id: moderator_id,
+            reason,
+        }
+    }
+
+    pub fn ban(moderator_id: UserId, reason: Option<String>) -> Self {
+        Self {
+            kind: MemberRemovalKind::Ban,
+            actor_id: moderator_id,
+            reason,
+        }
+    }
+
+    pub fn prune(moderator_id: UserId, days: u32) -> Self {
+        Self {
+            kind: MemberRemovalKind::Prune,
+            actor_id: moderator_id,
+            reason: Some(format!("Inactive for more than {} days", days)),
+        }
+    }
+}
+
+/// Request body for kicking a member
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct KickMemberRequest {
+    /// Reason shown to the member and kept in the audit trail
+    pub reason: Option<String>,
+}
+
+impl KickMemberRequest {
+    pub fn into_removal(self, moderator_id: UserId) -> Result<MemberRemoval, CoreError> {
+        let reason = self
+            .reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        if reason
+            .as_ref()
+            .is_some_and(|reason| reason.chars().count() > MAX_REMOVAL_REASON_SIZE)
+        {
+            return Err(CoreError::InvalidMemberRemoval {
+                msg: format!(
+                    "reason cannot exceed {} characters",
+                    MAX_REMOVAL_REASON_SIZE
+                ),
+            });
+        }
+
+        Ok(MemberRemoval::kick(moderator_id, reason))
+    }
+}
+
+/// Most users a single bulk operation can target
+pub const MAX_BULK_MEMBERS: usize = 100;
+
+/// Request body listing the users of a bulk operation
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct BulkMembersRequest {
+    pub user_ids: Vec<UserId>,
+}
+
+/// Request body for kicking several members at once
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct BulkKickRequest {
+    pub user_ids: Vec<UserId>,
+    /// Reason applied to every kicked member
+    pub reason: Option<String>,
+}
+
+/// Deduplicates the targets of a bulk operation, keeping their order, and checks their count
+pub fn validate_bulk_user_ids(user_ids: &[UserId]) -> Result<Vec<UserId>, CoreError> {
+    let mut unique: Vec<UserId> = Vec::with_capacity(user_ids.len());
+    for user_id in user_ids {
+        if !unique.contains(user_id) {
+            unique.push(*user_id);
+        }
+    }
+    if unique.is_empty() {
+        return Err(CoreError::InvalidBulkRequest {
+            msg: "at least one user is required".to_string(),
+        });
+    }
+    if unique.len() > MAX_BULK_MEMBERS {
+        return Err(CoreError::InvalidBulkRequest {
+            msg: format!("cannot target more than {} users", MAX_BULK_MEMBERS),
+        });
+    }
+    Ok(unique)
+}
+
+/// Outcome of a bulk operation for one user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct BulkMemberResult {
+    pub user_id: UserId,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BulkMemberResult {
+    pub fn success(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            success: true,
+            error: None,
+        }
+    }
+
+    pub fn failure(user_id: UserId, error: &CoreError) -> Self {
+        Self {
+            user_id,
+            success: false,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+pub const DEFAULT_PRUNE_DAYS: u32 = 30;
+pub const MAX_PRUNE_DAYS: u32 = 365;
+/// Number of members removed per transaction while pruning
+pub const PRUNE_BATCH_SIZE: u32 = 100;
+
+/// Which members a prune targets
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct PruneMembersRequest {
+    /// Members who joined more than this many days ago and only hold the default role are pruned
+    #[serde(default = "default_prune_days")]
+    pub days: u32,
+}
+
+fn default_prune_days() -> u32 {
+    DEFAULT_PRUNE_DAYS
+}
+
+impl Default for PruneMembersRequest {
+    fn default() -> Self {
+        Self {
+            days: DEFAULT_PRUNE_DAYS,
+        }
+    }
+}
+
+impl PruneMembersRequest {
+    /// Members who joined before this date can be pruned
+    pub fn joined_before(&self) -> Result<DateTime<Utc>, CoreError> {
+        if self.days == 0 || self.days > MAX_PRUNE_DAYS {
+            return Err(CoreError::InvalidMemberRemoval {
+                msg: format!("prune days must be between 1 and {}", MAX_PRUNE_DAYS),
+            });
+        }
+        Ok(Utc::now() - Duration::days(self.days as i64))
+    }
+}
+
+/// Number of members a prune removed, or would remove on a dry run
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct PruneMembersResult {
+    pub days: u32,
+    pub count: u64,
 }
 
 /// Event emitted when a member is deleted
@@ -117,6 +509,26 @@ pub struct UpdateMemberEvent {
 pub struct DeleteMemberEvent {
     pub server_id: ServerId,
     pub user_id: UserId,
+    // Defaults keep events written before removals were told apart readable
+    #[serde(default)]
+    pub kind: MemberRemovalKind,
+    /// The member themselves when leaving, the moderator otherwise
+    #[serde(default)]
+    pub actor_id: Option<UserId>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl DeleteMemberEvent {
+    pub fn new(server_id: ServerId, user_id: UserId, removal: MemberRemoval) -> Self {
+        Self {
+            server_id,
+            user_id,
+            kind: removal.kind,
+            actor_id: Some(removal.actor_id),
+            reason: removal.reason,
+        }
+    }
 }
 
 impl Into<UserLeaveServer> for DeleteMemberEvent {
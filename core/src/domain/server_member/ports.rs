@@ -1,14 +1,23 @@
 use std::future::Future;
 use std::sync::{Arc, Mutex};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::common::{CoreError, GetPaginated, TotalPaginatedElements};
+use crate::domain::common::{
+    CoreError, Cursor, CursorPage, GetPaginated, TotalPaginatedElements, next_page,
+};
 use crate::domain::friend::entities::UserId;
+use crate::domain::member_role::entities::AssignMemberRole;
+use crate::domain::member_role::ports::{MemberRoleRepository, MockMemberRoleRepository};
+use crate::domain::role::entities::RoleId;
 use crate::domain::server::entities::ServerId;
 
-use super::entities::{CreateMemberInput, MemberId, ServerMember, UpdateMemberInput};
+use super::entities::{
+    BulkKickRequest, BulkMemberResult, CreateMemberInput, KickMemberRequest, ListMembersQuery,
+    ListedMember, MemberFilter, MemberId, MemberProfile, MemberRemoval, MemberSortOrder,
+    PruneMembersRequest, PruneMembersResult, ServerMember, TimeoutMemberRequest, UpdateMemberInput,
+};
 
 /// Repository trait for server member persistence
 pub trait MemberRepository: Send + Sync {
@@ -18,6 +27,14 @@ pub trait MemberRepository: Send + Sync {
         input: CreateMemberInput,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 
+    /// Insert a new server member holding the given roles, in one transaction.
+    /// Role ids that no longer belong to the server are skipped.
+    fn insert_with_roles(
+        &self,
+        input: CreateMemberInput,
+        role_ids: &[RoleId],
+    ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
+
     /// Find a member by server ID and user ID
     fn find_by_server_and_user(
         &self,
@@ -25,30 +42,79 @@ pub trait MemberRepository: Send + Sync {
         user_id: &UserId,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 
-    /// List all members of a server with pagination
+    /// List the members of a server matching the filter with pagination
     fn list_by_server(
         &self,
         server_id: &ServerId,
         pagination: &GetPaginated,
+        filter: &MemberFilter,
     ) -> impl Future<Output = Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError>> + Send;
 
+    /// List the members of a server matching the filter after the cursor, keyed by join date
+    fn list_by_server_after(
+        &self,
+        server_id: &ServerId,
+        after: Option<Cursor>,
+        limit: u32,
+        filter: &MemberFilter,
+    ) -> impl Future<Output = Result<CursorPage<ServerMember>, CoreError>> + Send;
+
     /// Update a server member
     fn update(
         &self,
         input: UpdateMemberInput,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 
-    /// Delete a server member
+    /// Remove a member from a server, publishing who removed them and why
     fn delete(
         &self,
         server_id: &ServerId,
         user_id: &UserId,
+        removal: MemberRemoval,
     ) -> impl Future<Output = Result<(), CoreError>> + Send;
 
+    /// Remove several members in one transaction, publishing each removal.
+    /// Returns the users that were members and have been removed
+    fn delete_many(
+        &self,
+        server_id: &ServerId,
+        user_ids: &[UserId],
+        removal: MemberRemoval,
+    ) -> impl Future<Output = Result<Vec<UserId>, CoreError>> + Send;
+
+    /// Count the members other than the owner who joined before the date
+    /// and hold no role besides the default one
+    fn count_prunable(
+        &self,
+        server_id: &ServerId,
+        owner_id: &UserId,
+        joined_before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+
+    /// Remove up to `limit` prunable members in one transaction, publishing each removal.
+    /// Returns the users that have been removed
+    fn prune_batch(
+        &self,
+        server_id: &ServerId,
+        owner_id: &UserId,
+        joined_before: DateTime<Utc>,
+        limit: u32,
+        removal: MemberRemoval,
+    ) -> impl Future<Output = Result<Vec<UserId>, CoreError>> + Send;
+
     fn find_by_id(
         &self,
         member_id: MemberId,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
+
+    /// Set or clear (with `None`) the timeout of a member and publish it
+    fn set_timeout(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        moderator_id: &UserId,
+        timed_out_until: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 }
 
 /// Service trait for server member business logic
@@ -68,20 +134,35 @@ pub trait MemberService: Send + Sync {
         input: CreateMemberInput,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 
-    /// List all members of a server with pagination
+    /// List the members of a server with pagination
     ///
     /// # Arguments
     /// * `server_id` - The server to list members from
     /// * `pagination` - Page number and limit
+    /// * `query` - Nickname or display name prefix search, role filter and join date order
     ///
     /// # Returns
-    /// * `Ok((Vec<ServerMember>, TotalPaginatedElements))` - List of members and total count
+    /// * `Ok((Vec<ListedMember>, TotalPaginatedElements))` - List of members and total count
     /// * `Err(CoreError::ServerNotFound)` - If the server doesn't exist
     fn list_members(
         &self,
         server_id: ServerId,
         pagination: GetPaginated,
-    ) -> impl Future<Output = Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError>> + Send;
+        query: ListMembersQuery,
+    ) -> impl Future<Output = Result<(Vec<ListedMember>, TotalPaginatedElements), CoreError>> + Send;
+
+    /// List the members of a server after a cursor, keyed by join date then id
+    ///
+    /// # Returns
+    /// * `Ok((Vec<ListedMember>, TotalPaginatedElements, Option<Cursor>))` - Members, total count and the cursor of the next page
+    /// * `Err(CoreError::ServerNotFound)` - If the server doesn't exist
+    fn list_members_after(
+        &self,
+        server_id: ServerId,
+        after: Option<Cursor>,
+        limit: u32,
+        query: ListMembersQuery,
+    ) -> impl Future<Output = Result<CursorPage<ListedMember>, CoreError>> + Send;
 
     /// Update a server member
     ///
@@ -92,26 +173,89 @@ pub trait MemberService: Send + Sync {
     /// * `Ok(ServerMember)` - The updated member
     /// * `Err(CoreError::MemberNotFound)` - If the member doesn't exist
     /// * `Err(CoreError::InvalidMemberNickname)` - If the nickname is empty or whitespace
+    /// * `Err(CoreError::InvalidMemberProfile)` - If a profile field is too long or malformed
     fn update_member(
         &self,
         input: UpdateMemberInput,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 
-    /// Delete a server member
+    /// Leave a server
+    ///
+    /// # Arguments
+    /// * `server_id` - The server to leave
+    /// * `user_id` - The user leaving
+    ///
+    /// # Returns
+    /// * `Ok(())` - Member successfully removed
+    /// * `Err(CoreError::Forbidden)` - If the user owns the server
+    /// * `Err(CoreError::MemberNotFound)` - If the member doesn't exist
+    fn leave_server(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Kick a member out of a server
     ///
     /// # Arguments
     /// * `server_id` - The server to remove the member from
     /// * `user_id` - The user to remove
+    /// * `moderator_id` - The user removing them
+    /// * `request` - The optional reason of the kick
     ///
     /// # Returns
-    /// * `Ok(())` - Member successfully deleted
+    /// * `Ok(())` - Member successfully removed
+    /// * `Err(CoreError::InvalidMemberRemoval)` - If the reason is too long or the moderator kicks themselves
+    /// * `Err(CoreError::Forbidden)` - If the member owns the server
     /// * `Err(CoreError::MemberNotFound)` - If the member doesn't exist
-    fn delete_member(
+    fn kick_member(
         &self,
         server_id: ServerId,
         user_id: UserId,
+        moderator_id: UserId,
+        request: KickMemberRequest,
     ) -> impl Future<Output = Result<(), CoreError>> + Send;
 
+    /// Kick several members in one transaction
+    ///
+    /// # Returns
+    /// * `Ok(Vec<BulkMemberResult>)` - The outcome for each distinct user, in request order
+    /// * `Err(CoreError::InvalidBulkRequest)` - If no user or too many users are listed
+    /// * `Err(CoreError::InvalidMemberRemoval)` - If the reason is too long
+    /// * `Err(CoreError::ServerNotFound)` - If the server doesn't exist
+    fn bulk_kick_members(
+        &self,
+        server_id: ServerId,
+        moderator_id: UserId,
+        request: BulkKickRequest,
+    ) -> impl Future<Output = Result<Vec<BulkMemberResult>, CoreError>> + Send;
+
+    /// Count the members a prune would remove, without removing them
+    ///
+    /// # Returns
+    /// * `Ok(PruneMembersResult)` - The number of prunable members
+    /// * `Err(CoreError::InvalidMemberRemoval)` - If the number of days is out of range
+    /// * `Err(CoreError::ServerNotFound)` - If the server doesn't exist
+    fn count_prunable_members(
+        &self,
+        server_id: ServerId,
+        request: PruneMembersRequest,
+    ) -> impl Future<Output = Result<PruneMembersResult, CoreError>> + Send;
+
+    /// Remove the members who joined more than the given days ago and only hold
+    /// the default role, in batches
+    ///
+    /// # Returns
+    /// * `Ok(PruneMembersResult)` - The number of removed members
+    /// * `Err(CoreError::InvalidMemberRemoval)` - If the number of days is out of range
+    /// * `Err(CoreError::ServerNotFound)` - If the server doesn't exist
+    fn prune_members(
+        &self,
+        server_id: ServerId,
+        moderator_id: UserId,
+        request: PruneMembersRequest,
+    ) -> impl Future<Output = Result<PruneMembersResult, CoreError>> + Send;
+
     /// Get a server member by server ID and user ID
     /// # Arguments
     /// * `server_id` - The server ID
@@ -129,18 +273,54 @@ pub trait MemberService: Send + Sync {
         &self,
         member_id: MemberId,
     ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
+
+    /// Whether the server lets members change their own nickname
+    fn can_change_own_nickname(
+        &self,
+        server_id: ServerId,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Time out a member so they cannot send messages until the given date
+    ///
+    /// # Returns
+    /// * `Ok(ServerMember)` - The timed out member
+    /// * `Err(CoreError::InvalidMemberTimeout)` - If the date is in the past, too far away, or the moderator targets themselves
+    /// * `Err(CoreError::Forbidden)` - If the member is the owner of the server
+    /// * `Err(CoreError::MemberNotFound)` - If the member doesn't exist
+    fn timeout_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+        request: TimeoutMemberRequest,
+    ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
+
+    /// Lift the timeout of a member before it expires
+    fn clear_member_timeout(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+    ) -> impl Future<Output = Result<ServerMember, CoreError>> + Send;
 }
 
 /// Mock implementation of MemberRepository for testing
 #[derive(Clone)]
 pub struct MockMemberRepository {
     members: Arc<Mutex<Vec<ServerMember>>>,
+    member_role_repository: MockMemberRoleRepository,
 }
 
 impl MockMemberRepository {
     pub fn new() -> Self {
+        Self::with_member_role_repository(MockMemberRoleRepository::new())
+    }
+
+    /// Shares the member role mock the roles of joining members are assigned into
+    pub fn with_member_role_repository(member_role_repository: MockMemberRoleRepository) -> Self {
         Self {
             members: Arc::new(Mutex::new(Vec::new())),
+            member_role_repository,
         }
     }
 }
@@ -156,12 +336,33 @@ impl MemberRepository for MockMemberRepository {
             nickname: input.nickname,
             joined_at: Utc::now(),
             updated_at: None,
+            timed_out_until: None,
+            profile: MemberProfile::default(),
+            display_color: None,
         };
 
         members.push(member.clone());
         Ok(member)
     }
 
+    async fn insert_with_roles(
+        &self,
+        input: CreateMemberInput,
+        role_ids: &[RoleId],
+    ) -> Result<ServerMember, CoreError> {
+        let member = self.insert(input).await?;
+        for role_id in role_ids {
+            self.member_role_repository
+                .assign(AssignMemberRole {
+                    member_id: member.id,
+                    role_id: *role_id,
+                    expires_at: None,
+                })
+                .await?;
+        }
+        Ok(member)
+    }
+
     async fn find_by_server_and_user(
         &self,
         server_id: &ServerId,
@@ -185,13 +386,20 @@ impl MemberRepository for MockMemberRepository {
         &self,
         server_id: &ServerId,
         pagination: &GetPaginated,
+        filter: &MemberFilter,
     ) -> Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError> {
         let members = self.members.lock().unwrap();
-        let filtered: Vec<ServerMember> = members
+        let mut filtered: Vec<ServerMember> = members
             .iter()
-            .filter(|m| m.server_id == *server_id)
+            .filter(|m| m.server_id == *server_id && filter.matches_search(m))
             .cloned()
             .collect();
+        match filter.sort {
+            MemberSortOrder::JoinedAtDesc => {
+                filtered.sort_by_key(|m| std::cmp::Reverse(m.cursor()))
+            }
+            MemberSortOrder::JoinedAtAsc => filtered.sort_by_key(|m| m.cursor()),
+        }
 
         let total = filtered.len() as u64;
         let offset = (pagination.page - 1) * pagination.limit;
@@ -204,6 +412,40 @@ impl MemberRepository for MockMemberRepository {
         Ok((paginated, total))
     }
 
+    async fn list_by_server_after(
+        &self,
+        server_id: &ServerId,
+        after: Option<Cursor>,
+        limit: u32,
+        filter: &MemberFilter,
+    ) -> Result<CursorPage<ServerMember>, CoreError> {
+        let members = self.members.lock().unwrap();
+        let mut filtered: Vec<ServerMember> = members
+            .iter()
+            .filter(|m| m.server_id == *server_id && filter.matches_search(m))
+            .cloned()
+            .collect();
+        match filter.sort {
+            MemberSortOrder::JoinedAtDesc => {
+                filtered.sort_by_key(|m| std::cmp::Reverse(m.cursor()))
+            }
+            MemberSortOrder::JoinedAtAsc => filtered.sort_by_key(|m| m.cursor()),
+        }
+
+        let total = filtered.len() as u64;
+        let page: Vec<ServerMember> = filtered
+            .into_iter()
+            .filter(|m| match (after, filter.sort) {
+                (None, _) => true,
+                (Some(after), MemberSortOrder::JoinedAtDesc) => m.cursor() < after,
+                (Some(after), MemberSortOrder::JoinedAtAsc) => m.cursor() > after,
+            })
+            .take(limit as usize + 1)
+            .collect();
+
+        Ok(next_page(page, limit, ServerMember::cursor, total))
+    }
+
     async fn update(&self, input: UpdateMemberInput) -> Result<ServerMember, CoreError> {
         let mut members = self.members.lock().unwrap();
         let member = members
@@ -215,6 +457,9 @@ impl MemberRepository for MockMemberRepository {
                 if let Some(nickname) = input.nickname {
                     m.nickname = Some(nickname);
                 }
+                if let Some(profile) = input.profile {
+                    m.profile = profile;
+                }
                 m.updated_at = Some(Utc::now());
                 Ok(m.clone())
             }
@@ -225,7 +470,12 @@ impl MemberRepository for MockMemberRepository {
         }
     }
 
-    async fn delete(&self, server_id: &ServerId, user_id: &UserId) -> Result<(), CoreError> {
+    async fn delete(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        _removal: MemberRemoval,
+    ) -> Result<(), CoreError> {
         let mut members = self.members.lock().unwrap();
         let initial_len = members.len();
         members.retain(|m| !(m.server_id == *server_id && m.user_id == *user_id));
@@ -240,6 +490,61 @@ impl MemberRepository for MockMemberRepository {
         }
     }
 
+    async fn delete_many(
+        &self,
+        server_id: &ServerId,
+        user_ids: &[UserId],
+        _removal: MemberRemoval,
+    ) -> Result<Vec<UserId>, CoreError> {
+        let mut members = self.members.lock().unwrap();
+        let removed: Vec<UserId> = members
+            .iter()
+            .filter(|m| m.server_id == *server_id && user_ids.contains(&m.user_id))
+            .map(|m| m.user_id)
+            .collect();
+        members.retain(|m| !(m.server_id == *server_id && user_ids.contains(&m.user_id)));
+
+        Ok(removed)
+    }
+
+    // Mock members hold no roles, so any old enough member other than the owner is prunable
+    async fn count_prunable(
+        &self,
+        server_id: &ServerId,
+        owner_id: &UserId,
+        joined_before: DateTime<Utc>,
+    ) -> Result<u64, CoreError> {
+        let members = self.members.lock().unwrap();
+        Ok(members
+            .iter()
+            .filter(|m| {
+                m.server_id == *server_id && m.user_id != *owner_id && m.joined_at < joined_before
+            })
+            .count() as u64)
+    }
+
+    async fn prune_batch(
+        &self,
+        server_id: &ServerId,
+        owner_id: &UserId,
+        joined_before: DateTime<Utc>,
+        limit: u32,
+        _removal: MemberRemoval,
+    ) -> Result<Vec<UserId>, CoreError> {
+        let mut members = self.members.lock().unwrap();
+        let removed: Vec<UserId> = members
+            .iter()
+            .filter(|m| {
+                m.server_id == *server_id && m.user_id != *owner_id && m.joined_at < joined_before
+            })
+            .take(limit as usize)
+            .map(|m| m.user_id)
+            .collect();
+        members.retain(|m| !(m.server_id == *server_id && removed.contains(&m.user_id)));
+
+        Ok(removed)
+    }
+
     async fn find_by_id(&self, member_id: MemberId) -> Result<ServerMember, CoreError> {
         let members = self.members.lock().unwrap();
 
@@ -250,4 +555,25 @@ impl MemberRepository for MockMemberRepository {
 
         Ok(member.to_owned())
     }
+
+    async fn set_timeout(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        _moderator_id: &UserId,
+        timed_out_until: Option<DateTime<Utc>>,
+    ) -> Result<ServerMember, CoreError> {
+        let mut members = self.members.lock().unwrap();
+        let member = members
+            .iter_mut()
+            .find(|m| m.server_id == *server_id && m.user_id == *user_id)
+            .ok_or(CoreError::MemberNotFound {
+                server_id: *server_id,
+                user_id: *user_id,
+            })?;
+
+        member.timed_out_until = timed_out_until;
+        member.updated_at = Some(Utc::now());
+        Ok(member.clone())
+    }
 }
@@ -1,26 +1,36 @@
+use uuid::Uuid;
+
 use crate::domain::authorization::ports::AuthorizationRepository;
 use crate::domain::channel::ports::ChannelRepository;
 use crate::domain::channel_member::ports::ChannelMemberRepository;
 use crate::domain::common::services::Service;
-use crate::domain::common::{CoreError, GetPaginated, TotalPaginatedElements};
+use crate::domain::common::{CoreError, Cursor, CursorPage, GetPaginated, TotalPaginatedElements};
 use crate::domain::friend::entities::UserId;
 use crate::domain::friend::ports::FriendshipRepository;
 use crate::domain::health::port::HealthRepository;
 use crate::domain::member_role::ports::MemberRoleRepository;
 use crate::domain::outbox::ports::OutboxRepository;
+use crate::domain::role::entities::RoleId;
 use crate::domain::role::ports::RoleRepository;
-use crate::domain::server::entities::ServerId;
+use crate::domain::server::entities::{ServerId, ServerSettings};
 use crate::domain::server::ports::ServerRepository;
+use crate::domain::server_ban::ports::ServerBanRepository;
 use crate::domain::server_invitation::ports::ServerInvitationRepository;
+use crate::domain::server_join_request::ports::ServerJoinRequestRepository;
 use crate::domain::server_member::MemberId;
 use crate::domain::server_pictures::ServerPicturesRepository;
 use crate::domain::user::port::UserRepository;
 
-use super::entities::{CreateMemberInput, ServerMember, UpdateMemberInput};
+use super::entities::{
+    BulkKickRequest, BulkMemberResult, CreateMemberInput, KickMemberRequest, ListMembersQuery,
+    ListedMember, MemberFilter, MemberProfile, MemberRemoval, PRUNE_BATCH_SIZE,
+    PruneMembersRequest, PruneMembersResult, ServerMember, TimeoutMemberRequest, UpdateMemberInput,
+    validate_bulk_user_ids,
+};
 use super::ports::{MemberRepository, MemberService};
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> MemberService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> MemberService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -35,10 +45,18 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn create_member(&self, input: CreateMemberInput) -> Result<ServerMember, CoreError> {
         // Validate server exists
-        let _server = self.server_repository.find_by_id(&input.server_id).await?;
+        let server = self.server_repository.find_by_id(&input.server_id).await?;
+
+        // Joining without an invitation is only possible for open public servers
+        let settings = self.server_repository.find_settings(&server.id).await?;
+        if !settings.allows_open_join(&server.visibility) {
+            return Err(CoreError::Forbidden);
+        }
 
         // Check if member already exists
         if let Ok(_existing) = self
@@ -60,27 +78,49 @@ where
         }
 
         // Create member
-        let member = self.member_repository.insert(input).await?;
-        Ok(member)
+        self.join_server(input, &settings).await
     }
 
     async fn list_members(
         &self,
         server_id: ServerId,
         pagination: GetPaginated,
-    ) -> Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError> {
+        query: ListMembersQuery,
+    ) -> Result<(Vec<ListedMember>, TotalPaginatedElements), CoreError> {
         // Validate server exists
         let _server = self.server_repository.find_by_id(&server_id).await?;
 
+        let filter = self.member_filter(&query).await;
+
         // List members
         let (members, total) = self
             .member_repository
-            .list_by_server(&server_id, &pagination)
+            .list_by_server(&server_id, &pagination, &filter)
             .await?;
+        let members = self.listed_members(members, query.include_profiles).await;
 
         Ok((members, total))
     }
 
+    async fn list_members_after(
+        &self,
+        server_id: ServerId,
+        after: Option<Cursor>,
+        limit: u32,
+        query: ListMembersQuery,
+    ) -> Result<CursorPage<ListedMember>, CoreError> {
+        let _server = self.server_repository.find_by_id(&server_id).await?;
+
+        let filter = self.member_filter(&query).await;
+        let (members, total, next_cursor) = self
+            .member_repository
+            .list_by_server_after(&server_id, after, limit, &filter)
+            .await?;
+        let members = self.listed_members(members, query.include_profiles).await;
+
+        Ok((members, total, next_cursor))
+    }
+
     async fn update_member(&self, input: UpdateMemberInput) -> Result<ServerMember, CoreError> {
         // Check if member exists
 
@@ -96,20 +136,168 @@ where
             }
         }
 
+        let profile = input.profile.map(MemberProfile::validate).transpose()?;
+
         // Update member
-        let member = self.member_repository.update(input).await?;
+        let member = self
+            .member_repository
+            .update(UpdateMemberInput { profile, ..input })
+            .await?;
         Ok(member)
     }
 
-    async fn delete_member(&self, server_id: ServerId, user_id: UserId) -> Result<(), CoreError> {
-        // Check if member exists
-        let _ = self
+    async fn leave_server(&self, server_id: ServerId, user_id: UserId) -> Result<(), CoreError> {
+        // The owner has to hand the server over or delete it instead
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        if server.owner_id == user_id {
+            return Err(CoreError::Forbidden);
+        }
+
+        self.member_repository
+            .delete(&server_id, &user_id, MemberRemoval::leave(user_id))
+            .await
+    }
+
+    async fn kick_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+        request: KickMemberRequest,
+    ) -> Result<(), CoreError> {
+        if moderator_id == user_id {
+            return Err(CoreError::InvalidMemberRemoval {
+                msg: "cannot kick yourself, leave the server instead".to_string(),
+            });
+        }
+        let removal = request.into_removal(moderator_id)?;
+
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        if server.owner_id == user_id {
+            return Err(CoreError::Forbidden);
+        }
+        self.role_authority(server_id, moderator_id)
+            .await?
+            .check_position(self.member_rank(server_id, user_id).await?)?;
+
+        self.member_repository
+            .delete(&server_id, &user_id, removal)
+            .await
+    }
+
+    async fn bulk_kick_members(
+        &self,
+        server_id: ServerId,
+        moderator_id: UserId,
+        request: BulkKickRequest,
+    ) -> Result<Vec<BulkMemberResult>, CoreError> {
+        let user_ids = validate_bulk_user_ids(&request.user_ids)?;
+        let removal = KickMemberRequest {
+            reason: request.reason,
+        }
+        .into_removal(moderator_id)?;
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        let authority = self.role_authority(server_id, moderator_id).await?;
+
+        // Users that cannot be kicked are reported without failing the others
+        let mut rejected = Vec::new();
+        let mut targets = Vec::new();
+        for user_id in &user_ids {
+            if *user_id == moderator_id {
+                rejected.push(BulkMemberResult::failure(
+                    *user_id,
+                    &CoreError::InvalidMemberRemoval {
+                        msg: "cannot kick yourself, leave the server instead".to_string(),
+                    },
+                ));
+            } else if *user_id == server.owner_id {
+                rejected.push(BulkMemberResult::failure(*user_id, &CoreError::Forbidden));
+            } else if let Err(e) =
+                authority.check_position(self.member_rank(server_id, *user_id).await?)
+            {
+                rejected.push(BulkMemberResult::failure(*user_id, &e));
+            } else {
+                targets.push(*user_id);
+            }
+        }
+
+        let removed = if targets.is_empty() {
+            Vec::new()
+        } else {
+            self.member_repository
+                .delete_many(&server_id, &targets, removal)
+                .await?
+        };
+
+        Ok(user_ids
+            .into_iter()
+            .map(|user_id| {
+                if let Some(result) = rejected.iter().find(|r| r.user_id == user_id) {
+                    result.clone()
+                } else if removed.contains(&user_id) {
+                    BulkMemberResult::success(user_id)
+                } else {
+                    BulkMemberResult::failure(
+                        user_id,
+                        &CoreError::MemberNotFound { server_id, user_id },
+                    )
+                }
+            })
+            .collect())
+    }
+
+    async fn count_prunable_members(
+        &self,
+        server_id: ServerId,
+        request: PruneMembersRequest,
+    ) -> Result<PruneMembersResult, CoreError> {
+        let joined_before = request.joined_before()?;
+        let server = self.server_repository.find_by_id(&server_id).await?;
+
+        let count = self
             .member_repository
-            .find_by_server_and_user(&server_id, &user_id)
+            .count_prunable(&server_id, &server.owner_id, joined_before)
             .await?;
-        // Delete member
-        self.member_repository.delete(&server_id, &user_id).await?;
-        Ok(())
+
+        Ok(PruneMembersResult {
+            days: request.days,
+            count,
+        })
+    }
+
+    async fn prune_members(
+        &self,
+        server_id: ServerId,
+        moderator_id: UserId,
+        request: PruneMembersRequest,
+    ) -> Result<PruneMembersResult, CoreError> {
+        let joined_before = request.joined_before()?;
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        let removal = MemberRemoval::prune(moderator_id, request.days);
+
+        // Batches keep each transaction and its outbox writes small on large servers
+        let mut count = 0;
+        loop {
+            let removed = self
+                .member_repository
+                .prune_batch(
+                    &server_id,
+                    &server.owner_id,
+                    joined_before,
+                    PRUNE_BATCH_SIZE,
+                    removal.clone(),
+                )
+                .await?;
+            count += removed.len() as u64;
+            if removed.len() < PRUNE_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok(PruneMembersResult {
+            days: request.days,
+            count,
+        })
     }
 
     async fn get_member(
@@ -125,4 +313,156 @@ where
     async fn get_member_by_id(&self, member_id: MemberId) -> Result<ServerMember, CoreError> {
         self.member_repository.find_by_id(member_id).await
     }
+
+    async fn can_change_own_nickname(&self, server_id: ServerId) -> Result<bool, CoreError> {
+        let settings = self.server_repository.find_settings(&server_id).await?;
+        Ok(settings.allow_nickname_change)
+    }
+
+    async fn timeout_member(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+        request: TimeoutMemberRequest,
+    ) -> Result<ServerMember, CoreError> {
+        request.validate()?;
+        if moderator_id == user_id {
+            return Err(CoreError::InvalidMemberTimeout {
+                msg: "cannot time yourself out".to_string(),
+            });
+        }
+
+        let server = self.server_repository.find_by_id(&server_id).await?;
+        if server.owner_id == user_id {
+            return Err(CoreError::Forbidden);
+        }
+        self.role_authority(server_id, moderator_id)
+            .await?
+            .check_position(self.member_rank(server_id, user_id).await?)?;
+
+        self.member_repository
+            .set_timeout(&server_id, &user_id, &moderator_id, Some(request.until))
+            .await
+    }
+
+    async fn clear_member_timeout(
+        &self,
+        server_id: ServerId,
+        user_id: UserId,
+        moderator_id: UserId,
+    ) -> Result<ServerMember, CoreError> {
+        // Lifting a timeout takes the same rank as setting it
+        self.role_authority(server_id, moderator_id)
+            .await?
+            .check_position(self.member_rank(server_id, user_id).await?)?;
+
+        self.member_repository
+            .set_timeout(&server_id, &user_id, &moderator_id, None)
+            .await
+    }
+}
+
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+    Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
+where
+    S: ServerRepository,
+    F: FriendshipRepository,
+    U: UserRepository,
+    H: HealthRepository,
+    M: MemberRepository,
+    C: ChannelRepository,
+    R: RoleRepository,
+    O: OutboxRepository,
+    CM: ChannelMemberRepository,
+    MR: MemberRoleRepository,
+    SI: ServerInvitationRepository,
+    A: AuthorizationRepository,
+    SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
+{
+    /// Resolve the search of a member listing into repository filters
+    async fn member_filter(&self, query: &ListMembersQuery) -> MemberFilter {
+        let search = query
+            .search
+            .as_ref()
+            .map(|search| search.trim().to_string())
+            .filter(|search| !search.is_empty());
+
+        // Display names live in the user service, the matching users are
+        // listed along the members whose nickname matches
+        let mut user_ids = Vec::new();
+        if let Some(search) = &search {
+            match self
+                .user_repository
+                .search_users_by_display_name(search)
+                .await
+            {
+                Ok(users) => user_ids = users.iter().map(|user| UserId(user.sub)).collect(),
+                Err(e) => tracing::warn!("Could not search members by display name: {}", e),
+            }
+        }
+
+        MemberFilter {
+            nickname_prefix: search,
+            user_ids,
+            role_id: query.role_id.map(RoleId),
+            sort: query.sort,
+        }
+    }
+
+    /// Wrap members for listing, with the users' profiles when asked for
+    pub(crate) async fn listed_members(
+        &self,
+        members: Vec<ServerMember>,
+        include_profiles: bool,
+    ) -> Vec<ListedMember> {
+        let mut members: Vec<ListedMember> = members.into_iter().map(Into::into).collect();
+
+        // Profiles are a convenience for clients, the list is still returned without them
+        if include_profiles && !members.is_empty() {
+            let user_ids: Vec<Uuid> = members.iter().map(|m| m.member.user_id.0).collect();
+            match self.user_repository.get_users_by_ids(&user_ids).await {
+                Ok(users) => {
+                    for member in &mut members {
+                        if let Some(user) = users.iter().find(|u| u.sub == member.member.user_id.0)
+                        {
+                            member.display_name = Some(user.display_name.clone());
+                            member.profile_picture = Some(user.profile_picture.clone());
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Could not fetch member profiles: {}", e),
+            }
+        }
+
+        members
+    }
+
+    /// Adds a user to a server and applies the join settings of the server.
+    ///
+    /// Every path making a user a member goes through here once it has been allowed
+    /// to join, so that bans and the default role set are applied consistently.
+    pub(crate) async fn join_server(
+        &self,
+        input: CreateMemberInput,
+        settings: &ServerSettings,
+    ) -> Result<ServerMember, CoreError> {
+        if self
+            .server_ban_repository
+            .find_active(&input.server_id, &input.user_id)
+            .await?
+            .is_some()
+        {
+            return Err(CoreError::UserBanned {
+                server_id: input.server_id,
+                user_id: input.user_id,
+            });
+        }
+
+        self.member_repository
+            .insert_with_roles(input, &settings.default_role_ids)
+            .await
+    }
 }
@@ -6,6 +6,7 @@ use tracing::debug;
 pub enum Content {
     ServerPicture,
     ServerBanner,
+    RoleIcon,
 }
 
 impl Display for Content {
@@ -13,6 +14,7 @@ impl Display for Content {
         match self {
             Content::ServerPicture => write!(f, "server_picture"),
             Content::ServerBanner => write!(f, "server_banner"),
+            Content::RoleIcon => write!(f, "role_icon"),
         }
     }
 }
@@ -1,15 +1,19 @@
 use futures_util::future::join_all;
 
+use uuid::Uuid;
+
 use crate::domain::{
     common::CoreError,
+    role::entities::RoleId,
     server::entities::ServerId,
     server_pictures::{Content, ContentVerb, PresignedUrl, ServerPictureUrls, ServerPicturesMap},
 };
 
 pub trait ServerPicturesRepository: Send + Sync {
+    /// Sign a URL for the content of a server or a role, keyed by its id
     fn get_signed_url(
         &self,
-        server_id: ServerId,
+        resource_id: Uuid,
         content: Content,
         verb: ContentVerb,
     ) -> impl Future<Output = Result<PresignedUrl, CoreError>> + Send;
@@ -48,6 +52,16 @@ pub trait ServerPicturesRepository: Send + Sync {
         &self,
         server_ids: Vec<ServerId>,
     ) -> impl Future<Output = ServerPicturesMap> + Send;
+
+    fn put_role_icon(
+        &self,
+        role_id: RoleId,
+    ) -> impl Future<Output = Result<PresignedUrl, CoreError>> + Send;
+
+    fn get_role_icon(
+        &self,
+        role_id: RoleId,
+    ) -> impl Future<Output = Result<PresignedUrl, CoreError>> + Send;
 }
 
 pub trait ServerPicturesService: Send + Sync {
@@ -97,7 +111,7 @@ impl MockServerPicturesRepository {
 impl ServerPicturesRepository for MockServerPicturesRepository {
     async fn get_signed_url(
         &self,
-        _server_id: ServerId,
+        _resource_id: Uuid,
         _content: Content,
         _verb: ContentVerb,
     ) -> Result<PresignedUrl, CoreError> {
@@ -147,4 +161,12 @@ impl ServerPicturesRepository for MockServerPicturesRepository {
         });
         join_all(futures).await.into_iter().flatten().collect()
     }
+
+    async fn put_role_icon(&self, _role_id: RoleId) -> Result<PresignedUrl, CoreError> {
+        Ok(PresignedUrl::new("https://example.com".to_string()))
+    }
+
+    async fn get_role_icon(&self, _role_id: RoleId) -> Result<PresignedUrl, CoreError> {
+        Ok(PresignedUrl::new("https://example.com".to_string()))
+    }
 }
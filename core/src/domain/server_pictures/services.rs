@@ -11,15 +11,17 @@ use crate::{
         outbox::ports::OutboxRepository,
         role::ports::RoleRepository,
         server::{entities::ServerId, ports::ServerRepository},
+        server_ban::ports::ServerBanRepository,
         server_invitation::ports::ServerInvitationRepository,
+        server_join_request::ports::ServerJoinRequestRepository,
         server_member::MemberRepository,
         server_pictures::{PresignedUrl, ServerPictureUrls, ServerPicturesMap, ServerPicturesRepository, ServerPicturesService},
         user::port::UserRepository,
     },
 };
 
-impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC> ServerPicturesService
-    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC>
+impl<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR> ServerPicturesService
+    for Service<S, F, U, H, M, C, R, O, CM, MR, SI, A, SC, SB, JR>
 where
     S: ServerRepository,
     F: FriendshipRepository,
@@ -34,6 +36,8 @@ where
     SI: ServerInvitationRepository,
     A: AuthorizationRepository,
     SC: ServerPicturesRepository,
+    SB: ServerBanRepository,
+    JR: ServerJoinRequestRepository,
 {
     async fn put_server_banner(&self, server_id: ServerId) -> Result<PresignedUrl, CoreError> {
         self.server_pictures_repository.put_banner(server_id).await
@@ -4,12 +4,19 @@ mod tests {
 
     use crate::domain::channel::entities::{
         ChannelName, ChannelType, CreatePrivateChannelInput, CreateServerChannelInput,
-        UpdateChannelInput,
+        OverwriteTarget, PermissionOverwriteRequest, UpdateChannelInput,
     };
     use crate::domain::channel::ports::ChannelService;
     use crate::domain::common::CoreError;
-    use crate::domain::server::entities::ServerId;
-    use crate::domain::test::create_mock_service;
+    use crate::domain::friend::entities::UserId;
+    use crate::domain::role::{
+        entities::{CreateRoleInput, Permissions, RoleAppearance},
+        ports::RoleRepository,
+    };
+    use crate::domain::server::entities::{InsertServerInput, Server, ServerId, ServerVisibility};
+    use crate::domain::server::ports::ServerRepository;
+    use crate::domain::server_member::{CreateMemberInput, MemberId, MemberRepository};
+    use crate::domain::test::{MockService, create_mock_service};
 
     #[tokio::test]
     async fn test_create_private_channel_success() -> Result<(), Box<dyn std::error::Error>> {
@@ -694,4 +701,195 @@ mod tests {
 
         Ok(())
     }
+
+    // == Permission Overwrite Tests ==
+
+    async fn insert_server(service: &MockService) -> Server {
+        service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id: UserId(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("Could not create server")
+    }
+
+    #[tokio::test]
+    async fn test_upsert_role_overwrite_replaces_previous_one()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let server = insert_server(&service).await;
+        let channel = service
+            .create_server_channel(CreateServerChannelInput {
+                name: ChannelName::new("staff".to_string()),
+                server_id: server.id,
+                parent_id: None,
+                channel_type: ChannelType::ServerText,
+            })
+            .await?;
+        let role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *server.id,
+                name: "Staff".to_string(),
+                permissions: Permissions(0x40),
+                appearance: RoleAppearance::default(),
+            })
+            .await?;
+
+        let target = OverwriteTarget::Role(role.id);
+        for deny in [0x80, 0x40] {
+            service
+                .upsert_permission_overwrite(
+                    channel.id,
+                    target,
+                    PermissionOverwriteRequest { allow: 0, deny },
+                    server.owner_id,
+                )
+                .await?;
+        }
+
+        let overwrites = service.list_permission_overwrites(channel.id).await?;
+        assert_eq!(overwrites.len(), 1);
+        assert_eq!(overwrites[0].target, target);
+        assert_eq!(overwrites[0].deny.0, 0x40);
+
+        service
+            .delete_permission_overwrite(channel.id, target, server.owner_id)
+            .await?;
+        assert!(
+            service
+                .list_permission_overwrites(channel.id)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_upsert_overwrite_rejects_member_of_another_server()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let server = insert_server(&service).await;
+        let other_server = insert_server(&service).await;
+        let channel = service
+            .create_server_channel(CreateServerChannelInput {
+                name: ChannelName::new("general".to_string()),
+                server_id: server.id,
+                parent_id: None,
+                channel_type: ChannelType::ServerText,
+            })
+            .await?;
+        let outsider = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: other_server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await?;
+
+        let result = service
+            .upsert_permission_overwrite(
+                channel.id,
+                OverwriteTarget::Member(outsider.id),
+                PermissionOverwriteRequest {
+                    allow: 0x80,
+                    deny: 0,
+                },
+                server.owner_id,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(CoreError::InvalidPermissionOverwrite { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_member_overwrite_requires_a_higher_role() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let server = insert_server(&service).await;
+        let channel = service
+            .create_server_channel(CreateServerChannelInput {
+                name: ChannelName::new("general".to_string()),
+                server_id: server.id,
+                parent_id: None,
+                channel_type: ChannelType::ServerText,
+            })
+            .await?;
+        let moderator_id = UserId(Uuid::new_v4());
+        let mut members = Vec::new();
+        for user_id in [moderator_id, UserId(Uuid::new_v4())] {
+            members.push(
+                service
+                    .member_repository
+                    .insert(CreateMemberInput {
+                        server_id: server.id,
+                        user_id,
+                        nickname: None,
+                    })
+                    .await?,
+            );
+        }
+
+        // The moderator holds no role above the other member
+        let target = OverwriteTarget::Member(members[1].id);
+        let result = service
+            .upsert_permission_overwrite(
+                channel.id,
+                target,
+                PermissionOverwriteRequest {
+                    allow: 0,
+                    deny: 0x80,
+                },
+                moderator_id,
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+        let result = service
+            .delete_permission_overwrite(channel.id, target, moderator_id)
+            .await;
+        assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_private_channels_have_no_overwrites() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let channel = service
+            .create_private_channel(CreatePrivateChannelInput {
+                name: ChannelName::new("Direct Message".to_string()),
+            })
+            .await?;
+
+        let result = service
+            .upsert_permission_overwrite(
+                channel.id,
+                OverwriteTarget::Member(MemberId(Uuid::new_v4())),
+                PermissionOverwriteRequest {
+                    allow: 0x80,
+                    deny: 0,
+                },
+                UserId(Uuid::new_v4()),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(CoreError::InvalidPermissionOverwrite { .. })
+        ));
+
+        Ok(())
+    }
 }
@@ -1,12 +1,17 @@
 #[cfg(test)]
 mod tests {
+    use chrono::{Duration, Utc};
     use uuid::Uuid;
 
     use crate::domain::{
+        common::CoreError,
         friend::entities::UserId,
-        member_role::ports::MemberRoleService,
+        member_role::{
+            entities::{AssignMemberRole, AssignRoleRequest, SetMemberRolesRequest},
+            ports::{MemberRoleRepository, MemberRoleService},
+        },
         role::{
-            entities::{CreateRoleInput, Permissions},
+            entities::{CreateRoleInput, Permissions, RoleAppearance},
             ports::RoleRepository,
         },
         server::{
@@ -39,6 +44,7 @@ mod tests {
             server_id: *server.id,
             name: "test".to_string(),
             permissions: Permissions(0x1),
+            appearance: RoleAppearance::default(),
         };
 
         let role = service
@@ -58,7 +64,12 @@ mod tests {
             .await
             .expect("Could not create server member");
         let member_role = service
-            .assign_member_to_role(role.id, server_member.id)
+            .assign_member_to_role(
+                role.id,
+                server_member.id,
+                AssignRoleRequest::default(),
+                server.owner_id,
+            )
             .await
             .expect("Could not create member");
 
@@ -88,6 +99,7 @@ mod tests {
             server_id: *server.id,
             name: "test".to_string(),
             permissions: Permissions(0x1),
+            appearance: RoleAppearance::default(),
         };
 
         let role = service
@@ -108,12 +120,372 @@ mod tests {
             .expect("Could not create server member");
 
         let member_role = service
-            .assign_member_to_role(role.id, server_member.id)
+            .assign_member_to_role(
+                role.id,
+                server_member.id,
+                AssignRoleRequest::default(),
+                server.owner_id,
+            )
             .await
             .expect("Could not create member");
         service
-            .unassign_member_from_role(member_role.role_id, member_role.member_id)
+            .unassign_member_from_role(member_role.role_id, member_role.member_id, server.owner_id)
             .await
             .expect("Member role should be deleted");
     }
+
+    #[tokio::test]
+    async fn test_assign_member_role_above_actor_is_rejected() {
+        let service = create_mock_service();
+        let input = InsertServerInput {
+            name: "Test Server".to_string(),
+            owner_id: UserId::from(Uuid::new_v4()),
+            picture_url: None,
+            banner_url: None,
+            description: None,
+            visibility: ServerVisibility::Public,
+        };
+
+        let server = service
+            .server_repository
+            .insert(input)
+            .await
+            .expect("create_server returned an error");
+
+        let role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *server.id,
+                name: "Admin".to_string(),
+                permissions: Permissions(0x1),
+                appearance: RoleAppearance::default(),
+            })
+            .await
+            .expect("Could not create role");
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+
+        // The member holds no role ranking above the one they try to hand out
+        let result = service
+            .assign_member_to_role(
+                role.id,
+                server_member.id,
+                AssignRoleRequest::default(),
+                server_member.user_id,
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+    }
+
+    #[tokio::test]
+    async fn test_set_member_roles_assigns_missing_roles() {
+        let service = create_mock_service();
+        let server = service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("create_server returned an error");
+        let role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *server.id,
+                name: "Moderator".to_string(),
+                permissions: Permissions(0x1),
+                appearance: RoleAppearance::default(),
+            })
+            .await
+            .expect("Could not create role");
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+
+        let change = service
+            .set_member_roles(
+                server.id,
+                server_member.user_id,
+                SetMemberRolesRequest {
+                    role_ids: vec![role.id],
+                },
+                server.owner_id,
+            )
+            .await
+            .expect("Could not set member roles");
+        assert_eq!(change.assigned, vec![role.id]);
+        assert!(change.removed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_member_roles_rejects_role_of_another_server() {
+        let service = create_mock_service();
+        let mut servers = Vec::new();
+        for name in ["First Server", "Second Server"] {
+            let server = service
+                .server_repository
+                .insert(InsertServerInput {
+                    name: name.to_string(),
+                    owner_id: UserId::from(Uuid::new_v4()),
+                    picture_url: None,
+                    banner_url: None,
+                    description: None,
+                    visibility: ServerVisibility::Public,
+                })
+                .await
+                .expect("create_server returned an error");
+            servers.push(server);
+        }
+        let foreign_role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *servers[1].id,
+                name: "Foreign".to_string(),
+                permissions: Permissions(0x1),
+                appearance: RoleAppearance::default(),
+            })
+            .await
+            .expect("Could not create role");
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: servers[0].id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+
+        let result = service
+            .set_member_roles(
+                servers[0].id,
+                server_member.user_id,
+                SetMemberRolesRequest {
+                    role_ids: vec![foreign_role.id],
+                },
+                servers[0].owner_id,
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::RoleNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_set_member_roles_above_actor_is_rejected() {
+        let service = create_mock_service();
+        let server = service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("create_server returned an error");
+        let role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *server.id,
+                name: "Admin".to_string(),
+                permissions: Permissions(0x1),
+                appearance: RoleAppearance::default(),
+            })
+            .await
+            .expect("Could not create role");
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+
+        // The member tries to hand themselves a role ranking above every role they hold
+        let result = service
+            .set_member_roles(
+                server.id,
+                server_member.user_id,
+                SetMemberRolesRequest {
+                    role_ids: vec![role.id],
+                },
+                server_member.user_id,
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+    }
+
+    #[tokio::test]
+    async fn test_assign_member_role_with_past_expiry_is_rejected() {
+        let service = create_mock_service();
+        let server = service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("create_server returned an error");
+        let role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *server.id,
+                name: "Event Speaker".to_string(),
+                permissions: Permissions(0x1),
+                appearance: RoleAppearance::default(),
+            })
+            .await
+            .expect("Could not create role");
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+
+        let result = service
+            .assign_member_to_role(
+                role.id,
+                server_member.id,
+                AssignRoleRequest {
+                    expires_at: Some(Utc::now() - Duration::hours(1)),
+                },
+                server.owner_id,
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::InvalidRoleExpiry { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_unassign_expired_roles_only_removes_expired_assignments() {
+        let service = create_mock_service();
+        let server = service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("create_server returned an error");
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+        for (name, expires_at) in [
+            ("Trial Moderator", Some(Utc::now() - Duration::minutes(1))),
+            ("Event Speaker", Some(Utc::now() + Duration::hours(24))),
+            ("Member", None),
+        ] {
+            let role = service
+                .role_repository
+                .create(CreateRoleInput {
+                    server_id: *server.id,
+                    name: name.to_string(),
+                    permissions: Permissions(0x1),
+                    appearance: RoleAppearance::default(),
+                })
+                .await
+                .expect("Could not create role");
+            service
+                .member_role_repository
+                .assign(AssignMemberRole {
+                    member_id: server_member.id,
+                    role_id: role.id,
+                    expires_at,
+                })
+                .await
+                .expect("Could not assign role");
+        }
+
+        let unassigned = service
+            .unassign_expired_roles()
+            .await
+            .expect("Could not unassign expired roles");
+        assert_eq!(unassigned, 1);
+
+        let unassigned = service
+            .unassign_expired_roles()
+            .await
+            .expect("Could not unassign expired roles");
+        assert_eq!(unassigned, 0);
+    }
+
+    #[tokio::test]
+    async fn test_default_role_cannot_be_assigned_or_unassigned() {
+        let service = create_mock_service();
+        let owner_id = UserId::from(Uuid::new_v4());
+        let server = service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id,
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("create_server returned an error");
+        let default_role = service.role_repository.insert_default_role(server.id);
+        let server_member = service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: None,
+            })
+            .await
+            .expect("Could not create server member");
+
+        let assigned = service
+            .assign_member_to_role(
+                default_role.id,
+                server_member.id,
+                AssignRoleRequest::default(),
+                owner_id,
+            )
+            .await;
+        assert!(matches!(assigned, Err(CoreError::DefaultRoleAssignment)));
+
+        let unassigned = service
+            .unassign_member_from_role(default_role.id, server_member.id, owner_id)
+            .await;
+        assert!(matches!(unassigned, Err(CoreError::DefaultRoleAssignment)));
+    }
 }
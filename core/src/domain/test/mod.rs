@@ -10,7 +10,9 @@ use crate::{
         outbox::ports::MockOutboxRepository,
         role::ports::MockRoleRepository,
         server::ports::MockServerRepository,
+        server_ban::ports::MockServerBanRepository,
         server_invitation::ports::MockServerInvitationRepository,
+        server_join_request::ports::MockServerJoinRequestRepository,
         server_member::MockMemberRepository,
         server_pictures::{self, MockServerPicturesRepository},
         user::port::MockUserRepository,
@@ -22,6 +24,9 @@ pub mod friend;
 pub mod member_role;
 pub mod role;
 pub mod server;
+pub mod server_ban;
+pub mod server_invitation;
+pub mod server_join_request;
 pub mod server_member;
 
 pub type MockService = Service<
@@ -38,6 +43,8 @@ pub type MockService = Service<
     MockServerInvitationRepository,
     MockAuthorizationRepository,
     MockServerPicturesRepository,
+    MockServerBanRepository,
+    MockServerJoinRequestRepository,
 >;
 
 pub fn create_mock_service() -> MockService {
@@ -45,15 +52,21 @@ pub fn create_mock_service() -> MockService {
     let friendship_repository = MockFriendshipRepository::new();
     let user_repository = MockUserRepository::new();
     let health_repository = MockHealthRepository::new();
-    let member_repository = MockMemberRepository::new();
-    let server_repository = MockServerRepository::new();
+    let member_role_repository = MockMemberRoleRepository::new();
+    let member_repository =
+        MockMemberRepository::with_member_role_repository(member_role_repository.clone());
     let role_repository = MockRoleRepository::new();
+    let server_repository = MockServerRepository::with_repositories(
+        role_repository.clone(),
+        channel_repository.clone(),
+    );
     let outbox_repository = MockOutboxRepository::new();
     let channel_member_repository = MockChannelMemberRepository::new();
-    let member_role_repository = MockMemberRoleRepository::new();
     let server_invitation_repository = MockServerInvitationRepository::new();
     let authorization_repository = MockAuthorizationRepository::new();
     let server_pictures_repository = MockServerPicturesRepository::new();
+    let server_ban_repository = MockServerBanRepository::new();
+    let server_join_request_repository = MockServerJoinRequestRepository::new();
     MockService::new(
         server_repository,
         friendship_repository,
@@ -68,5 +81,7 @@ pub fn create_mock_service() -> MockService {
         server_invitation_repository,
         authorization_repository,
         server_pictures_repository,
+        server_ban_repository,
+        server_join_request_repository,
     )
 }
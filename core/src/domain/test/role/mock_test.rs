@@ -3,28 +3,57 @@
 #[cfg(test)]
 mod tests {
     use crate::domain::{
-        common::GetPaginated,
+        common::{CoreError, GetPaginated},
+        friend::entities::UserId,
+        member_role::{entities::AssignMemberRole, ports::MemberRoleRepository},
         role::{
-            entities::{CreateRoleInput, Permissions, RoleId, UpdateRoleInput},
+            entities::{
+                ALL_PERMISSIONS, CreateRoleInput, Permissions, ReorderRolesRequest, RoleAppearance,
+                RoleId, UpdateRoleAppearance, UpdateRoleInput,
+            },
             ports::{RoleRepository, RoleService},
         },
-        test::create_mock_service,
+        server::{
+            entities::{InsertServerInput, ServerId, ServerVisibility},
+            ports::ServerRepository,
+        },
+        server_member::{CreateMemberInput, MemberRepository},
+        test::{MockService, create_mock_service},
     };
     use uuid::Uuid;
 
+    /// Roles are managed by the owner of their server unless stated otherwise
+    async fn insert_server(service: &MockService) -> (Uuid, UserId) {
+        let owner_id = UserId::from(Uuid::new_v4());
+        let server = service
+            .server_repository
+            .insert(InsertServerInput {
+                name: "Test Server".to_string(),
+                owner_id,
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await
+            .expect("Could not create server");
+        (*server.id, owner_id)
+    }
+
     #[tokio::test]
     async fn test_create_role_success() -> Result<(), Box<dyn std::error::Error>> {
         let service = create_mock_service();
 
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Admin".to_string(),
-            permissions: Permissions::try_from(0x1).unwrap(), // Administrator permission
+            permissions: Permissions::try_from(0x1).unwrap(), // Administrator permission,
+            appearance: RoleAppearance::default(),
         };
 
         let role = service
-            .create_role(input)
+            .create_role(input, owner_id)
             .await
             .expect("create_role returned an error");
 
@@ -39,15 +68,16 @@ mod tests {
     {
         let service = create_mock_service();
 
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Moderator".to_string(),
-            permissions: Permissions::try_from(0x4 | 0x10 | 0x400).unwrap(), // ManageRoles | ManageChannels | ManageMessages
+            permissions: Permissions::try_from(0x4 | 0x10 | 0x400).unwrap(), // ManageRoles | ManageChannels | ManageMessages,
+            appearance: RoleAppearance::default(),
         };
 
         let role = service
-            .create_role(input)
+            .create_role(input, owner_id)
             .await
             .expect("create_role returned an error");
 
@@ -61,15 +91,16 @@ mod tests {
     async fn test_create_role_with_zero_permissions() -> Result<(), Box<dyn std::error::Error>> {
         let service = create_mock_service();
 
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Guest".to_string(),
-            permissions: Permissions::try_from(0x0).unwrap(), // No permissions
+            permissions: Permissions::try_from(0x0).unwrap(), // No permissions,
+            appearance: RoleAppearance::default(),
         };
 
         let role = service
-            .create_role(input)
+            .create_role(input, owner_id)
             .await
             .expect("create_role returned an error");
 
@@ -83,13 +114,14 @@ mod tests {
         let service = create_mock_service();
 
         // Insert a role using repository
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Test Role".to_string(),
-            permissions: Permissions::try_from(0x2).unwrap(), // ManageServer
+            permissions: Permissions::try_from(0x2).unwrap(), // ManageServer,
+            appearance: RoleAppearance::default(),
         };
-        let created_role = service.create_role(input).await?;
+        let created_role = service.create_role(input, owner_id).await?;
 
         // Get the role
         let role = service
@@ -132,7 +164,7 @@ mod tests {
     async fn test_list_roles_by_server_success() -> Result<(), Box<dyn std::error::Error>> {
         let service = create_mock_service();
 
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
 
         // Insert multiple roles for the same server
         for i in 1..=3 {
@@ -140,8 +172,9 @@ mod tests {
                 server_id,
                 name: format!("Test Role {}", i),
                 permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
             };
-            service.create_role(input).await?;
+            service.create_role(input, owner_id).await?;
         }
 
         let (roles, total) = service
@@ -155,13 +188,74 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_list_roles_by_server_counts_members() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        let held = service
+            .create_role(
+                CreateRoleInput {
+                    server_id,
+                    name: "Held".to_string(),
+                    permissions: Permissions(0x40),
+                    appearance: RoleAppearance::default(),
+                },
+                owner_id,
+            )
+            .await?;
+        let unheld = service
+            .create_role(
+                CreateRoleInput {
+                    server_id,
+                    name: "Unheld".to_string(),
+                    permissions: Permissions(0x40),
+                    appearance: RoleAppearance::default(),
+                },
+                owner_id,
+            )
+            .await?;
+        for _ in 0..2 {
+            let member = service
+                .member_repository
+                .insert(CreateMemberInput {
+                    server_id: ServerId(server_id),
+                    user_id: UserId::from(Uuid::new_v4()),
+                    nickname: None,
+                })
+                .await?;
+            service
+                .member_role_repository
+                .assign(AssignMemberRole {
+                    member_id: member.id,
+                    role_id: held.id,
+                    expires_at: None,
+                })
+                .await?;
+        }
+
+        let (roles, _) = service
+            .list_roles_by_server(&GetPaginated::default(), server_id)
+            .await?;
+        let count_of = |role_id: RoleId| {
+            roles
+                .iter()
+                .find(|role| role.id == role_id)
+                .and_then(|role| role.member_count)
+        };
+        assert_eq!(count_of(held.id), Some(2));
+        assert_eq!(count_of(unheld.id), Some(0));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_list_roles_by_server_multiple_servers() -> Result<(), Box<dyn std::error::Error>>
     {
         let service = create_mock_service();
 
-        let server_id_1 = Uuid::new_v4();
-        let server_id_2 = Uuid::new_v4();
+        let (server_id_1, owner_id_1) = insert_server(&service).await;
+        let (server_id_2, owner_id_2) = insert_server(&service).await;
 
         // Insert roles for server 1
         for i in 1..=3 {
@@ -169,8 +263,9 @@ mod tests {
                 server_id: server_id_1,
                 name: format!("Server1 Role {}", i),
                 permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
             };
-            service.create_role(input).await?;
+            service.create_role(input, owner_id_1).await?;
         }
 
         // Insert roles for server 2
@@ -179,8 +274,9 @@ mod tests {
                 server_id: server_id_2,
                 name: format!("Server2 Role {}", i),
                 permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
             };
-            service.create_role(input).await?;
+            service.create_role(input, owner_id_2).await?;
         }
 
         // List roles for server 1
@@ -208,7 +304,7 @@ mod tests {
     async fn test_list_roles_by_server_with_pagination() -> Result<(), Box<dyn std::error::Error>> {
         let service = create_mock_service();
 
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
 
         // Insert 25 roles
         for i in 1..=25 {
@@ -216,8 +312,9 @@ mod tests {
                 server_id,
                 name: format!("Test Role {}", i),
                 permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
             };
-            service.create_role(input).await?;
+            service.create_role(input, owner_id).await?;
         }
 
         // Test page 1
@@ -277,23 +374,25 @@ mod tests {
         let service = create_mock_service();
 
         // Insert a role
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Original Role".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let created_role = service.create_role(input).await?;
+        let created_role = service.create_role(input, owner_id).await?;
 
         // Update the role
         let update_input = UpdateRoleInput {
             id: created_role.id.clone(),
             name: Some("Updated Role".to_string()),
             permissions: Some(0x2), // ManageServer
+            appearance: UpdateRoleAppearance::default(),
         };
 
         let updated_role = service
-            .update_role(update_input)
+            .update_role(update_input, owner_id)
             .await
             .expect("update_role returned an error");
 
@@ -311,23 +410,25 @@ mod tests {
         let service = create_mock_service();
 
         // Insert a role
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Original Role".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let created_role = service.create_role(input).await?;
+        let created_role = service.create_role(input, owner_id).await?;
 
         // Update only the name
         let update_input = UpdateRoleInput {
             id: created_role.id.clone(),
             name: Some("Updated Name Only".to_string()),
             permissions: None,
+            appearance: UpdateRoleAppearance::default(),
         };
 
         let updated_role = service
-            .update_role(update_input)
+            .update_role(update_input, owner_id)
             .await
             .expect("update_role returned an error");
 
@@ -345,23 +446,25 @@ mod tests {
         let service = create_mock_service();
 
         // Insert a role
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Original Role".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let created_role = service.create_role(input).await?;
+        let created_role = service.create_role(input, owner_id).await?;
 
         // Update only the permissions
         let update_input = UpdateRoleInput {
             id: created_role.id.clone(),
             name: None,
             permissions: Some(0x4 | 0x10), // ManageRoles | ManageChannels
+            appearance: UpdateRoleAppearance::default(),
         };
 
         let updated_role = service
-            .update_role(update_input)
+            .update_role(update_input, owner_id)
             .await
             .expect("update_role returned an error");
 
@@ -381,9 +484,12 @@ mod tests {
             id: RoleId::from(Uuid::new_v4()),
             name: Some("Updated Role".to_string()),
             permissions: None,
+            appearance: UpdateRoleAppearance::default(),
         };
 
-        let result = service.update_role(update_input).await;
+        let result = service
+            .update_role(update_input, UserId(Uuid::new_v4()))
+            .await;
 
         assert!(result.is_err(), "update_role should have returned an error");
 
@@ -405,22 +511,24 @@ mod tests {
         let service = create_mock_service();
 
         // Insert a role
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Original Role".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let created_role = service.create_role(input).await?;
+        let created_role = service.create_role(input, owner_id).await?;
 
         // Try to update with invalid permissions
         let update_input = UpdateRoleInput {
             id: created_role.id.clone(),
             name: None,
-            permissions: Some(0x1000), // Invalid permission bit
+            permissions: Some(1 << 40), // Invalid permission bit
+            appearance: UpdateRoleAppearance::default(),
         };
 
-        let result = service.update_role(update_input).await;
+        let result = service.update_role(update_input, owner_id).await;
 
         assert!(result.is_err(), "update_role should have returned an error");
 
@@ -446,17 +554,18 @@ mod tests {
         let service = create_mock_service();
 
         // Insert a role
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
         let input = CreateRoleInput {
             server_id,
             name: "Test Role".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let created_role = service.create_role(input).await?;
+        let created_role = service.create_role(input, owner_id).await?;
 
         // Delete the role
         service
-            .delete_role(&created_role.id)
+            .delete_role(&created_role.id, owner_id)
             .await
             .expect("delete_role returned an error");
 
@@ -482,7 +591,9 @@ mod tests {
         let service = create_mock_service();
 
         let non_existent_id = RoleId::from(Uuid::new_v4());
-        let result = service.delete_role(&non_existent_id).await;
+        let result = service
+            .delete_role(&non_existent_id, UserId(Uuid::new_v4()))
+            .await;
 
         assert!(result.is_err(), "delete_role should have returned an error");
 
@@ -504,26 +615,28 @@ mod tests {
     {
         let service = create_mock_service();
 
-        let server_id = Uuid::new_v4();
+        let (server_id, owner_id) = insert_server(&service).await;
 
         // Insert multiple roles
         let input1 = CreateRoleInput {
             server_id,
             name: "Role 1".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let role1 = service.create_role(input1).await?;
+        let role1 = service.create_role(input1, owner_id).await?;
 
         let input2 = CreateRoleInput {
             server_id,
             name: "Role 2".to_string(),
             permissions: Permissions::try_from(0x2).unwrap(),
+            appearance: RoleAppearance::default(),
         };
-        let role2 = service.create_role(input2).await?;
+        let role2 = service.create_role(input2, owner_id).await?;
 
         // Delete role1
         service
-            .delete_role(&role1.id)
+            .delete_role(&role1.id, owner_id)
             .await
             .expect("delete_role returned an error");
 
@@ -541,4 +654,322 @@ mod tests {
 
         Ok(())
     }
+
+    // == Hierarchy Tests ==
+
+    #[tokio::test]
+    async fn test_create_role_without_higher_role_is_rejected()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, _) = insert_server(&service).await;
+
+        let input = CreateRoleInput {
+            server_id,
+            name: "Admin".to_string(),
+            permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
+        };
+
+        // A member holding no role above the default one manages no role
+        let result = service.create_role(input, UserId(Uuid::new_v4())).await;
+        assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_new_roles_enter_at_the_bottom() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        for name in ["First", "Second"] {
+            let input = CreateRoleInput {
+                server_id,
+                name: name.to_string(),
+                permissions: Permissions::try_from(0x40).unwrap(),
+                appearance: RoleAppearance::default(),
+            };
+            service.create_role(input, owner_id).await?;
+        }
+
+        let (roles, _) = service
+            .list_roles_by_server(&GetPaginated::default(), server_id)
+            .await?;
+        let positions: Vec<(&str, i32)> = roles
+            .iter()
+            .map(|role| (role.name.as_str(), role.position))
+            .collect();
+        assert_eq!(positions, vec![("First", 2), ("Second", 1)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reorder_roles_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        let mut created = Vec::new();
+        for name in ["First", "Second", "Third"] {
+            let input = CreateRoleInput {
+                server_id,
+                name: name.to_string(),
+                permissions: Permissions::try_from(0x40).unwrap(),
+                appearance: RoleAppearance::default(),
+            };
+            created.push(service.create_role(input, owner_id).await?);
+        }
+
+        let request = ReorderRolesRequest {
+            role_ids: vec![created[0].id, created[1].id, created[2].id],
+        };
+        let roles = service
+            .reorder_roles(ServerId(server_id), request, owner_id)
+            .await?;
+
+        let names: Vec<&str> = roles.iter().map(|role| role.name.as_str()).collect();
+        assert_eq!(names, vec!["Third", "Second", "First"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reorder_roles_rejects_incomplete_order() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        let mut created = Vec::new();
+        for name in ["First", "Second"] {
+            let input = CreateRoleInput {
+                server_id,
+                name: name.to_string(),
+                permissions: Permissions::try_from(0x40).unwrap(),
+                appearance: RoleAppearance::default(),
+            };
+            created.push(service.create_role(input, owner_id).await?);
+        }
+
+        let request = ReorderRolesRequest {
+            role_ids: vec![created[0].id, created[0].id],
+        };
+        let result = service
+            .reorder_roles(ServerId(server_id), request, owner_id)
+            .await;
+        assert!(matches!(result, Err(CoreError::InvalidRoleOrder { .. })));
+
+        Ok(())
+    }
+
+    // == Appearance Tests ==
+
+    #[tokio::test]
+    async fn test_create_role_with_appearance() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        let input = CreateRoleInput {
+            server_id,
+            name: "Moderator".to_string(),
+            permissions: Permissions::try_from(0x40).unwrap(),
+            appearance: RoleAppearance {
+                color: 0x2ECC71,
+                hoist: true,
+                mentionable: true,
+                has_icon: true,
+            },
+        };
+        let role = service.create_role(input, owner_id).await?;
+
+        assert_eq!(role.appearance.color, 0x2ECC71);
+        assert!(role.appearance.hoist);
+        assert!(role.appearance.mentionable);
+        assert!(role.icon_url.is_some(), "Expected an icon upload URL");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_role_rejects_invalid_color() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        for color in [-1, 0x1000000] {
+            let input = CreateRoleInput {
+                server_id,
+                name: "Colorful".to_string(),
+                permissions: Permissions::try_from(0x40).unwrap(),
+                appearance: RoleAppearance {
+                    color,
+                    ..Default::default()
+                },
+            };
+            let result = service.create_role(input, owner_id).await;
+            assert!(matches!(result, Err(CoreError::InvalidRoleColor { .. })));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_role_appearance_keeps_other_fields()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        let input = CreateRoleInput {
+            server_id,
+            name: "Helper".to_string(),
+            permissions: Permissions::try_from(0x40).unwrap(),
+            appearance: RoleAppearance {
+                color: 0xE67E22,
+                hoist: true,
+                ..Default::default()
+            },
+        };
+        let role = service.create_role(input, owner_id).await?;
+        assert!(role.icon_url.is_none());
+
+        let update_input = UpdateRoleInput {
+            id: role.id,
+            name: None,
+            permissions: None,
+            appearance: UpdateRoleAppearance {
+                mentionable: Some(true),
+                ..Default::default()
+            },
+        };
+        let updated = service.update_role(update_input, owner_id).await?;
+
+        assert_eq!(updated.appearance.color, 0xE67E22);
+        assert!(updated.appearance.hoist);
+        assert!(updated.appearance.mentionable);
+
+        Ok(())
+    }
+
+    // == Default Role Tests ==
+
+    #[tokio::test]
+    async fn test_delete_default_role_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+        let default_role = service
+            .role_repository
+            .insert_default_role(ServerId(server_id));
+
+        let result = service.delete_role(&default_role.id, owner_id).await;
+        assert!(matches!(result, Err(CoreError::DefaultRoleDeletion)));
+        assert!(
+            service
+                .role_repository
+                .find_by_id(&default_role.id)
+                .await
+                .is_ok()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_default_role_only_edits_permissions()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+        let default_role = service
+            .role_repository
+            .insert_default_role(ServerId(server_id));
+
+        let renamed = service
+            .update_role(
+                UpdateRoleInput {
+                    id: default_role.id,
+                    name: Some("Everyone".to_string()),
+                    permissions: None,
+                    appearance: UpdateRoleAppearance::default(),
+                },
+                owner_id,
+            )
+            .await;
+        assert!(matches!(renamed, Err(CoreError::DefaultRoleUpdate)));
+
+        let restyled = service
+            .update_role(
+                UpdateRoleInput {
+                    id: default_role.id,
+                    name: None,
+                    permissions: None,
+                    appearance: UpdateRoleAppearance {
+                        hoist: Some(true),
+                        ..Default::default()
+                    },
+                },
+                owner_id,
+            )
+            .await;
+        assert!(matches!(restyled, Err(CoreError::DefaultRoleUpdate)));
+
+        let updated = service
+            .update_role(
+                UpdateRoleInput {
+                    id: default_role.id,
+                    name: None,
+                    permissions: Some(0x1),
+                    appearance: UpdateRoleAppearance::default(),
+                },
+                owner_id,
+            )
+            .await?;
+        assert_eq!(*updated.permissions, 0x1);
+        assert_eq!(updated.name, default_role.name);
+        assert!(updated.is_default);
+
+        Ok(())
+    }
+
+    // == Effective Permissions Tests ==
+
+    #[tokio::test]
+    async fn test_effective_permissions_owner_holds_everything()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, owner_id) = insert_server(&service).await;
+
+        let permissions = service
+            .get_effective_permissions(ServerId(server_id), owner_id)
+            .await?;
+        assert!(permissions.is_owner);
+        assert_eq!(permissions.permissions.0, ALL_PERMISSIONS);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_effective_permissions_require_membership()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let (server_id, _) = insert_server(&service).await;
+        let user_id = UserId::from(Uuid::new_v4());
+
+        let result = service
+            .get_effective_permissions(ServerId(server_id), user_id)
+            .await;
+        assert!(matches!(result, Err(CoreError::MemberNotFound { .. })));
+
+        service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: ServerId(server_id),
+                user_id,
+                nickname: None,
+            })
+            .await?;
+        let permissions = service
+            .get_effective_permissions(ServerId(server_id), user_id)
+            .await?;
+        assert!(!permissions.is_owner);
+        assert!(permissions.names.is_empty());
+
+        Ok(())
+    }
 }
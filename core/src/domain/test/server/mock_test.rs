@@ -3,10 +3,22 @@
 #[cfg(test)]
 mod tests {
     use crate::domain::{
+        channel::{
+            entities::{ChannelType, CreateChannelRepoInput},
+            ports::ChannelRepository,
+        },
         common::{CoreError, GetPaginated},
         friend::entities::UserId,
+        role::{
+            entities::{CreateRoleInput, Permissions, RoleAppearance},
+            ports::RoleRepository,
+        },
         server::{
-            entities::{InsertServerInput, ServerId, ServerVisibility, UpdateServerInput},
+            entities::{
+                CreateServerFromTemplateRequest, CreateServerTemplateInput, InsertServerInput,
+                SERVER_EXPORT_VERSION, ServerId, ServerJoinMode, ServerTemplateId,
+                ServerVisibility, UpdateServerInput, UpdateServerSettingsRequest,
+            },
             ports::{ServerRepository, ServerService},
         },
         test::create_mock_service,
@@ -505,4 +517,326 @@ mod tests {
 
         Ok(())
     }
+
+    // == Server Template Tests ==
+
+    #[tokio::test]
+    async fn test_create_server_from_template_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let source = service
+            .create_server(InsertServerInput {
+                name: "Source Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: Some("Template source".to_string()),
+                visibility: ServerVisibility::Private,
+            })
+            .await?;
+
+        service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *source.id,
+                name: "Moderator".to_string(),
+                permissions: Permissions(0x4),
+                appearance: RoleAppearance::default(),
+            })
+            .await?;
+        let folder = service
+            .channel_repository
+            .create(CreateChannelRepoInput {
+                name: "General".to_string(),
+                server_id: Some(source.id),
+                parent_id: None,
+                channel_type: ChannelType::ServerFolder,
+            })
+            .await?;
+        service
+            .channel_repository
+            .create(CreateChannelRepoInput {
+                name: "chat".to_string(),
+                server_id: Some(source.id),
+                parent_id: Some(folder.id),
+                channel_type: ChannelType::ServerText,
+            })
+            .await?;
+
+        let template = service
+            .create_template(CreateServerTemplateInput {
+                server_id: source.id,
+                creator_id: source.owner_id,
+                name: "Community".to_string(),
+                description: None,
+            })
+            .await?;
+
+        assert_eq!(template.source_server_id, Some(source.id));
+        assert_eq!(template.snapshot.roles.len(), 1);
+        assert_eq!(template.snapshot.roles[0].name, "Moderator");
+        assert_eq!(template.snapshot.channels.len(), 2);
+        assert_eq!(template.snapshot.channels[0].parent_key, None);
+        assert_eq!(
+            template.snapshot.channels[1].parent_key,
+            Some(template.snapshot.channels[0].key)
+        );
+
+        let owner_id = UserId::from(Uuid::new_v4());
+        let server = service
+            .create_server_from_template(
+                &template.id,
+                owner_id,
+                CreateServerFromTemplateRequest {
+                    name: "From Template".to_string(),
+                    picture_url: None,
+                    banner_url: None,
+                    description: None,
+                    visibility: None,
+                },
+            )
+            .await?;
+
+        assert_eq!(server.name, "From Template");
+        assert_eq!(server.owner_id, owner_id);
+        // Settings fall back to the template ones
+        assert_eq!(server.visibility, ServerVisibility::Private);
+        assert_eq!(server.description, Some("Template source".to_string()));
+
+        // Roles and channels of the snapshot are instantiated in the new server
+        let roles = service.role_repository.list_in_server(*server.id).await?;
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].name, "Moderator");
+        assert_eq!(*roles[0].permissions, 0x4);
+        let channels = service.channel_repository.list_in_server(server.id).await?;
+        assert_eq!(channels.len(), 2);
+        let new_folder = channels
+            .iter()
+            .find(|channel| channel.name == "General")
+            .expect("folder should be instantiated");
+        let new_chat = channels
+            .iter()
+            .find(|channel| channel.name == "chat")
+            .expect("channel should be instantiated");
+        assert_ne!(new_folder.id, folder.id);
+        assert_eq!(new_chat.parent_id, Some(new_folder.id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_server_from_unknown_template() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let error = service
+            .create_server_from_template(
+                &ServerTemplateId::from(Uuid::new_v4()),
+                UserId::from(Uuid::new_v4()),
+                CreateServerFromTemplateRequest {
+                    name: "From Template".to_string(),
+                    picture_url: None,
+                    banner_url: None,
+                    description: None,
+                    visibility: None,
+                },
+            )
+            .await
+            .expect_err("create_server_from_template should have returned an error");
+
+        assert!(matches!(error, CoreError::ServerTemplateNotFound { .. }));
+
+        Ok(())
+    }
+
+    // == Export / Import Tests ==
+
+    #[tokio::test]
+    async fn test_export_then_import_server() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let source = service
+            .create_server(InsertServerInput {
+                name: "Exported Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: Some("Backup".to_string()),
+                visibility: ServerVisibility::Private,
+            })
+            .await?;
+
+        let bundle = service.export_server(&source.id).await?;
+        assert_eq!(bundle.version, SERVER_EXPORT_VERSION);
+        assert_eq!(bundle.server.id, source.id);
+
+        let imported = service.import_server(source.owner_id, bundle).await?;
+
+        assert_ne!(imported.id, source.id);
+        assert_eq!(imported.owner_id, source.owner_id);
+        assert_eq!(imported.name, "Exported Server");
+        assert_eq!(imported.visibility, ServerVisibility::Private);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_server_gives_the_copy_to_the_caller()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let source = service
+            .create_server(InsertServerInput {
+                name: "Exported Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await?;
+        let bundle = service.export_server(&source.id).await?;
+        let importer_id = UserId::from(Uuid::new_v4());
+
+        let imported = service.import_server(importer_id, bundle.clone()).await?;
+        assert_ne!(imported.id, source.id);
+        assert_eq!(imported.owner_id, importer_id);
+
+        // A bundle whose exported server no longer exists is imported all the same
+        let mut orphan = bundle;
+        orphan.server.id = ServerId::from(Uuid::new_v4());
+        let imported = service.import_server(importer_id, orphan).await?;
+        assert_eq!(imported.owner_id, importer_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_import_server_rejects_unsupported_version()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+
+        let source = service
+            .create_server(InsertServerInput {
+                name: "Exported Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await?;
+
+        let mut bundle = service.export_server(&source.id).await?;
+        bundle.version = SERVER_EXPORT_VERSION + 1;
+
+        let error = service
+            .import_server(source.owner_id, bundle)
+            .await
+            .expect_err("import_server should have returned an error");
+
+        assert!(matches!(error, CoreError::InvalidServerExport { .. }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_server_settings_rejects_foreign_channel()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let input = InsertServerInput {
+            name: "Settings Server".to_string(),
+            owner_id: UserId::from(Uuid::new_v4()),
+            picture_url: None,
+            banner_url: None,
+            description: None,
+            visibility: ServerVisibility::Public,
+        };
+        let server = service.create_server(input.clone()).await?;
+        let other = service.create_server(input).await?;
+
+        let foreign_channel = service
+            .channel_repository
+            .create(CreateChannelRepoInput {
+                name: "elsewhere".to_string(),
+                server_id: Some(other.id),
+                parent_id: None,
+                channel_type: ChannelType::ServerText,
+            })
+            .await?;
+
+        let error = service
+            .update_server_settings(
+                &server.id,
+                UpdateServerSettingsRequest {
+                    join_mode: ServerJoinMode::InviteOnly,
+                    system_channel_id: Some(foreign_channel.id),
+                    default_role_ids: vec![],
+                    allow_nickname_change: false,
+                    join_questions: vec![],
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, CoreError::InvalidServerSettings { .. }));
+
+        // Nothing was saved
+        let settings = service.get_server_settings(&server.id).await?;
+        assert_eq!(settings.join_mode, ServerJoinMode::Open);
+        assert!(settings.allow_nickname_change);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update_server_settings_success() -> Result<(), Box<dyn std::error::Error>> {
+        let service = create_mock_service();
+        let server = service
+            .create_server(InsertServerInput {
+                name: "Settings Server".to_string(),
+                owner_id: UserId::from(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            })
+            .await?;
+        let role = service
+            .role_repository
+            .create(CreateRoleInput {
+                server_id: *server.id,
+                name: "Newcomer".to_string(),
+                permissions: Permissions(0x4),
+                appearance: RoleAppearance::default(),
+            })
+            .await?;
+        let welcome = service
+            .channel_repository
+            .create(CreateChannelRepoInput {
+                name: "welcome".to_string(),
+                server_id: Some(server.id),
+                parent_id: None,
+                channel_type: ChannelType::ServerText,
+            })
+            .await?;
+
+        let settings = service
+            .update_server_settings(
+                &server.id,
+                UpdateServerSettingsRequest {
+                    join_mode: ServerJoinMode::RequestToJoin,
+                    system_channel_id: Some(welcome.id),
+                    default_role_ids: vec![role.id, role.id],
+                    allow_nickname_change: false,
+                    join_questions: vec![],
+                },
+            )
+            .await?;
+
+        assert_eq!(settings.join_mode, ServerJoinMode::RequestToJoin);
+        assert_eq!(settings.system_channel_id, Some(welcome.id));
+        assert_eq!(settings.default_role_ids, vec![role.id]);
+        assert!(!settings.allow_nickname_change);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,147 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::domain::common::{CoreError, GetPaginated};
+use crate::domain::friend::entities::UserId;
+use crate::domain::server::entities::{InsertServerInput, Server, ServerVisibility};
+use crate::domain::server::ports::ServerRepository;
+use crate::domain::server_ban::entities::{BanMemberRequest, InsertServerBanInput};
+use crate::domain::server_ban::ports::{ServerBanRepository, ServerBanService};
+use crate::domain::server_member::entities::CreateMemberInput;
+use crate::domain::server_member::ports::MemberService;
+use crate::domain::test::{MockService, create_mock_service};
+
+async fn create_public_server(service: &MockService) -> Result<Server, CoreError> {
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    service.server_repository.insert(server_input).await
+}
+
+fn join_input(server: &Server, user_id: UserId) -> CreateMemberInput {
+    CreateMemberInput {
+        server_id: server.id,
+        user_id,
+        nickname: None,
+    }
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_banned_user_cannot_join() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_public_server(&service).await?;
+    let user_id = UserId::from(Uuid::new_v4());
+
+    let request = BanMemberRequest {
+        reason: Some("Spam".to_string()),
+        expires_at: None,
+    };
+    let ban = service
+        .ban_member(server.id, user_id, server.owner_id, request)
+        .await?;
+    assert_eq!(ban.reason, Some("Spam".to_string()));
+
+    let result = service.create_member(join_input(&server, user_id)).await;
+    assert!(matches!(result, Err(CoreError::UserBanned { .. })));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_unban_allows_rejoin() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_public_server(&service).await?;
+    let user_id = UserId::from(Uuid::new_v4());
+
+    let request = BanMemberRequest {
+        reason: None,
+        expires_at: Some(Utc::now() + Duration::days(1)),
+    };
+    service
+        .ban_member(server.id, user_id, server.owner_id, request)
+        .await?;
+    service
+        .unban_member(server.id, user_id, server.owner_id)
+        .await?;
+
+    let member = service.create_member(join_input(&server, user_id)).await?;
+    assert_eq!(member.user_id, user_id);
+
+    let result = service
+        .unban_member(server.id, user_id, server.owner_id)
+        .await;
+    assert!(matches!(result, Err(CoreError::ServerBanNotFound { .. })));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_expired_ban_does_not_prevent_join() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_public_server(&service).await?;
+    let user_id = UserId::from(Uuid::new_v4());
+
+    service
+        .server_ban_repository
+        .insert(InsertServerBanInput {
+            server_id: server.id,
+            user_id,
+            moderator_id: server.owner_id,
+            reason: None,
+            expires_at: Some(Utc::now() - Duration::hours(1)),
+        })
+        .await?;
+
+    service.create_member(join_input(&server, user_id)).await?;
+
+    let (bans, total) = service
+        .list_bans(server.id, &GetPaginated::default())
+        .await?;
+    assert!(bans.is_empty());
+    assert_eq!(total, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_cannot_ban_owner_self_or_equal_rank() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_public_server(&service).await?;
+    let moderator_id = UserId::from(Uuid::new_v4());
+    let request = BanMemberRequest {
+        reason: None,
+        expires_at: None,
+    };
+
+    let result = service
+        .ban_member(server.id, server.owner_id, moderator_id, request.clone())
+        .await;
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+
+    let result = service
+        .ban_member(server.id, moderator_id, moderator_id, request.clone())
+        .await;
+    assert!(matches!(result, Err(CoreError::InvalidServerBan { .. })));
+
+    // The moderator holds no role above the user
+    let result = service
+        .ban_member(
+            server.id,
+            UserId::from(Uuid::new_v4()),
+            moderator_id,
+            request,
+        )
+        .await;
+    assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+    Ok(())
+}
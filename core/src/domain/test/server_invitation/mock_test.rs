@@ -0,0 +1,157 @@
+use uuid::Uuid;
+
+use crate::domain::common::CoreError;
+use crate::domain::friend::entities::UserId;
+use crate::domain::server::entities::{InsertServerInput, ServerVisibility};
+use crate::domain::server::ports::{ServerRepository, ServerService};
+use crate::domain::server_invitation::entities::{
+    InsertServerInvitationInput, SetVanitySlugRequest,
+};
+use crate::domain::server_invitation::ports::ServerInvitationService;
+use crate::domain::server_member::ports::MemberRepository;
+use crate::domain::test::create_mock_service;
+
+fn server_input(visibility: ServerVisibility) -> InsertServerInput {
+    InsertServerInput {
+        name: "Vanity Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: Some("Come in".to_string()),
+        visibility,
+    }
+}
+
+#[tokio::test]
+async fn test_join_by_vanity_slug_success() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = service
+        .server_repository
+        .insert(server_input(ServerVisibility::Public))
+        .await?;
+
+    let vanity = service
+        .set_vanity_slug(
+            &server.id,
+            &server.owner_id,
+            SetVanitySlugRequest {
+                slug: "Rust-Lovers".to_string(),
+            },
+        )
+        .await?;
+    assert_eq!(vanity.slug, "rust-lovers");
+
+    // Lookup is case-insensitive and only returns the preview
+    let lookup = service.resolve_vanity_slug("RUST-lovers").await?;
+    assert_eq!(lookup.server.id, server.id);
+    assert_eq!(lookup.server.member_count, 0);
+
+    let user_id = UserId::from(Uuid::new_v4());
+    service.join_by_vanity_slug("rust-lovers", user_id).await?;
+
+    let member = service
+        .member_repository
+        .find_by_server_and_user(&server.id, &user_id)
+        .await?;
+    assert_eq!(member.user_id, user_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_vanity_slug_conflict() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let first = service
+        .server_repository
+        .insert(server_input(ServerVisibility::Public))
+        .await?;
+    let second = service
+        .server_repository
+        .insert(server_input(ServerVisibility::Public))
+        .await?;
+
+    service
+        .set_vanity_slug(
+            &first.id,
+            &first.owner_id,
+            SetVanitySlugRequest {
+                slug: "taken".to_string(),
+            },
+        )
+        .await?;
+    let result = service
+        .set_vanity_slug(
+            &second.id,
+            &second.owner_id,
+            SetVanitySlugRequest {
+                slug: "TAKEN".to_string(),
+            },
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(CoreError::VanitySlugAlreadyTaken { .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_vanity_slug_private_server_forbidden() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = service
+        .server_repository
+        .insert(server_input(ServerVisibility::Private))
+        .await?;
+
+    let result = service
+        .set_vanity_slug(
+            &server.id,
+            &server.owner_id,
+            SetVanitySlugRequest {
+                slug: "secret-club".to_string(),
+            },
+        )
+        .await;
+
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_preview_private_server_requires_invitation() -> Result<(), Box<dyn std::error::Error>>
+{
+    let service = create_mock_service();
+    let server = service
+        .server_repository
+        .insert(server_input(ServerVisibility::Private))
+        .await?;
+    let invitee_id = UserId::from(Uuid::new_v4());
+
+    let result = service.preview_server(&server.id, &invitee_id, None).await;
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+
+    let invitation = service
+        .create_invitation(InsertServerInvitationInput {
+            server_id: server.id,
+            inviter_id: server.owner_id,
+            invitee_id: Some(invitee_id),
+            expires_at: None,
+        })
+        .await?;
+
+    let preview = service
+        .preview_server(&server.id, &invitee_id, Some(invitation.id))
+        .await?;
+    assert_eq!(preview.name, "Vanity Server");
+    assert_eq!(preview.description, Some("Come in".to_string()));
+
+    // A personal invitation does not grant a preview to someone else
+    let stranger_id = UserId::from(Uuid::new_v4());
+    let result = service
+        .get_invitation_preview(&invitation.id, &stranger_id)
+        .await;
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use uuid::Uuid;
+
+use crate::domain::common::{CoreError, GetPaginated};
+use crate::domain::friend::entities::UserId;
+use crate::domain::server::entities::{
+    InsertServerInput, Server, ServerJoinMode, ServerVisibility, UpdateServerSettingsRequest,
+};
+use crate::domain::server::ports::{ServerRepository, ServerService};
+use crate::domain::server_ban::entities::BanMemberRequest;
+use crate::domain::server_ban::ports::ServerBanService;
+use crate::domain::server_join_request::entities::{
+    JoinRequestAnswer, JoinRequestStatus, ListJoinRequestsQuery, SubmitJoinRequestRequest,
+};
+use crate::domain::server_join_request::ports::ServerJoinRequestService;
+use crate::domain::server_member::entities::CreateMemberInput;
+use crate::domain::server_member::ports::MemberRepository;
+use crate::domain::test::{MockService, create_mock_service};
+
+async fn create_request_to_join_server(service: &MockService) -> Result<Server, CoreError> {
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Private,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    service
+        .update_server_settings(
+            &server.id,
+            UpdateServerSettingsRequest {
+                join_mode: ServerJoinMode::RequestToJoin,
+                system_channel_id: None,
+                default_role_ids: vec![],
+                allow_nickname_change: true,
+                join_questions: vec!["Who invited you?".to_string()],
+            },
+        )
+        .await?;
+    Ok(server)
+}
+
+fn answers(answers: &[&str]) -> SubmitJoinRequestRequest {
+    SubmitJoinRequestRequest {
+        answers: answers.iter().map(|answer| answer.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_approved_request_makes_the_applicant_a_member()
+-> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_request_to_join_server(&service).await?;
+    let user_id = UserId::from(Uuid::new_v4());
+
+    let request = service
+        .submit_join_request(server.id, user_id, answers(&["A friend"]))
+        .await?;
+    assert_eq!(request.status, JoinRequestStatus::Pending);
+    assert_eq!(
+        request.answers,
+        vec![JoinRequestAnswer {
+            question: "Who invited you?".to_string(),
+            answer: "A friend".to_string(),
+        }]
+    );
+
+    let (pending, total) = service
+        .list_join_requests(
+            server.id,
+            ListJoinRequestsQuery::default(),
+            &GetPaginated::default(),
+        )
+        .await?;
+    assert_eq!(total, 1);
+    assert_eq!(pending[0].id, request.id);
+
+    let approved = service
+        .approve_join_request(server.id, request.id, server.owner_id)
+        .await?;
+    assert_eq!(approved.status, JoinRequestStatus::Approved);
+    assert_eq!(approved.reviewer_id, Some(server.owner_id));
+    service
+        .member_repository
+        .find_by_server_and_user(&server.id, &user_id)
+        .await?;
+
+    let own = service.get_own_join_request(server.id, user_id).await?;
+    assert_eq!(own.status, JoinRequestStatus::Approved);
+
+    let result = service
+        .deny_join_request(server.id, request.id, server.owner_id)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::JoinRequestAlreadyReviewed { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_denied_request_lets_the_applicant_apply_again()
+-> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_request_to_join_server(&service).await?;
+    let user_id = UserId::from(Uuid::new_v4());
+
+    let request = service
+        .submit_join_request(server.id, user_id, SubmitJoinRequestRequest::default())
+        .await?;
+    let result = service
+        .submit_join_request(server.id, user_id, SubmitJoinRequestRequest::default())
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::JoinRequestAlreadyPending { .. })
+    ));
+
+    let denied = service
+        .deny_join_request(server.id, request.id, server.owner_id)
+        .await?;
+    assert_eq!(denied.status, JoinRequestStatus::Denied);
+    let result = service
+        .member_repository
+        .find_by_server_and_user(&server.id, &user_id)
+        .await;
+    assert!(matches!(result, Err(CoreError::MemberNotFound { .. })));
+
+    service
+        .submit_join_request(server.id, user_id, SubmitJoinRequestRequest::default())
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_join_requests_are_rejected_outside_request_to_join()
+-> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+    let server = create_request_to_join_server(&service).await?;
+
+    let member_id = UserId::from(Uuid::new_v4());
+    service
+        .member_repository
+        .insert(CreateMemberInput {
+            server_id: server.id,
+            user_id: member_id,
+            nickname: None,
+        })
+        .await?;
+    let result = service
+        .submit_join_request(server.id, member_id, answers(&[]))
+        .await;
+    assert!(matches!(result, Err(CoreError::MemberAlreadyExists { .. })));
+
+    let banned_id = UserId::from(Uuid::new_v4());
+    service
+        .ban_member(
+            server.id,
+            banned_id,
+            server.owner_id,
+            BanMemberRequest {
+                reason: None,
+                expires_at: None,
+            },
+        )
+        .await?;
+    let result = service
+        .submit_join_request(server.id, banned_id, answers(&[]))
+        .await;
+    assert!(matches!(result, Err(CoreError::UserBanned { .. })));
+
+    service
+        .update_server_settings(
+            &server.id,
+            UpdateServerSettingsRequest {
+                join_mode: ServerJoinMode::InviteOnly,
+                system_channel_id: None,
+                default_role_ids: vec![],
+                allow_nickname_change: true,
+                join_questions: vec![],
+            },
+        )
+        .await?;
+    let result = service
+        .submit_join_request(server.id, UserId::from(Uuid::new_v4()), answers(&[]))
+        .await;
+    assert!(matches!(result, Err(CoreError::InvalidJoinRequest { .. })));
+
+    Ok(())
+}
@@ -1,12 +1,24 @@
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 
-use crate::domain::common::{CoreError, GetPaginated};
+use crate::domain::common::{CoreError, Cursor, GetPaginated};
 use crate::domain::friend::entities::UserId;
-use crate::domain::server::entities::{InsertServerInput, ServerVisibility};
-use crate::domain::server::ports::ServerRepository;
-use crate::domain::server_member::entities::{CreateMemberInput, UpdateMemberInput};
+use crate::domain::member_role::ports::MemberRoleRepository;
+use crate::domain::role::entities::{CreateRoleInput, Permissions, RoleAppearance};
+use crate::domain::role::ports::RoleRepository;
+use crate::domain::server::entities::{
+    InsertServerInput, ServerJoinMode, ServerVisibility, UpdateServerSettingsRequest,
+};
+use crate::domain::server::ports::{ServerRepository, ServerService};
+use crate::domain::server_member::entities::{
+    BulkKickRequest, CreateMemberInput, KickMemberRequest, ListMembersQuery, MAX_BULK_MEMBERS,
+    MAX_MEMBER_BIO_SIZE, MAX_MEMBER_PRONOUNS_SIZE, MAX_PRUNE_DAYS, MAX_REMOVAL_REASON_SIZE,
+    MAX_TIMEOUT_DAYS, MemberProfile, MemberSortOrder, PruneMembersRequest, PruneMembersResult,
+    TimeoutMemberRequest, UpdateMemberInput, validate_bulk_user_ids,
+};
 use crate::domain::server_member::ports::{MemberRepository, MemberService};
 use crate::domain::test::create_mock_service;
+use crate::domain::user::entities::User;
 
 #[tokio::test]
 #[cfg(test)]
@@ -174,7 +186,9 @@ async fn test_list_members_success() -> Result<(), Box<dyn std::error::Error>> {
     // Service already created above
 
     let pagination = GetPaginated { page: 1, limit: 10 };
-    let (members, total) = service.list_members(server.id, pagination).await?;
+    let (members, total) = service
+        .list_members(server.id, pagination, ListMembersQuery::default())
+        .await?;
 
     assert_eq!(members.len(), 3);
     assert_eq!(total, 3);
@@ -201,7 +215,9 @@ async fn test_list_members_empty() -> Result<(), Box<dyn std::error::Error>> {
     // Service already created above
 
     let pagination = GetPaginated { page: 1, limit: 10 };
-    let (members, total) = service.list_members(server.id, pagination).await?;
+    let (members, total) = service
+        .list_members(server.id, pagination, ListMembersQuery::default())
+        .await?;
 
     assert_eq!(members.len(), 0);
     assert_eq!(total, 0);
@@ -218,7 +234,11 @@ async fn test_list_members_server_not_found() -> Result<(), Box<dyn std::error::
 
     let pagination = GetPaginated { page: 1, limit: 10 };
     let result = service
-        .list_members(Uuid::new_v4().into(), pagination)
+        .list_members(
+            Uuid::new_v4().into(),
+            pagination,
+            ListMembersQuery::default(),
+        )
         .await;
 
     assert!(matches!(result, Err(CoreError::ServerNotFound { .. })));
@@ -256,7 +276,9 @@ async fn test_list_members_with_pagination() -> Result<(), Box<dyn std::error::E
 
     // Get page 2 with limit 2
     let pagination = GetPaginated { page: 2, limit: 2 };
-    let (members, total) = service.list_members(server.id, pagination).await?;
+    let (members, total) = service
+        .list_members(server.id, pagination, ListMembersQuery::default())
+        .await?;
 
     assert_eq!(members.len(), 2);
     assert_eq!(total, 5);
@@ -297,6 +319,7 @@ async fn test_update_member_success() -> Result<(), Box<dyn std::error::Error>>
         server_id: server.id,
         user_id,
         nickname: Some("NewNickname".to_string()),
+        profile: None,
     };
 
     let updated_member = service.update_member(update_input).await?;
@@ -340,6 +363,7 @@ async fn test_update_member_partial() -> Result<(), Box<dyn std::error::Error>>
         server_id: server.id,
         user_id,
         nickname: None,
+        profile: None,
     };
 
     let updated_member = service.update_member(update_input).await?;
@@ -364,6 +388,7 @@ async fn test_update_member_not_found() -> Result<(), Box<dyn std::error::Error>
         server_id: Uuid::new_v4().into(),
         user_id: UserId::from(Uuid::new_v4()),
         nickname: None,
+        profile: None,
     };
 
     let result = service.update_member(update_input).await;
@@ -406,6 +431,7 @@ async fn test_update_member_invalid_nickname() -> Result<(), Box<dyn std::error:
         server_id: server.id,
         user_id,
         nickname: Some("".to_string()),
+        profile: None,
     };
 
     let result = service.update_member(update_input).await;
@@ -417,7 +443,96 @@ async fn test_update_member_invalid_nickname() -> Result<(), Box<dyn std::error:
 
 #[tokio::test]
 #[cfg(test)]
-async fn test_delete_member_success() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_update_member_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+
+    let user_id = UserId::from(Uuid::new_v4());
+    service
+        .member_repository
+        .insert(CreateMemberInput {
+            server_id: server.id,
+            user_id,
+            nickname: None,
+        })
+        .await?;
+
+    // Fields are trimmed and empty ones dropped
+    let updated_member = service
+        .update_member(UpdateMemberInput {
+            server_id: server.id,
+            user_id,
+            nickname: None,
+            profile: Some(MemberProfile {
+                avatar_url: Some("https://example.com/avatar.png".to_string()),
+                bio: Some("  Hello there  ".to_string()),
+                pronouns: Some(" ".to_string()),
+            }),
+        })
+        .await?;
+    assert_eq!(
+        updated_member.profile,
+        MemberProfile {
+            avatar_url: Some("https://example.com/avatar.png".to_string()),
+            bio: Some("Hello there".to_string()),
+            pronouns: None,
+        }
+    );
+
+    // Omitting the profile keeps the current one
+    let updated_member = service
+        .update_member(UpdateMemberInput {
+            server_id: server.id,
+            user_id,
+            nickname: Some("Nick".to_string()),
+            profile: None,
+        })
+        .await?;
+    assert_eq!(updated_member.profile.bio, Some("Hello there".to_string()));
+
+    for profile in [
+        MemberProfile {
+            bio: Some("a".repeat(MAX_MEMBER_BIO_SIZE + 1)),
+            ..Default::default()
+        },
+        MemberProfile {
+            pronouns: Some("a".repeat(MAX_MEMBER_PRONOUNS_SIZE + 1)),
+            ..Default::default()
+        },
+        MemberProfile {
+            avatar_url: Some("javascript:alert(1)".to_string()),
+            ..Default::default()
+        },
+    ] {
+        let result = service
+            .update_member(UpdateMemberInput {
+                server_id: server.id,
+                user_id,
+                nickname: None,
+                profile: Some(profile),
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(CoreError::InvalidMemberProfile { .. })
+        ));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_kick_member_success() -> Result<(), Box<dyn std::error::Error>> {
     let service = create_mock_service();
 
     // Create test server
@@ -442,8 +557,13 @@ async fn test_delete_member_success() -> Result<(), Box<dyn std::error::Error>>
     service.member_repository.insert(create_input).await?;
 
     // Service already created above
-    // Delete member
-    service.delete_member(server.id, user_id).await?;
+    // Kick member
+    let request = KickMemberRequest {
+        reason: Some("Spamming".to_string()),
+    };
+    service
+        .kick_member(server.id, user_id, server.owner_id, request)
+        .await?;
 
     // Verify member is deleted
     let result = service
@@ -457,16 +577,704 @@ async fn test_delete_member_success() -> Result<(), Box<dyn std::error::Error>>
 
 #[tokio::test]
 #[cfg(test)]
-async fn test_delete_member_not_found() -> Result<(), Box<dyn std::error::Error>> {
+async fn test_kick_member_not_found() -> Result<(), Box<dyn std::error::Error>> {
     let service = create_mock_service();
 
-    // Service already created above
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
 
     let result = service
-        .delete_member(Uuid::new_v4().into(), UserId::from(Uuid::new_v4()))
+        .kick_member(
+            server.id,
+            UserId::from(Uuid::new_v4()),
+            server.owner_id,
+            KickMemberRequest::default(),
+        )
         .await;
 
     assert!(matches!(result, Err(CoreError::MemberNotFound { .. })));
 
     Ok(())
 }
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_kick_member_rejects_owner_self_and_long_reason()
+-> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let moderator_id = UserId::from(Uuid::new_v4());
+
+    let result = service
+        .kick_member(
+            server.id,
+            server.owner_id,
+            moderator_id,
+            KickMemberRequest::default(),
+        )
+        .await;
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+
+    let result = service
+        .kick_member(
+            server.id,
+            moderator_id,
+            moderator_id,
+            KickMemberRequest::default(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::InvalidMemberRemoval { .. })
+    ));
+
+    let request = KickMemberRequest {
+        reason: Some("a".repeat(MAX_REMOVAL_REASON_SIZE + 1)),
+    };
+    let result = service
+        .kick_member(
+            server.id,
+            UserId::from(Uuid::new_v4()),
+            moderator_id,
+            request,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::InvalidMemberRemoval { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_moderation_requires_a_higher_role() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let moderator_id = UserId::from(Uuid::new_v4());
+    let member_id = UserId::from(Uuid::new_v4());
+    for user_id in [moderator_id, member_id] {
+        service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id,
+                nickname: None,
+            })
+            .await?;
+    }
+
+    // Neither holds a role ranking above the other
+    let result = service
+        .kick_member(
+            server.id,
+            member_id,
+            moderator_id,
+            KickMemberRequest::default(),
+        )
+        .await;
+    assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+    let result = service
+        .timeout_member(
+            server.id,
+            member_id,
+            moderator_id,
+            TimeoutMemberRequest {
+                until: Utc::now() + Duration::hours(1),
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+    let result = service
+        .clear_member_timeout(server.id, member_id, moderator_id)
+        .await;
+    assert!(matches!(result, Err(CoreError::RoleHierarchy)));
+
+    service
+        .member_repository
+        .find_by_server_and_user(&server.id, &member_id)
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_leave_server() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let member = service
+        .create_member(CreateMemberInput {
+            server_id: server.id,
+            user_id: UserId::from(Uuid::new_v4()),
+            nickname: None,
+        })
+        .await?;
+
+    service.leave_server(server.id, member.user_id).await?;
+    let result = service.get_member(server.id, member.user_id).await;
+    assert!(matches!(result, Err(CoreError::MemberNotFound { .. })));
+
+    // The owner cannot walk away from their server
+    let result = service.leave_server(server.id, server.owner_id).await;
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_create_member_assigns_default_roles() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let role = service
+        .role_repository
+        .create(CreateRoleInput {
+            server_id: *server.id,
+            name: "Newcomer".to_string(),
+            permissions: Permissions(0x4),
+            appearance: RoleAppearance::default(),
+        })
+        .await?;
+    service
+        .update_server_settings(
+            &server.id,
+            UpdateServerSettingsRequest {
+                join_mode: ServerJoinMode::Open,
+                system_channel_id: None,
+                default_role_ids: vec![role.id],
+                allow_nickname_change: true,
+                join_questions: vec![],
+            },
+        )
+        .await?;
+
+    service
+        .create_member(CreateMemberInput {
+            server_id: server.id,
+            user_id: UserId::from(Uuid::new_v4()),
+            nickname: None,
+        })
+        .await?;
+
+    let counts = service
+        .member_role_repository
+        .count_members_by_role(server.id)
+        .await?;
+    assert_eq!(counts.get(&role.id), Some(&1));
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_create_member_invite_only_forbidden() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Invite Only Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    service
+        .update_server_settings(
+            &server.id,
+            UpdateServerSettingsRequest {
+                join_mode: ServerJoinMode::InviteOnly,
+                system_channel_id: None,
+                default_role_ids: vec![],
+                allow_nickname_change: true,
+                join_questions: vec![],
+            },
+        )
+        .await?;
+
+    let result = service
+        .create_member(CreateMemberInput {
+            server_id: server.id,
+            user_id: UserId::from(Uuid::new_v4()),
+            nickname: None,
+        })
+        .await;
+
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_timeout_member_set_and_clear() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let member = service
+        .create_member(CreateMemberInput {
+            server_id: server.id,
+            user_id: UserId::from(Uuid::new_v4()),
+            nickname: None,
+        })
+        .await?;
+    assert!(!member.is_timed_out());
+
+    let request = TimeoutMemberRequest {
+        until: Utc::now() + Duration::hours(1),
+    };
+    let member = service
+        .timeout_member(server.id, member.user_id, server.owner_id, request)
+        .await?;
+    assert!(member.is_timed_out());
+
+    let member = service
+        .clear_member_timeout(server.id, member.user_id, server.owner_id)
+        .await?;
+    assert!(!member.is_timed_out());
+    assert!(member.timed_out_until.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_timeout_member_rejects_owner_and_invalid_dates()
+-> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let moderator_id = UserId::from(Uuid::new_v4());
+
+    let in_an_hour = TimeoutMemberRequest {
+        until: Utc::now() + Duration::hours(1),
+    };
+    let result = service
+        .timeout_member(server.id, server.owner_id, moderator_id, in_an_hour)
+        .await;
+    assert!(matches!(result, Err(CoreError::Forbidden)));
+
+    let in_the_past = TimeoutMemberRequest {
+        until: Utc::now() - Duration::hours(1),
+    };
+    let result = service
+        .timeout_member(
+            server.id,
+            UserId::from(Uuid::new_v4()),
+            moderator_id,
+            in_the_past,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::InvalidMemberTimeout { .. })
+    ));
+
+    let too_long = TimeoutMemberRequest {
+        until: Utc::now() + Duration::days(MAX_TIMEOUT_DAYS + 1),
+    };
+    let result = service
+        .timeout_member(
+            server.id,
+            UserId::from(Uuid::new_v4()),
+            moderator_id,
+            too_long,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::InvalidMemberTimeout { .. })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_list_members_search_and_sort() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    for nickname in ["Alice", "alfred", "Bob"] {
+        service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId::from(Uuid::new_v4()),
+                nickname: Some(nickname.to_string()),
+            })
+            .await?;
+    }
+    // Matched by display name only, the member has no nickname
+    let user_id = Uuid::new_v4();
+    service
+        .member_repository
+        .insert(CreateMemberInput {
+            server_id: server.id,
+            user_id: UserId::from(user_id),
+            nickname: None,
+        })
+        .await?;
+    service
+        .user_repository
+        .add_user(User {
+            sub: user_id,
+            display_name: "Alvin".to_string(),
+            profile_picture: String::new(),
+            description: String::new(),
+        })
+        .await;
+
+    let query = ListMembersQuery {
+        search: Some(" AL".to_string()),
+        role_id: None,
+        sort: MemberSortOrder::JoinedAtAsc,
+        include_profiles: false,
+    };
+    let (members, total) = service
+        .list_members(server.id, GetPaginated::default(), query)
+        .await?;
+
+    assert_eq!(total, 3);
+    let nicknames: Vec<_> = members
+        .iter()
+        .filter_map(|m| m.member.nickname.clone())
+        .collect();
+    assert_eq!(nicknames, vec!["Alice".to_string(), "alfred".to_string()]);
+    assert!(
+        members
+            .iter()
+            .any(|m| m.member.user_id == UserId::from(user_id))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_list_members_include_profiles() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let user_id = Uuid::new_v4();
+    service
+        .member_repository
+        .insert(CreateMemberInput {
+            server_id: server.id,
+            user_id: UserId::from(user_id),
+            nickname: None,
+        })
+        .await?;
+    service
+        .user_repository
+        .add_user(User {
+            sub: user_id,
+            display_name: "alice".to_string(),
+            profile_picture: "https://example.com/alice.png".to_string(),
+            description: String::new(),
+        })
+        .await;
+
+    let (members, _) = service
+        .list_members(
+            server.id,
+            GetPaginated::default(),
+            ListMembersQuery::default(),
+        )
+        .await?;
+    assert_eq!(members[0].display_name, None);
+
+    let query = ListMembersQuery {
+        include_profiles: true,
+        ..Default::default()
+    };
+    let (members, _) = service
+        .list_members(server.id, GetPaginated::default(), query)
+        .await?;
+    assert_eq!(members[0].display_name.as_deref(), Some("alice"));
+    assert_eq!(
+        members[0].profile_picture.as_deref(),
+        Some("https://example.com/alice.png")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_list_members_after_walks_pages() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    for i in 0..5 {
+        service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id: UserId::from(Uuid::new_v4()),
+                nickname: Some(format!("User{}", i)),
+            })
+            .await?;
+    }
+
+    let (first, total, next_cursor) = service
+        .list_members_after(server.id, None, 3, ListMembersQuery::default())
+        .await?;
+    assert_eq!(first.len(), 3);
+    assert_eq!(total, 5);
+
+    // The cursor goes through its opaque form like it would over HTTP
+    let next_cursor = Cursor::decode(&next_cursor.expect("a next page").encode())?;
+    let (second, _, next_cursor) = service
+        .list_members_after(server.id, Some(next_cursor), 3, ListMembersQuery::default())
+        .await?;
+    assert_eq!(second.len(), 2);
+    assert!(next_cursor.is_none());
+    assert!(
+        second
+            .iter()
+            .all(|m| first.iter().all(|f| f.member.id != m.member.id))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_invalid_cursor_is_rejected() {
+    let result = Cursor::decode("not a cursor");
+
+    assert!(matches!(result, Err(CoreError::InvalidCursor)));
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_bulk_kick_members_reports_each_user() -> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+
+    let moderator_id = UserId::from(Uuid::new_v4());
+    let member_id = UserId::from(Uuid::new_v4());
+    for user_id in [moderator_id, member_id] {
+        service
+            .member_repository
+            .insert(CreateMemberInput {
+                server_id: server.id,
+                user_id,
+                nickname: None,
+            })
+            .await?;
+    }
+    let stranger_id = UserId::from(Uuid::new_v4());
+
+    let request = BulkKickRequest {
+        user_ids: vec![
+            member_id,
+            server.owner_id,
+            moderator_id,
+            stranger_id,
+            member_id,
+        ],
+        reason: Some("Raid".to_string()),
+    };
+    let results = service
+        .bulk_kick_members(server.id, moderator_id, request)
+        .await?;
+
+    assert_eq!(results.len(), 4);
+    let outcome = |user_id: UserId| {
+        results
+            .iter()
+            .find(|result| result.user_id == user_id)
+            .map(|result| result.success)
+    };
+    // The moderator holds no role above the member
+    assert_eq!(outcome(member_id), Some(false));
+    assert_eq!(outcome(server.owner_id), Some(false));
+    assert_eq!(outcome(moderator_id), Some(false));
+    assert_eq!(outcome(stranger_id), Some(false));
+
+    let request = BulkKickRequest {
+        user_ids: vec![member_id, stranger_id],
+        reason: Some("Raid".to_string()),
+    };
+    let results = service
+        .bulk_kick_members(server.id, server.owner_id, request)
+        .await?;
+    assert!(results[0].success);
+    assert!(!results[1].success);
+
+    let kicked = service
+        .member_repository
+        .find_by_server_and_user(&server.id, &member_id)
+        .await;
+    assert!(matches!(kicked, Err(CoreError::MemberNotFound { .. })));
+    service
+        .member_repository
+        .find_by_server_and_user(&server.id, &moderator_id)
+        .await?;
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_bulk_user_ids_limits() {
+    assert!(matches!(
+        validate_bulk_user_ids(&[]),
+        Err(CoreError::InvalidBulkRequest { .. })
+    ));
+
+    let too_many: Vec<UserId> = (0..=MAX_BULK_MEMBERS)
+        .map(|_| UserId::from(Uuid::new_v4()))
+        .collect();
+    assert!(matches!(
+        validate_bulk_user_ids(&too_many),
+        Err(CoreError::InvalidBulkRequest { .. })
+    ));
+}
+
+#[tokio::test]
+#[cfg(test)]
+async fn test_prune_members_keeps_recent_members_and_owner()
+-> Result<(), Box<dyn std::error::Error>> {
+    let service = create_mock_service();
+
+    let server_input = InsertServerInput {
+        name: "Test Server".to_string(),
+        owner_id: UserId::from(Uuid::new_v4()),
+        picture_url: None,
+        banner_url: None,
+        description: None,
+        visibility: ServerVisibility::Public,
+    };
+    let server = service.server_repository.insert(server_input).await?;
+    let member_id = UserId::from(Uuid::new_v4());
+    service
+        .member_repository
+        .insert(CreateMemberInput {
+            server_id: server.id,
+            user_id: member_id,
+            nickname: None,
+        })
+        .await?;
+
+    let dry_run = service
+        .count_prunable_members(server.id, PruneMembersRequest::default())
+        .await?;
+    assert_eq!(dry_run.count, 0);
+
+    let pruned = service
+        .prune_members(server.id, server.owner_id, PruneMembersRequest { days: 1 })
+        .await?;
+    assert_eq!(pruned, PruneMembersResult { days: 1, count: 0 });
+    service
+        .member_repository
+        .find_by_server_and_user(&server.id, &member_id)
+        .await?;
+
+    for days in [0, MAX_PRUNE_DAYS + 1] {
+        let result = service
+            .prune_members(server.id, server.owner_id, PruneMembersRequest { days })
+            .await;
+        assert!(matches!(
+            result,
+            Err(CoreError::InvalidMemberRemoval { .. })
+        ));
+    }
+
+    Ok(())
+}
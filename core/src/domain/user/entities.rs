@@ -13,3 +13,8 @@ pub struct User {
 pub struct GetUserByDisplayname {
     pub display_name: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetUsersByIds {
+    pub user_ids: Vec<Uuid>,
+}
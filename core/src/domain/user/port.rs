@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use futures_util::lock::Mutex;
+use uuid::Uuid;
 
 use crate::{domain::user::entities::User, infrastructure::user::repositories::error::UserError};
 
@@ -9,6 +10,18 @@ pub trait UserRepository: Send + Sync {
         &self,
         username: &String,
     ) -> impl Future<Output = Result<Option<User>, UserError>> + Send;
+
+    /// Fetch several users at once, unknown ids are left out of the result
+    fn get_users_by_ids(
+        &self,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<User>, UserError>> + Send;
+
+    /// Find the users whose display name starts with the prefix, ignoring case
+    fn search_users_by_display_name(
+        &self,
+        prefix: &str,
+    ) -> impl Future<Output = Result<Vec<User>, UserError>> + Send;
 }
 
 #[derive(Clone)]
@@ -22,10 +35,33 @@ impl MockUserRepository {
             users: Arc::new(Mutex::new(Vec::new())),
         }
     }
+
+    pub async fn add_user(&self, user: User) {
+        self.users.lock().await.push(user);
+    }
 }
 
 impl UserRepository for MockUserRepository {
     async fn get_user_by_username(&self, username: &String) -> Result<Option<User>, UserError> {
         Ok(None)
     }
+
+    async fn get_users_by_ids(&self, user_ids: &[Uuid]) -> Result<Vec<User>, UserError> {
+        let users = self.users.lock().await;
+        Ok(users
+            .iter()
+            .filter(|user| user_ids.contains(&user.sub))
+            .cloned()
+            .collect())
+    }
+
+    async fn search_users_by_display_name(&self, prefix: &str) -> Result<Vec<User>, UserError> {
+        let prefix = prefix.to_lowercase();
+        let users = self.users.lock().await;
+        Ok(users
+            .iter()
+            .filter(|user| user.display_name.to_lowercase().starts_with(&prefix))
+            .cloned()
+            .collect())
+    }
 }
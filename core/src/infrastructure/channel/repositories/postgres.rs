@@ -7,7 +7,8 @@ use crate::{
         channel::{
             entities::{
                 Channel, ChannelId, ChannelType, CreateChannelRepoInput, DeleteChannelEvent,
-                ServerChannelCreation, UpdateChannelRepoInput,
+                DeletePermissionOverwriteEvent, OverwriteTarget, PermissionOverwrite,
+                ServerChannelCreation, UpdateChannelRepoInput, UpsertPermissionOverwriteInput,
             },
             ports::ChannelRepository,
         },
@@ -22,6 +23,8 @@ pub struct PostgresChannelRepository {
     pub(crate) pool: PgPool,
     create_channel_router: MessageRoutingInfo,
     delete_channel_router: MessageRoutingInfo,
+    upsert_overwrite_router: MessageRoutingInfo,
+    delete_overwrite_router: MessageRoutingInfo,
 }
 
 impl PostgresChannelRepository {
@@ -29,15 +32,27 @@ impl PostgresChannelRepository {
         pool: PgPool,
         create_channel_router: MessageRoutingInfo,
         delete_channel_router: MessageRoutingInfo,
+        upsert_overwrite_router: MessageRoutingInfo,
+        delete_overwrite_router: MessageRoutingInfo,
     ) -> Self {
         Self {
             pool,
             create_channel_router,
             delete_channel_router,
+            upsert_overwrite_router,
+            delete_overwrite_router,
         }
     }
 }
 
+/// Column of `channel_permission_overwrites` holding the target and its id
+fn overwrite_target_column(target: OverwriteTarget) -> (&'static str, Uuid) {
+    match target {
+        OverwriteTarget::Role(role_id) => ("role_id", role_id.0),
+        OverwriteTarget::Member(member_id) => ("member_id", member_id.0),
+    }
+}
+
 /// Internal struct for mapping database rows to Channel entities
 struct ChannelRow {
     id: Uuid,
@@ -281,6 +296,119 @@ impl ChannelRepository for PostgresChannelRepository {
             None => Err(CoreError::ChannelNotFound { id: channel_id }),
         }
     }
+
+    async fn list_permission_overwrites(
+        &self,
+        channel_id: ChannelId,
+    ) -> Result<Vec<PermissionOverwrite>, CoreError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT channel_id, role_id, member_id, allow_permissions, deny_permissions,
+                   created_at, updated_at
+            FROM channel_permission_overwrites
+            WHERE channel_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(channel_id.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to list permission overwrites: {}", e),
+        })?;
+
+        Ok(rows.iter().map(PermissionOverwrite::from).collect())
+    }
+
+    async fn upsert_permission_overwrite(
+        &self,
+        input: UpsertPermissionOverwriteInput,
+    ) -> Result<PermissionOverwrite, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        // Each target column has its own partial unique index
+        let (column, target_id) = overwrite_target_column(input.target);
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO channel_permission_overwrites
+                (channel_id, {column}, allow_permissions, deny_permissions)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (channel_id, {column}) WHERE {column} IS NOT NULL
+            DO UPDATE SET allow_permissions = EXCLUDED.allow_permissions,
+                          deny_permissions = EXCLUDED.deny_permissions,
+                          updated_at = NOW()
+            RETURNING channel_id, role_id, member_id, allow_permissions, deny_permissions,
+                      created_at, updated_at
+            "#
+        ))
+        .bind(input.channel_id.0)
+        .bind(target_id)
+        .bind(*input.allow)
+        .bind(*input.deny)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to upsert permission overwrite: {}", e),
+        })?;
+        let overwrite = PermissionOverwrite::from(&row);
+
+        OutboxEventRecord::new(self.upsert_overwrite_router.clone(), overwrite.clone())
+            .write(&mut *tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(overwrite)
+    }
+
+    async fn delete_permission_overwrite(
+        &self,
+        channel_id: ChannelId,
+        target: OverwriteTarget,
+    ) -> Result<(), CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let (column, target_id) = overwrite_target_column(target);
+        let result = sqlx::query(&format!(
+            r#"DELETE FROM channel_permission_overwrites WHERE channel_id = $1 AND {column} = $2"#
+        ))
+        .bind(channel_id.0)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to delete permission overwrite: {}", e),
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::PermissionOverwriteNotFound { channel_id });
+        }
+
+        let delete_event = DeletePermissionOverwriteEvent { channel_id, target };
+        OutboxEventRecord::new(self.delete_overwrite_router.clone(), delete_event)
+            .write(&mut *tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -318,8 +446,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.created");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository =
-            PostgresChannelRepository::new(pool.clone(), create_router.clone(), delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router.clone(),
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -375,7 +508,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository = PostgresChannelRepository::new(pool.clone(), create_router, delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router,
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let input = CreateChannelRepoInput {
             name: "my-dm".to_string(),
@@ -410,8 +549,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository =
-            PostgresChannelRepository::new(pool.clone(), create_router.clone(), delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router.clone(),
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -450,8 +594,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository =
-            PostgresChannelRepository::new(pool.clone(), create_router.clone(), delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router.clone(),
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -490,8 +639,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository =
-            PostgresChannelRepository::new(pool.clone(), create_router.clone(), delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router.clone(),
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -546,7 +700,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository = PostgresChannelRepository::new(pool.clone(), create_router, delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router,
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let nonexistent_id = ChannelId(Uuid::new_v4());
 
@@ -568,8 +728,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository =
-            PostgresChannelRepository::new(pool.clone(), create_router.clone(), delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router.clone(),
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -611,8 +776,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository =
-            PostgresChannelRepository::new(pool.clone(), create_router.clone(), delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router.clone(),
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -665,7 +835,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository = PostgresChannelRepository::new(pool.clone(), create_router, delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router,
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let nonexistent_id = ChannelId(Uuid::new_v4());
 
@@ -687,7 +863,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository = PostgresChannelRepository::new(pool.clone(), create_router, delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router,
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let nonexistent_id = ChannelId(Uuid::new_v4());
 
@@ -715,7 +897,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("channel.exchange");
         let delete_router = MessageRoutingInfo::new("channel.exchange");
 
-        let repository = PostgresChannelRepository::new(pool.clone(), create_router, delete_router);
+        let repository = PostgresChannelRepository::new(
+            pool.clone(),
+            create_router,
+            delete_router,
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
 
         let server_id = ServerId(Uuid::new_v4());
         create_test_server(&pool, server_id).await?;
@@ -1,8 +1,10 @@
+use chrono::{DateTime, Utc};
 use sqlx::{PgPool, query_as};
+use uuid::Uuid;
 
 use crate::{
     domain::{
-        common::{GetPaginated, TotalPaginatedElements},
+        common::{Cursor, CursorPage, GetPaginated, TotalPaginatedElements, next_page},
         friend::{
             entities::{DeleteFriendInput, Friend, FriendRequest, UserId},
             ports::FriendshipRepository,
@@ -59,6 +61,58 @@ impl FriendshipRepository for PostgresFriendshipRepository {
         Ok((friends, total_count as TotalPaginatedElements))
     }
 
+    async fn list_friends_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: &UserId,
+    ) -> Result<CursorPage<Friend>, FriendshipError> {
+        let total_count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM friends WHERE user_id_1 = $1 OR user_id_2 = $1",
+        )
+        .bind(user_id.0)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|_| FriendshipError::DatabaseError)?;
+
+        // The id of the other user breaks ties between friendships made at the same time
+        let friends = sqlx::query_as::<_, (Uuid, Uuid, DateTime<Utc>)>(
+            r#"
+            SELECT user_id_1, user_id_2, created_at
+            FROM friends
+            WHERE (user_id_1 = $1 OR user_id_2 = $1)
+              AND ($2::TIMESTAMPTZ IS NULL OR (
+                  created_at,
+                  CASE WHEN user_id_1 = $1 THEN user_id_2 ELSE user_id_1 END
+              ) < ($2, $3))
+            ORDER BY created_at DESC,
+                CASE WHEN user_id_1 = $1 THEN user_id_2 ELSE user_id_1 END DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(user_id.0)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.id))
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| FriendshipError::DatabaseError)?
+        .into_iter()
+        .map(|(user_id_1, user_id_2, created_at)| Friend {
+            user_id_1: UserId(user_id_1),
+            user_id_2: UserId(user_id_2),
+            created_at,
+        })
+        .collect();
+
+        Ok(next_page(
+            friends,
+            limit,
+            |friend| friend.cursor_for(user_id),
+            total_count as TotalPaginatedElements,
+        ))
+    }
+
     async fn get_friend(
         &self,
         user_id_1: &UserId,
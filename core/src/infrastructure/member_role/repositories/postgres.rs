@@ -1,17 +1,25 @@
-use sqlx::{PgPool, query_as};
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::{
     domain::{
         common::{CoreError, GetPaginated, TotalPaginatedElements},
         member_role::{
             entities::{
-                AssignMemberRole, AssignUserRole, MemberRole, UnassignMemberRole, UnassignUserRole,
+                AssignMemberRole, AssignUserRole, AssignedRole, MemberRole, MemberRolesChange,
+                UnassignMemberRole, UnassignUserRole,
             },
             ports::MemberRoleRepository,
         },
-        server_member::{self, ServerMember},
+        role::entities::RoleId,
+        server::entities::ServerId,
+        server_member::{self, MemberId, ServerMember},
+    },
+    infrastructure::{
+        MessageRoutingInfo, outbox::OutboxEventRecord, server_member::repositories::DISPLAY_COLOR,
     },
-    infrastructure::{MessageRoutingInfo, outbox::OutboxEventRecord},
 };
 
 #[derive(Clone, Debug)]
@@ -43,42 +51,50 @@ impl MemberRoleRepository for PostgresMemberRoleRepository {
             .await
             .map_err(|e| CoreError::Error { msg: e.to_string() })?;
 
-        let server_member = sqlx::query_as!(
-            ServerMember,
+        let row = sqlx::query(&format!(
             r#"
-            SELECT id, server_id, user_id, nickname, joined_at, updated_at
-            FROM server_members
-            WHERE id = $1 
+            SELECT id, server_id, user_id, nickname, joined_at, updated_at,
+                CASE WHEN timed_out_until > NOW() THEN timed_out_until END AS timed_out_until,
+                profile,
+                {}
+            FROM server_members sm
+            WHERE id = $1
             "#,
-            *member_role.member_id
-        )
+            DISPLAY_COLOR
+        ))
+        .bind(member_role.member_id.0)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to find member: {}", e),
         })?;
+        let server_member = ServerMember::from(&row);
 
         let assign_user = AssignUserRole {
             role_id: member_role.role_id,
             user_id: server_member.user_id,
         };
 
-        let member_role = query_as!(
-            MemberRole,
+        let member_role_row = sqlx::query(
             r#"
-            INSERT INTO member_roles (role_id, member_id)
-            VALUES ($1, $2)
-            RETURNING role_id, member_id, created_at, updated_at
+            INSERT INTO member_roles (role_id, member_id, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (member_id, role_id) DO UPDATE
+                SET expires_at = EXCLUDED.expires_at, updated_at = NOW()
+                WHERE member_roles.expires_at <= NOW()
+            RETURNING role_id, member_id, created_at, updated_at, expires_at
             "#,
-            *member_role.role_id,
-            *member_role.member_id
         )
+        .bind(*member_role.role_id)
+        .bind(*member_role.member_id)
+        .bind(member_role.expires_at)
         .fetch_one(&mut *tx)
         .await
         .map_err(|_| CoreError::AssignMemberRoleError {
             member_id: member_role.member_id,
             role_id: member_role.role_id,
         })?;
+        let member_role = MemberRole::from(&member_role_row);
 
         let assign_member_to_role_event =
             OutboxEventRecord::new(self.assign_role_routing.clone(), assign_user.clone());
@@ -99,33 +115,44 @@ impl MemberRoleRepository for PostgresMemberRoleRepository {
             .await
             .map_err(|e| CoreError::Error { msg: e.to_string() })?;
 
-        let server_member = sqlx::query_as!(
-            ServerMember,
+        // Only the call actually removing the assignment announces it, so concurrent
+        // unassignments of the same role publish a single event
+        let deleted: Option<Uuid> = sqlx::query_scalar(
+            r#"DELETE FROM member_roles WHERE member_id = $1 AND role_id = $2 RETURNING member_id"#,
+        )
+        .bind(*member_role.member_id)
+        .bind(*member_role.role_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        if deleted.is_none() {
+            return Ok(());
+        }
+
+        let row = sqlx::query(&format!(
             r#"
-            SELECT id, server_id, user_id, nickname, joined_at, updated_at
-            FROM server_members
-            WHERE id = $1 
+            SELECT id, server_id, user_id, nickname, joined_at, updated_at,
+                CASE WHEN timed_out_until > NOW() THEN timed_out_until END AS timed_out_until,
+                profile,
+                {}
+            FROM server_members sm
+            WHERE id = $1
             "#,
-            *member_role.member_id
-        )
+            DISPLAY_COLOR
+        ))
+        .bind(member_role.member_id.0)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to find member: {}", e),
         })?;
+        let server_member = ServerMember::from(&row);
 
         let unassign_user = UnassignUserRole {
             role_id: member_role.role_id,
             user_id: server_member.user_id,
         };
 
-        sqlx::query(r#"DELETE FROM member_roles WHERE member_id = $1 AND role_id = $2"#)
-            .bind(*member_role.member_id)
-            .bind(*member_role.role_id)
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
-
         let unassign_member_from_role_event =
             OutboxEventRecord::new(self.unassign_role_routing.clone(), unassign_user);
 
@@ -136,42 +163,215 @@ impl MemberRoleRepository for PostgresMemberRoleRepository {
         Ok(())
     }
 
+    async fn assign_many(
+        &self,
+        role_id: &RoleId,
+        members: &[ServerMember],
+    ) -> Result<Vec<MemberRole>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Error { msg: e.to_string() })?;
+
+        let member_ids: Vec<Uuid> = members.iter().map(|member| member.id.0).collect();
+        let member_roles: Vec<MemberRole> = sqlx::query(
+            r#"
+            INSERT INTO member_roles (role_id, member_id)
+            SELECT $1, UNNEST($2::UUID[])
+            ON CONFLICT (member_id, role_id) DO UPDATE
+                SET expires_at = EXCLUDED.expires_at, updated_at = NOW()
+                WHERE member_roles.expires_at <= NOW()
+            RETURNING role_id, member_id, created_at, updated_at, expires_at
+            "#,
+        )
+        .bind(role_id.0)
+        .bind(&member_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to assign role: {}", e),
+        })?
+        .iter()
+        .map(MemberRole::from)
+        .collect();
+
+        // One event per member, as if each had been assigned on its own
+        for member_role in &member_roles {
+            let Some(member) = members.iter().find(|m| m.id == member_role.member_id) else {
+                continue;
+            };
+            let assign_user = AssignUserRole {
+                role_id: *role_id,
+                user_id: member.user_id,
+            };
+            OutboxEventRecord::new(self.assign_role_routing.clone(), assign_user)
+                .write(&mut *tx)
+                .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Error { msg: e.to_string() })?;
+
+        Ok(member_roles)
+    }
+
+    async fn unassign_many(
+        &self,
+        role_id: &RoleId,
+        members: &[ServerMember],
+    ) -> Result<Vec<MemberId>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Error { msg: e.to_string() })?;
+
+        let member_ids: Vec<Uuid> = members.iter().map(|member| member.id.0).collect();
+        let unassigned: Vec<MemberId> = sqlx::query_scalar::<_, Uuid>(
+            r#"DELETE FROM member_roles WHERE role_id = $1 AND member_id = ANY($2) RETURNING member_id"#,
+        )
+        .bind(role_id.0)
+        .bind(&member_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+        .into_iter()
+        .map(MemberId)
+        .collect();
+
+        for member in members.iter().filter(|m| unassigned.contains(&m.id)) {
+            let unassign_user = UnassignUserRole {
+                role_id: *role_id,
+                user_id: member.user_id,
+            };
+            OutboxEventRecord::new(self.unassign_role_routing.clone(), unassign_user)
+                .write(&mut *tx)
+                .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Error { msg: e.to_string() })?;
+
+        Ok(unassigned)
+    }
+
+    async fn change_member_roles(
+        &self,
+        member: &ServerMember,
+        change: &MemberRolesChange,
+    ) -> Result<MemberRolesChange, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::Error { msg: e.to_string() })?;
+
+        let removed_ids: Vec<Uuid> = change.removed.iter().map(|role_id| role_id.0).collect();
+        let removed: Vec<RoleId> = sqlx::query_scalar::<_, Uuid>(
+            r#"DELETE FROM member_roles WHERE member_id = $1 AND role_id = ANY($2) RETURNING role_id"#,
+        )
+        .bind(member.id.0)
+        .bind(&removed_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+        .into_iter()
+        .map(RoleId)
+        .collect();
+
+        let assigned_ids: Vec<Uuid> = change.assigned.iter().map(|role_id| role_id.0).collect();
+        let assigned: Vec<RoleId> = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO member_roles (role_id, member_id)
+            SELECT UNNEST($1::UUID[]), $2
+            ON CONFLICT (member_id, role_id) DO UPDATE
+                SET expires_at = EXCLUDED.expires_at, updated_at = NOW()
+                WHERE member_roles.expires_at <= NOW()
+            RETURNING role_id
+            "#,
+        )
+        .bind(&assigned_ids)
+        .bind(member.id.0)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to assign roles: {}", e),
+        })?
+        .into_iter()
+        .map(RoleId)
+        .collect();
+
+        // Only the roles that actually changed are announced
+        for role_id in &removed {
+            let unassign_user = UnassignUserRole {
+                role_id: *role_id,
+                user_id: member.user_id,
+            };
+            OutboxEventRecord::new(self.unassign_role_routing.clone(), unassign_user)
+                .write(&mut *tx)
+                .await?;
+        }
+        for role_id in &assigned {
+            let assign_user = AssignUserRole {
+                role_id: *role_id,
+                user_id: member.user_id,
+            };
+            OutboxEventRecord::new(self.assign_role_routing.clone(), assign_user)
+                .write(&mut *tx)
+                .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::Error { msg: e.to_string() })?;
+
+        Ok(MemberRolesChange { assigned, removed })
+    }
+
     async fn list_members_by_role(
         &self,
         role_id: &crate::domain::role::entities::RoleId,
         pagination: &GetPaginated,
     ) -> Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError> {
-        let offset = (pagination.page - 1) * pagination.limit;
+        // The offset follows the capped limit so that pages neither overlap nor skip members
         let limit = std::cmp::min(pagination.limit, 50) as i64;
+        let offset = pagination.page.saturating_sub(1) as i64 * limit;
 
-        let members = sqlx::query_as!(
-            ServerMember,
+        let rows = sqlx::query(&format!(
             r#"
-            SELECT sm.id, sm.server_id, sm.user_id, sm.nickname, sm.joined_at, sm.updated_at
+            SELECT sm.id, sm.server_id, sm.user_id, sm.nickname, sm.joined_at, sm.updated_at,
+                CASE WHEN sm.timed_out_until > NOW() THEN sm.timed_out_until END AS timed_out_until,
+                sm.profile,
+                {}
             FROM server_members sm
             INNER JOIN member_roles mr ON mr.member_id = sm.id
-            WHERE mr.role_id = $1
-            ORDER BY sm.joined_at DESC
+            WHERE mr.role_id = $1 AND (mr.expires_at IS NULL OR mr.expires_at > NOW())
+            ORDER BY sm.joined_at DESC, sm.id DESC
             LIMIT $2 OFFSET $3
             "#,
-            **role_id,
-            limit,
-            offset as i64
-        )
+            DISPLAY_COLOR
+        ))
+        .bind(**role_id)
+        .bind(limit)
+        .bind(offset)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to list members by role: {}", e),
         })?;
+        let members = rows.iter().map(ServerMember::from).collect();
 
-        let total = sqlx::query_scalar!(
+        let total = sqlx::query_scalar::<_, i64>(
             r#"
-            SELECT COUNT(*) as "count!"
+            SELECT COUNT(*)
             FROM member_roles
-            WHERE role_id = $1
+            WHERE role_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
             "#,
-            **role_id
         )
+        .bind(**role_id)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| CoreError::DatabaseError {
@@ -181,62 +381,143 @@ impl MemberRoleRepository for PostgresMemberRoleRepository {
         Ok((members, total as u64))
     }
 
+    async fn count_members_by_role(
+        &self,
+        server_id: ServerId,
+    ) -> Result<HashMap<RoleId, u64>, CoreError> {
+        let counts = sqlx::query_as::<_, (Uuid, i64)>(
+            r#"
+            SELECT mr.role_id, COUNT(*)
+            FROM member_roles mr
+            INNER JOIN roles r ON r.id = mr.role_id
+            WHERE r.server_id = $1 AND (mr.expires_at IS NULL OR mr.expires_at > NOW())
+            GROUP BY mr.role_id
+            "#,
+        )
+        .bind(*server_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to count members by role: {}", e),
+        })?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(role_id, count)| (RoleId(role_id), count as u64))
+            .collect())
+    }
+
     async fn list_roles_by_user_and_server(
         &self,
         user_id: crate::domain::friend::entities::UserId,
         server_id: crate::domain::server::entities::ServerId,
     ) -> Result<Vec<crate::domain::role::entities::Role>, CoreError> {
-        let roles = sqlx::query_as!(
-            crate::domain::role::entities::Role,
+        let rows = sqlx::query(
+            r#"
+            SELECT r.id, r.server_id, r.name, r.permissions, r.position, r.color, r.hoist,
+                   r.is_default, r.mentionable, r.has_icon, r.created_at, r.updated_at
+            FROM roles r
+            INNER JOIN member_roles mr ON mr.role_id = r.id
+            INNER JOIN server_members sm ON sm.id = mr.member_id
+            WHERE sm.user_id = $1 AND r.server_id = $2
+                AND (mr.expires_at IS NULL OR mr.expires_at > NOW())
+            ORDER BY r.created_at ASC
+            "#,
+        )
+        .bind(*user_id)
+        .bind(*server_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to list roles by user and server: {}", e),
+        })?;
+
+        Ok(rows.iter().map(Into::into).collect())
+    }
+
+    async fn list_assigned_roles_by_user_and_server(
+        &self,
+        user_id: crate::domain::friend::entities::UserId,
+        server_id: crate::domain::server::entities::ServerId,
+    ) -> Result<Vec<AssignedRole>, CoreError> {
+        let rows = sqlx::query(
             r#"
-            SELECT r.id, r.server_id, r.name, r.permissions as "permissions: _", r.created_at, r.updated_at
+            SELECT r.id, r.server_id, r.name, r.permissions, r.position, r.color, r.hoist,
+                   r.is_default, r.mentionable, r.has_icon, r.created_at, r.updated_at, mr.expires_at
             FROM roles r
             INNER JOIN member_roles mr ON mr.role_id = r.id
             INNER JOIN server_members sm ON sm.id = mr.member_id
             WHERE sm.user_id = $1 AND r.server_id = $2
+                AND (mr.expires_at IS NULL OR mr.expires_at > NOW())
             ORDER BY r.created_at ASC
             "#,
-            *user_id,
-            *server_id
         )
+        .bind(*user_id)
+        .bind(*server_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to list roles by user and server: {}", e),
         })?;
 
-        Ok(roles)
+        Ok(rows.iter().map(AssignedRole::from).collect())
+    }
+
+    async fn list_expired_assignments(&self, limit: u32) -> Result<Vec<MemberRole>, CoreError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT role_id, member_id, created_at, updated_at, expires_at
+            FROM member_roles
+            WHERE expires_at <= NOW()
+            ORDER BY expires_at ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to list expired role assignments: {}", e),
+        })?;
+
+        Ok(rows.iter().map(MemberRole::from).collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use sqlx::{PgPool, query_as};
+    use chrono::{Duration, Utc};
+    use sqlx::PgPool;
     use uuid::Uuid;
 
     use crate::{
         domain::{
             common::CoreError,
+            friend::entities::UserId,
             member_role::{
-                entities::{AssignMemberRole, UnassignMemberRole},
+                entities::{AssignMemberRole, MemberRolesChange, UnassignMemberRole},
                 ports::MemberRoleRepository,
             },
             role::entities::{Permissions, Role, RoleId},
-            server::entities::ServerVisibility,
+            server::entities::{ServerId, ServerVisibility},
             server_member::{MemberId, ServerMember},
         },
         infrastructure::{
             MessageRoutingInfo, member_role::repositories::postgres::PostgresMemberRoleRepository,
+            server_member::repositories::DISPLAY_COLOR,
         },
     };
     pub async fn create_test_member(pool: &PgPool, server_id: Uuid) -> Uuid {
-        let row = sqlx::query(
+        let row = sqlx::query(&format!(
             r#"
-            INSERT INTO server_members (id, server_id, user_id)
+            INSERT INTO server_members AS sm (id, server_id, user_id)
             VALUES ($1, $2, $3)
-            RETURNING id, server_id, user_id, nickname, joined_at, updated_at
+            RETURNING id, server_id, user_id, nickname, joined_at, updated_at, timed_out_until,
+                profile,
+                {}
             "#,
-        )
+            DISPLAY_COLOR
+        ))
         .bind(Uuid::new_v4())
         .bind(server_id)
         .bind(Uuid::new_v4())
@@ -249,23 +530,27 @@ mod tests {
     }
 
     async fn create_test_role(pool: &PgPool, server_id: Uuid) -> Uuid {
+        create_named_test_role(pool, server_id, "default_test_role").await
+    }
+
+    async fn create_named_test_role(pool: &PgPool, server_id: Uuid, name: &str) -> Uuid {
         let permission = Permissions(0x1);
 
-        let role = query_as!(
-            Role,
+        let row = sqlx::query(
             r#"
             INSERT INTO roles (server_id, name, permissions)
             VALUES ($1, $2, $3)
-            RETURNING id, server_id, name, permissions as "permissions: _", created_at, updated_at
+            RETURNING id, server_id, name, permissions, position, color, hoist, mentionable,
+                      is_default, has_icon, created_at, updated_at
             "#,
-            server_id,
-            "default_test_role",
-            *permission
         )
+        .bind(server_id)
+        .bind(name)
+        .bind(*permission)
         .fetch_one(pool)
         .await
         .unwrap();
-        *role.id
+        *Role::from(&row).id
     }
 
     async fn create_test_server(pool: &PgPool, name: &str) -> Uuid {
@@ -299,6 +584,7 @@ mod tests {
         let assign_member_role = AssignMemberRole {
             member_id: MemberId(member_id),
             role_id: RoleId(role_id),
+            expires_at: None,
         };
         let assigned = repository.assign(assign_member_role).await.unwrap();
 
@@ -314,7 +600,7 @@ mod tests {
         let repository = PostgresMemberRoleRepository::new(
             pool.clone(),
             assign_role_routing,
-            unassign_role_routing,
+            unassign_role_routing.clone(),
         );
         let server_id = create_test_server(&pool.clone(), "test_server").await;
         let role_id = create_test_role(&pool.clone(), server_id).await;
@@ -322,13 +608,289 @@ mod tests {
         let assign_member_role = AssignMemberRole {
             member_id: MemberId(member_id),
             role_id: RoleId(role_id),
+            expires_at: None,
         };
         let _ = repository.assign(assign_member_role).await.unwrap();
         let unassign = UnassignMemberRole {
             member_id: MemberId(member_id),
             role_id: RoleId(role_id),
         };
+        repository.unassign(unassign.clone()).await.unwrap();
+
+        // Assert: unassigning a role no longer held publishes nothing
         repository.unassign(unassign).await.unwrap();
+        let events: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM outbox_messages WHERE exchange_name = $1")
+                .bind(unassign_role_routing.exchange_name())
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(events, 1);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_change_member_roles_only_applies_actual_changes(
+        pool: PgPool,
+    ) -> Result<(), CoreError> {
+        let repository = PostgresMemberRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test_assign"),
+            MessageRoutingInfo::new("test_unassign"),
+        );
+        let server_id = create_test_server(&pool, "test_server").await;
+        let held_role = RoleId(create_named_test_role(&pool, server_id, "held").await);
+        let new_role = RoleId(create_named_test_role(&pool, server_id, "new").await);
+        let not_held_role = RoleId(create_named_test_role(&pool, server_id, "not_held").await);
+        let member_id = create_test_member(&pool, server_id).await;
+        repository
+            .assign(AssignMemberRole {
+                member_id: MemberId(member_id),
+                role_id: held_role,
+                expires_at: None,
+            })
+            .await?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT id, server_id, user_id, nickname, joined_at, updated_at, timed_out_until,
+                profile,
+                {}
+            FROM server_members sm
+            WHERE id = $1
+            "#,
+            DISPLAY_COLOR
+        ))
+        .bind(member_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let member = ServerMember::from(&row);
+
+        let applied = repository
+            .change_member_roles(
+                &member,
+                &MemberRolesChange {
+                    assigned: vec![new_role],
+                    removed: vec![held_role, not_held_role],
+                },
+            )
+            .await?;
+
+        assert_eq!(applied.assigned, vec![new_role]);
+        assert_eq!(applied.removed, vec![held_role]);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_expired_assignments_are_no_longer_held(pool: PgPool) -> Result<(), CoreError> {
+        let repository = PostgresMemberRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test_assign"),
+            MessageRoutingInfo::new("test_unassign"),
+        );
+        let server_id = create_test_server(&pool, "test_server").await;
+        let expired_role = RoleId(create_named_test_role(&pool, server_id, "expired").await);
+        let lasting_role = RoleId(create_named_test_role(&pool, server_id, "lasting").await);
+        let member_id = MemberId(create_test_member(&pool, server_id).await);
+        repository
+            .assign(AssignMemberRole {
+                member_id,
+                role_id: lasting_role,
+                expires_at: Some(Utc::now() + Duration::days(1)),
+            })
+            .await?;
+        // Expiring right away, as if the sweeper had not run yet
+        repository
+            .assign(AssignMemberRole {
+                member_id,
+                role_id: expired_role,
+                expires_at: Some(Utc::now()),
+            })
+            .await?;
+
+        let expired = repository.list_expired_assignments(10).await?;
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].role_id, expired_role);
+
+        let user_id =
+            sqlx::query_scalar::<_, Uuid>("SELECT user_id FROM server_members WHERE id = $1")
+                .bind(*member_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let held = repository
+            .list_assigned_roles_by_user_and_server(UserId(user_id), ServerId(server_id))
+            .await?;
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].role.id, lasting_role);
+        assert!(held[0].expires_at.is_some());
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_expired_assignments_can_be_granted_again(pool: PgPool) -> Result<(), CoreError> {
+        let repository = PostgresMemberRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test_assign"),
+            MessageRoutingInfo::new("test_unassign"),
+        );
+        let server_id = create_test_server(&pool, "test_server").await;
+        let single_role = RoleId(create_named_test_role(&pool, server_id, "single").await);
+        let bulk_role = RoleId(create_named_test_role(&pool, server_id, "bulk").await);
+        let set_role = RoleId(create_named_test_role(&pool, server_id, "set").await);
+        let member_id = MemberId(create_test_member(&pool, server_id).await);
+
+        // Arrange: every assignment expired without the sweeper having removed it
+        for role_id in [single_role, bulk_role, set_role] {
+            repository
+                .assign(AssignMemberRole {
+                    member_id,
+                    role_id,
+                    expires_at: Some(Utc::now()),
+                })
+                .await?;
+        }
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT id, server_id, user_id, nickname, joined_at, updated_at, timed_out_until,
+                profile,
+                {}
+            FROM server_members sm
+            WHERE id = $1
+            "#,
+            DISPLAY_COLOR
+        ))
+        .bind(*member_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let member = ServerMember::from(&row);
+
+        // Act: grant the roles again through each path
+        let assigned = repository
+            .assign(AssignMemberRole {
+                member_id,
+                role_id: single_role,
+                expires_at: None,
+            })
+            .await?;
+        assert_eq!(assigned.expires_at, None);
+        let assigned = repository
+            .assign_many(&bulk_role, std::slice::from_ref(&member))
+            .await?;
+        assert_eq!(assigned.len(), 1);
+        let applied = repository
+            .change_member_roles(
+                &member,
+                &MemberRolesChange {
+                    assigned: vec![set_role],
+                    removed: vec![],
+                },
+            )
+            .await?;
+        assert_eq!(applied.assigned, vec![set_role]);
+
+        // Assert: the roles are held again and no longer swept
+        let held = repository
+            .list_assigned_roles_by_user_and_server(member.user_id, ServerId(server_id))
+            .await?;
+        for role_id in [single_role, bulk_role, set_role] {
+            assert!(held.iter().any(|assigned| assigned.role.id == role_id));
+        }
+        assert!(repository.list_expired_assignments(10).await?.is_empty());
+
+        // Assert: a role still held is not granted twice
+        let result = repository
+            .assign(AssignMemberRole {
+                member_id,
+                role_id: single_role,
+                expires_at: None,
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(CoreError::AssignMemberRoleError { .. })
+        ));
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_count_members_by_role(pool: PgPool) -> Result<(), CoreError> {
+        let repository = PostgresMemberRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test_assign"),
+            MessageRoutingInfo::new("test_unassign"),
+        );
+        let server_id = create_test_server(&pool, "test_server").await;
+        let held_role = RoleId(create_named_test_role(&pool, server_id, "held").await);
+        let unheld_role = RoleId(create_named_test_role(&pool, server_id, "unheld").await);
+        for _ in 0..3 {
+            let member_id = MemberId(create_test_member(&pool, server_id).await);
+            repository
+                .assign(AssignMemberRole {
+                    member_id,
+                    role_id: held_role,
+                    expires_at: None,
+                })
+                .await?;
+        }
+
+        let counts = repository
+            .count_members_by_role(ServerId(server_id))
+            .await?;
+        assert_eq!(counts.get(&held_role), Some(&3));
+        assert_eq!(counts.get(&unheld_role), None);
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_default_role_is_held_by_every_member(pool: PgPool) -> Result<(), CoreError> {
+        let repository = PostgresMemberRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test_assign"),
+            MessageRoutingInfo::new("test_unassign"),
+        );
+        let server_id = create_test_server(&pool, "test_server").await;
+        sqlx::query(
+            r#"
+            INSERT INTO roles (id, server_id, name, permissions, is_default)
+            VALUES ($1, $1, 'BasicUser', 0, TRUE)
+            "#,
+        )
+        .bind(server_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let member_id = MemberId(create_test_member(&pool, server_id).await);
+
+        let held: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM member_roles WHERE member_id = $1 AND role_id = $2",
+        )
+        .bind(*member_id)
+        .bind(server_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(held, 1);
+
+        let unassigned = repository
+            .unassign(UnassignMemberRole {
+                role_id: RoleId(server_id),
+                member_id,
+            })
+            .await;
+        assert!(unassigned.is_err());
+        let deleted = sqlx::query("DELETE FROM roles WHERE id = $1")
+            .bind(server_id)
+            .execute(&pool)
+            .await;
+        assert!(deleted.is_err());
+        let renamed = sqlx::query("UPDATE roles SET name = 'Everyone' WHERE id = $1")
+            .bind(server_id)
+            .execute(&pool)
+            .await;
+        assert!(renamed.is_err());
         Ok(())
     }
 }
@@ -6,7 +6,9 @@ pub mod member_role;
 pub mod outbox;
 pub mod role;
 pub mod server;
+pub mod server_ban;
 pub mod server_invitation;
+pub mod server_join_request;
 pub mod server_member;
 pub mod server_pictures;
 pub mod user;
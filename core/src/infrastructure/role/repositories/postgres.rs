@@ -1,11 +1,13 @@
-use sqlx::{PgPool, query_as};
+use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
 use crate::{
     domain::{
         common::{CoreError, GetPaginated, TotalPaginatedElements},
         role::{
-            entities::{CreateRoleInput, DeleteRole, Role, RoleId, UpdateRoleRepoInput},
+            entities::{
+                CreateRoleInput, DeleteRole, Role, RoleAppearanceEvent, RoleId, UpdateRoleRepoInput,
+            },
             ports::RoleRepository,
         },
     },
@@ -18,6 +20,7 @@ pub struct PostgresRoleRepository {
     create_role_router: MessageRoutingInfo,
     update_role_router: MessageRoutingInfo,
     delete_role_router: MessageRoutingInfo,
+    role_appearance_router: MessageRoutingInfo,
 }
 
 impl PostgresRoleRepository {
@@ -26,16 +29,22 @@ impl PostgresRoleRepository {
         create_role_router: MessageRoutingInfo,
         update_role_router: MessageRoutingInfo,
         delete_role_router: MessageRoutingInfo,
+        role_appearance_router: MessageRoutingInfo,
     ) -> Self {
         Self {
             pool,
             create_role_router,
             update_role_router,
             delete_role_router,
+            role_appearance_router,
         }
     }
 }
 
+/// Columns mapped onto a `Role`
+pub(crate) const ROLE_COLUMNS: &str = "id, server_id, name, permissions, position, color, hoist, \
+     is_default, mentionable, has_icon, created_at, updated_at";
+
 impl RoleRepository for PostgresRoleRepository {
     async fn create(&self, input: CreateRoleInput) -> Result<Role, CoreError> {
         let mut tx = self
@@ -44,25 +53,48 @@ impl RoleRepository for PostgresRoleRepository {
             .await
             .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
 
-        let role = query_as!(
-            Role,
+        // New roles enter the hierarchy right above the default role
+        sqlx::query(
             r#"
-            INSERT INTO roles (server_id, name, permissions)
-            VALUES ($1, $2, $3)
-            RETURNING id, server_id, name, permissions as "permissions: _", created_at, updated_at
+            UPDATE roles
+            SET position = position + 1
+            WHERE server_id = $1 AND position >= 1
             "#,
-            input.server_id,
-            input.name,
-            input.permissions.0
         )
+        .bind(input.server_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        let role_row = sqlx::query(&format!(
+            r#"
+            INSERT INTO roles (server_id, name, permissions, position, color, hoist, mentionable, has_icon)
+            VALUES ($1, $2, $3, 1, $4, $5, $6, $7)
+            RETURNING {ROLE_COLUMNS}
+            "#
+        ))
+        .bind(input.server_id)
+        .bind(&input.name)
+        .bind(input.permissions.0)
+        .bind(input.appearance.color)
+        .bind(input.appearance.hoist)
+        .bind(input.appearance.mentionable)
+        .bind(input.appearance.has_icon)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        let role = Role::from(&role_row);
 
         // Write the create event to the outbox table for eventual processing
         let create_role_event =
             OutboxEventRecord::new(self.create_role_router.clone(), role.clone());
         create_role_event.write(&mut *tx).await?;
+        OutboxEventRecord::new(
+            self.role_appearance_router.clone(),
+            RoleAppearanceEvent::from(&role),
+        )
+        .write(&mut *tx)
+        .await?;
 
         tx.commit()
             .await
@@ -72,21 +104,14 @@ impl RoleRepository for PostgresRoleRepository {
     }
 
     async fn find_by_id(&self, id: &RoleId) -> Result<Role, CoreError> {
-        let role = query_as!(
-            Role,
-            r#"
-            SELECT id, server_id, name, permissions as "permissions: _", created_at, updated_at
-            FROM roles
-            WHERE id = $1
-            "#,
-            id.0
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        let row = sqlx::query(&format!("SELECT {ROLE_COLUMNS} FROM roles WHERE id = $1"))
+            .bind(id.0)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
 
-        match role {
-            Some(r) => Ok(r),
+        match row {
+            Some(row) => Ok((&row).into()),
             None => Err(CoreError::RoleNotFound { id: *id }),
         }
     }
@@ -107,26 +132,45 @@ impl RoleRepository for PostgresRoleRepository {
             .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
 
         // Get paginated roles for the server
-        let roles = query_as!(
-            Role,
+        let roles: Vec<Role> = sqlx::query(&format!(
             r#"
-            SELECT id, server_id, name, permissions as "permissions: _", created_at, updated_at
+            SELECT {ROLE_COLUMNS}
             FROM roles
             WHERE server_id = $1
-            ORDER BY created_at DESC
+            ORDER BY position DESC, created_at DESC
             LIMIT $2 OFFSET $3
-            "#,
-            server_id,
-            limit,
-            offset as i64
-        )
+            "#
+        ))
+        .bind(server_id)
+        .bind(limit)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
-        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+        .iter()
+        .map(Role::from)
+        .collect();
 
         Ok((roles, total as u64))
     }
 
+    async fn list_in_server(&self, server_id: Uuid) -> Result<Vec<Role>, CoreError> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {ROLE_COLUMNS}
+            FROM roles
+            WHERE server_id = $1
+            ORDER BY position DESC, created_at DESC
+            "#
+        ))
+        .bind(server_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        Ok(rows.iter().map(Role::from).collect())
+    }
+
     async fn update(&self, input: UpdateRoleRepoInput) -> Result<Role, CoreError> {
         let mut tx = self
             .pool
@@ -135,45 +179,53 @@ impl RoleRepository for PostgresRoleRepository {
             .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
 
         // First, fetch the current role to get existing values
-        let current = query_as!(
-            Role,
-            r#"
-            SELECT id, server_id, name, permissions as "permissions: _", created_at, updated_at
-            FROM roles
-            WHERE id = $1
-            "#,
-            input.id.0
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
-        .ok_or_else(|| CoreError::RoleNotFound { id: input.id })?;
+        let current_row = sqlx::query(&format!("SELECT {ROLE_COLUMNS} FROM roles WHERE id = $1"))
+            .bind(input.id.0)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+            .ok_or_else(|| CoreError::RoleNotFound { id: input.id })?;
+        let current = Role::from(&current_row);
 
         // Apply updates, falling back to current values if not provided
         let new_name = input.name.as_ref().unwrap_or(&current.name);
         let new_permissions = input.permissions.as_ref().unwrap_or(&current.permissions);
+        let new_appearance = input.appearance.apply(&current.appearance);
 
         // Update the role in the database
-        let role = query_as!(
-            Role,
+        let role_row = sqlx::query(&format!(
             r#"
             UPDATE roles
-            SET name = $1, permissions = $2, updated_at = NOW()
-            WHERE id = $3
-            RETURNING id, server_id, name, permissions as "permissions: _", created_at, updated_at
-            "#,
-            new_name,
-            new_permissions.0,
-            input.id.0
-        )
+            SET name = $1, permissions = $2, color = $3, hoist = $4, mentionable = $5,
+                has_icon = $6, updated_at = NOW()
+            WHERE id = $7
+            RETURNING {ROLE_COLUMNS}
+            "#
+        ))
+        .bind(new_name)
+        .bind(new_permissions.0)
+        .bind(new_appearance.color)
+        .bind(new_appearance.hoist)
+        .bind(new_appearance.mentionable)
+        .bind(new_appearance.has_icon)
+        .bind(input.id.0)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        let role = Role::from(&role_row);
 
         // Write the update event to the outbox table for eventual processing
         let update_role_event =
             OutboxEventRecord::new(self.update_role_router.clone(), role.clone());
         update_role_event.write(&mut *tx).await?;
+        if role.appearance != current.appearance {
+            OutboxEventRecord::new(
+                self.role_appearance_router.clone(),
+                RoleAppearanceEvent::from(&role),
+            )
+            .write(&mut *tx)
+            .await?;
+        }
 
         tx.commit()
             .await
@@ -189,15 +241,29 @@ impl RoleRepository for PostgresRoleRepository {
             .await
             .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
 
-        let result = sqlx::query(r#"DELETE FROM roles WHERE id = $1"#)
-            .bind(id.0)
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
-
-        if result.rows_affected() == 0 {
-            return Err(CoreError::RoleNotFound { id: *id });
-        }
+        let deleted =
+            sqlx::query(r#"DELETE FROM roles WHERE id = $1 RETURNING server_id, position"#)
+                .bind(id.0)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+                .ok_or(CoreError::RoleNotFound { id: *id })?;
+
+        // Close the gap left in the hierarchy
+        let server_id: Uuid = deleted.get("server_id");
+        let position: i32 = deleted.get("position");
+        sqlx::query(
+            r#"
+            UPDATE roles
+            SET position = position - 1
+            WHERE server_id = $1 AND position > $2
+            "#,
+        )
+        .bind(server_id)
+        .bind(position)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
 
         // Write the delete event to the outbox table for eventual processing
         let delete_role_event =
@@ -210,15 +276,69 @@ impl RoleRepository for PostgresRoleRepository {
 
         Ok(())
     }
+
+    async fn reorder(&self, server_id: Uuid, role_ids: &[RoleId]) -> Result<Vec<Role>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        for (index, role_id) in role_ids.iter().enumerate() {
+            // Only the roles that actually move are touched and announced
+            let moved = sqlx::query(&format!(
+                r#"
+                UPDATE roles
+                SET position = $1, updated_at = NOW()
+                WHERE id = $2 AND server_id = $3 AND position <> $1
+                RETURNING {ROLE_COLUMNS}
+                "#
+            ))
+            .bind(index as i32 + 1)
+            .bind(role_id.0)
+            .bind(server_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+            .map(|row| Role::from(&row));
+
+            if let Some(role) = moved {
+                OutboxEventRecord::new(self.update_role_router.clone(), role)
+                    .write(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let roles: Vec<Role> = sqlx::query(&format!(
+            r#"
+            SELECT {ROLE_COLUMNS}
+            FROM roles
+            WHERE server_id = $1
+            ORDER BY position DESC, created_at DESC
+            "#
+        ))
+        .bind(server_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+        .iter()
+        .map(Role::from)
+        .collect();
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        Ok(roles)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::role::entities::Permissions;
+    use crate::domain::role::entities::{Permissions, RoleAppearance, UpdateRoleAppearance};
     use crate::domain::server::entities::ServerVisibility;
     use crate::infrastructure::outbox::MessageRouter;
-    use sqlx::Row;
 
     async fn create_test_server(pool: &PgPool, name: &str) -> Uuid {
         let owner_id = Uuid::new_v4();
@@ -243,14 +363,20 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo =
-            PostgresRoleRepository::new(pool.clone(), create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let server_id = create_test_server(&pool, "Test Server").await;
 
         let input = CreateRoleInput {
             server_id,
             name: "Admin".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
 
         let role = repo.create(input).await.unwrap();
@@ -277,6 +403,7 @@ mod tests {
             create_router.clone(),
             update_router,
             delete_router,
+            MessageRoutingInfo::new("test"),
         );
         let server_id = create_test_server(&pool, "Test Server").await;
 
@@ -284,6 +411,7 @@ mod tests {
             server_id,
             name: "Admin".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
 
         let _role = repo.create(input.clone()).await.unwrap();
@@ -324,7 +452,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo = PostgresRoleRepository::new(pool, create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool,
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let nonexistent_id = RoleId(Uuid::new_v4());
 
         let result = repo.find_by_id(&nonexistent_id).await;
@@ -341,8 +475,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo =
-            PostgresRoleRepository::new(pool.clone(), create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let server_id = create_test_server(&pool, "Test Server").await;
 
         // Create 5 roles
@@ -351,6 +490,7 @@ mod tests {
                 server_id,
                 name: format!("Role {}", i),
                 permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
             };
             repo.create(input).await.unwrap();
         }
@@ -373,8 +513,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo =
-            PostgresRoleRepository::new(pool.clone(), create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let server1_id = create_test_server(&pool, "Server 1").await;
         let server2_id = create_test_server(&pool, "Server 2").await;
 
@@ -384,6 +529,7 @@ mod tests {
                 server_id: server1_id,
                 name: format!("Server1 Role {}", i),
                 permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
             };
             repo.create(input).await.unwrap();
         }
@@ -394,6 +540,7 @@ mod tests {
                 server_id: server2_id,
                 name: format!("Server2 Role {}", i),
                 permissions: Permissions::try_from(0x2).unwrap(),
+                appearance: RoleAppearance::default(),
             };
             repo.create(input).await.unwrap();
         }
@@ -417,14 +564,20 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo =
-            PostgresRoleRepository::new(pool.clone(), create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let server_id = create_test_server(&pool, "Test Server").await;
 
         let input = CreateRoleInput {
             server_id,
             name: "Original Name".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
         let role = repo.create(input).await.unwrap();
 
@@ -433,18 +586,27 @@ mod tests {
             id: role.id,
             name: Some("Updated Name".to_string()),
             permissions: Some(Permissions::try_from(0x3).unwrap()),
+            appearance: UpdateRoleAppearance {
+                color: Some(0x3498DB),
+                hoist: Some(true),
+                ..Default::default()
+            },
         };
         let updated = repo.update(update_input).await.unwrap();
 
         assert_eq!(updated.id, role.id);
         assert_eq!(updated.name, "Updated Name");
         assert_eq!(updated.permissions.0, 0x3);
+        assert_eq!(updated.appearance.color, 0x3498DB);
+        assert!(updated.appearance.hoist);
+        assert!(!updated.appearance.mentionable);
         assert!(updated.updated_at.is_some());
 
         // Verify changes persisted
         let fetched = repo.find_by_id(&role.id).await.unwrap();
         assert_eq!(fetched.name, "Updated Name");
         assert_eq!(fetched.permissions.0, 0x3);
+        assert_eq!(fetched.appearance.color, 0x3498DB);
     }
 
     #[sqlx::test(migrations = "./migrations")]
@@ -457,6 +619,7 @@ mod tests {
             create_router,
             update_router.clone(),
             delete_router,
+            MessageRoutingInfo::new("test"),
         );
         let server_id = create_test_server(&pool, "Test Server").await;
 
@@ -464,6 +627,7 @@ mod tests {
             server_id,
             name: "Original Name".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
         let role = repo.create(input).await.unwrap();
 
@@ -472,6 +636,7 @@ mod tests {
             id: role.id,
             name: Some("Updated Name".to_string()),
             permissions: Some(Permissions::try_from(0x3).unwrap()),
+            appearance: UpdateRoleAppearance::default(),
         };
         repo.update(update_input).await.unwrap();
 
@@ -509,18 +674,86 @@ mod tests {
         );
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_appearance_changes_write_appearance_outbox(pool: PgPool) {
+        let appearance_router = MessageRoutingInfo::new("test.appearance");
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test"),
+            MessageRoutingInfo::new("test"),
+            MessageRoutingInfo::new("test"),
+            appearance_router.clone(),
+        );
+        let server_id = create_test_server(&pool, "Test Server").await;
+        let role = repo
+            .create(CreateRoleInput {
+                server_id,
+                name: "Painted".to_string(),
+                permissions: Permissions::try_from(0x1).unwrap(),
+                appearance: RoleAppearance::default(),
+            })
+            .await
+            .unwrap();
+
+        // Act: a rename keeps the appearance, a new color changes it
+        repo.update(UpdateRoleRepoInput {
+            id: role.id,
+            name: Some("Renamed".to_string()),
+            permissions: None,
+            appearance: UpdateRoleAppearance::default(),
+        })
+        .await
+        .unwrap();
+        repo.update(UpdateRoleRepoInput {
+            id: role.id,
+            name: None,
+            permissions: None,
+            appearance: UpdateRoleAppearance {
+                color: Some(0x3498DB),
+                ..Default::default()
+            },
+        })
+        .await
+        .unwrap();
+
+        // Assert: one event for the creation and one for the color
+        let payloads: Vec<serde_json::Value> = sqlx::query_scalar(
+            "SELECT payload FROM outbox_messages WHERE exchange_name = $1 ORDER BY created_at",
+        )
+        .bind(appearance_router.exchange_name())
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(
+            payloads[1].get("role_id").and_then(|v| v.as_str()),
+            Some(role.id.to_string().as_str())
+        );
+        assert_eq!(
+            payloads[1].get("color").and_then(|v| v.as_i64()),
+            Some(0x3498DB)
+        );
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_update_nonexistent_role_returns_error(pool: PgPool) {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo = PostgresRoleRepository::new(pool, create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool,
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let nonexistent_id = RoleId(Uuid::new_v4());
 
         let update_input = UpdateRoleRepoInput {
             id: nonexistent_id,
             name: Some("New Name".to_string()),
             permissions: None,
+            appearance: UpdateRoleAppearance::default(),
         };
 
         let result = repo.update(update_input).await;
@@ -537,14 +770,20 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo =
-            PostgresRoleRepository::new(pool.clone(), create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let server_id = create_test_server(&pool, "Test Server").await;
 
         let input = CreateRoleInput {
             server_id,
             name: "To Delete".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
         let role = repo.create(input).await.unwrap();
 
@@ -570,6 +809,7 @@ mod tests {
             create_router,
             update_router,
             delete_router.clone(),
+            MessageRoutingInfo::new("test"),
         );
         let server_id = create_test_server(&pool, "Test Server").await;
 
@@ -577,6 +817,7 @@ mod tests {
             server_id,
             name: "To Delete".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
         let role = repo.create(input).await.unwrap();
         let role_id = role.id;
@@ -613,7 +854,13 @@ mod tests {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo = PostgresRoleRepository::new(pool, create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool,
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let nonexistent_id = RoleId(Uuid::new_v4());
 
         let result = repo.delete(&nonexistent_id).await;
@@ -625,13 +872,78 @@ mod tests {
         }
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_positions_follow_create_reorder_and_delete(pool: PgPool) {
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::new("test"),
+            MessageRoutingInfo::new("test"),
+            MessageRoutingInfo::new("test"),
+            MessageRoutingInfo::new("test"),
+        );
+        let server_id = create_test_server(&pool, "Test Server").await;
+
+        let mut created = Vec::new();
+        for name in ["First", "Second", "Third"] {
+            let input = CreateRoleInput {
+                server_id,
+                name: name.to_string(),
+                permissions: Permissions::try_from(0x40).unwrap(),
+                appearance: RoleAppearance::default(),
+            };
+            created.push(repo.create(input).await.unwrap());
+        }
+
+        // Each new role enters at the bottom, pushing the others up
+        let names: Vec<String> = repo
+            .list_in_server(server_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|role| role.name)
+            .collect();
+        assert_eq!(names, vec!["First", "Second", "Third"]);
+
+        let reordered = repo
+            .reorder(server_id, &[created[0].id, created[1].id, created[2].id])
+            .await
+            .unwrap();
+        let positions: Vec<(String, i32)> = reordered
+            .into_iter()
+            .map(|role| (role.name, role.position))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                ("Third".to_string(), 3),
+                ("Second".to_string(), 2),
+                ("First".to_string(), 1)
+            ]
+        );
+
+        repo.delete(&created[0].id).await.unwrap();
+        let positions: Vec<i32> = repo
+            .list_in_server(server_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|role| role.position)
+            .collect();
+        assert_eq!(positions, vec![2, 1]);
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_cascade_delete_when_server_deleted(pool: PgPool) {
         let create_router = MessageRoutingInfo::new("test");
         let update_router = MessageRoutingInfo::new("test");
         let delete_router = MessageRoutingInfo::new("test");
-        let repo =
-            PostgresRoleRepository::new(pool.clone(), create_router, update_router, delete_router);
+        let repo = PostgresRoleRepository::new(
+            pool.clone(),
+            create_router,
+            update_router,
+            delete_router,
+            MessageRoutingInfo::new("test"),
+        );
         let server_id = create_test_server(&pool, "Test Server").await;
 
         // Create roles for the server
@@ -639,6 +951,7 @@ mod tests {
             server_id,
             name: "Role 1".to_string(),
             permissions: Permissions::try_from(0x1).unwrap(),
+            appearance: RoleAppearance::default(),
         };
         let role1 = repo.create(input1).await.unwrap();
 
@@ -646,6 +959,7 @@ mod tests {
             server_id,
             name: "Role 2".to_string(),
             permissions: Permissions::try_from(0x2).unwrap(),
+            appearance: RoleAppearance::default(),
         };
         let role2 = repo.create(input2).await.unwrap();
 
@@ -1,20 +1,31 @@
-use sqlx::{PgPool, query_as};
+use std::collections::HashMap;
+
+use sqlx::{PgConnection, PgPool, Row, query_as};
 use tracing::debug;
 use uuid::Uuid;
 
 use crate::{
     domain::{
-        common::{CoreError, GetPaginated, TotalPaginatedElements},
+        channel::entities::{ChannelId, ChannelType, ServerChannelCreation},
+        common::{CoreError, Cursor, CursorPage, GetPaginated, TotalPaginatedElements, next_page},
         friend::entities::UserId,
-        member_role::entities::{AssignUserRole, MemberRole},
-        role::entities::{Permission, Permissions, Role},
+        member_role::entities::AssignUserRole,
+        role::entities::{DEFAULT_ROLE_NAME, Permission, Permissions, Role, RoleId},
         server::{
-            entities::{DeleteServerEvent, InsertServerInput, Server, ServerId, UpdateServerInput},
+            entities::{
+                DeleteServerEvent, ExportedChannel, ExportedMember, ExportedRole, ExportedServer,
+                ImportServerInput, InsertServerInput, InsertServerTemplateInput,
+                SERVER_EXPORT_VERSION, Server, ServerExport, ServerId, ServerSettings,
+                ServerTemplate, ServerTemplateId, ServerTemplateSnapshot, UpdateServerInput,
+            },
             ports::ServerRepository,
         },
         server_member::{MemberId, ServerMember},
     },
-    infrastructure::{MessageRoutingInfo, outbox::OutboxEventRecord},
+    infrastructure::{
+        MessageRoutingInfo, outbox::OutboxEventRecord, role::repositories::postgres::ROLE_COLUMNS,
+        server_member::repositories::DISPLAY_COLOR,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -25,6 +36,7 @@ pub struct PostgresServerRepository {
     create_role_router: MessageRoutingInfo,
     user_join_server_router: MessageRoutingInfo,
     assign_role_routing: MessageRoutingInfo,
+    create_channel_router: MessageRoutingInfo,
 }
 
 impl PostgresServerRepository {
@@ -35,6 +47,7 @@ impl PostgresServerRepository {
         create_role_router: MessageRoutingInfo,
         user_join_server_router: MessageRoutingInfo,
         assign_role_routing: MessageRoutingInfo,
+        create_channel_router: MessageRoutingInfo,
     ) -> Self {
         Self {
             pool,
@@ -43,10 +56,263 @@ impl PostgresServerRepository {
             create_role_router,
             user_join_server_router,
             assign_role_routing,
+            create_channel_router,
         }
     }
 }
 
+impl PostgresServerRepository {
+    /// Inserts the server, the owner membership and the default role (id == server id)
+    /// inside the given transaction, along with their outbox events.
+    async fn insert_server_with_owner(
+        &self,
+        conn: &mut PgConnection,
+        input: &InsertServerInput,
+        default_role_name: &str,
+        default_permissions: &Permissions,
+    ) -> Result<Server, CoreError> {
+        // Insert the server into the database
+        let server = query_as!(
+            Server,
+            r#"
+            INSERT INTO servers (name, owner_id, picture_url, banner_url, description, visibility)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, name, banner_url, picture_url, description, owner_id, 
+                      visibility as "visibility: _", created_at, updated_at
+            "#,
+            input.name,
+            input.owner_id.0,
+            input.picture_url,
+            input.banner_url,
+            input.description,
+            input.visibility as _
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|_| CoreError::FailedToInsertServer {
+            name: input.name.clone(),
+        })?;
+
+        let member_id = Uuid::new_v4();
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO server_members AS sm (id, server_id, user_id)
+            VALUES ($1, $2, $3)
+            RETURNING id, server_id, user_id, nickname, joined_at, updated_at, timed_out_until,
+                profile,
+                {}
+            "#,
+            DISPLAY_COLOR
+        ))
+        .bind(member_id)
+        .bind(server.id.0)
+        .bind(input.owner_id.0)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|_| CoreError::FailedToInsertMember {
+            server_id: server.id,
+            user_id: input.owner_id,
+        })?;
+        let server_member = ServerMember::from(&row);
+
+        let member_join_server =
+            OutboxEventRecord::new(self.user_join_server_router.clone(), server_member.clone());
+
+        member_join_server.write(&mut *conn).await?;
+
+        // Write the create event to the outbox table for eventual processing
+        let create_server_event =
+            OutboxEventRecord::new(self.create_server_router.clone(), server.clone());
+        create_server_event.write(&mut *conn).await?;
+
+        let role_row = sqlx::query(&format!(
+            r#"
+            INSERT INTO roles (id, server_id, name, permissions, is_default)
+            VALUES ($1, $2, $3, $4, TRUE)
+            RETURNING {ROLE_COLUMNS}
+            "#
+        ))
+        .bind(*server.id)
+        .bind(*server.id)
+        .bind(default_role_name)
+        .bind(**default_permissions)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        let role = &Role::from(&role_row);
+
+        let create_role_event = OutboxEventRecord::new(self.create_role_router.clone(), role);
+
+        create_role_event.write(&mut *conn).await?;
+
+        // The owner joined before the default role existed, so the database did not assign it yet
+        let role_id = &role.clone().id.clone();
+        sqlx::query(
+            r#"
+                INSERT INTO member_roles (role_id, member_id)
+                VALUES ($1, $2)
+                ON CONFLICT (member_id, role_id) DO NOTHING
+                "#,
+        )
+        .bind(**role_id)
+        .bind(member_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|_| CoreError::AssignMemberRoleError {
+            member_id: MemberId(member_id),
+            role_id: role.clone().id,
+        })?;
+
+        debug!("{:?}", self.assign_role_routing.clone());
+
+        let user_assign = AssignUserRole {
+            user_id: input.owner_id,
+            role_id: *role_id,
+        };
+
+        let assign_member_to_role_event =
+            OutboxEventRecord::new(self.assign_role_routing.clone(), user_assign);
+
+        assign_member_to_role_event.write(&mut *conn).await?;
+
+        Ok(server)
+    }
+
+    /// Inserts a server with its owner, then every role and channel of the snapshot,
+    /// inside the given transaction. Returns the server and the new role ids, in the order
+    /// of the snapshot roles.
+    async fn insert_server_from_snapshot(
+        &self,
+        conn: &mut PgConnection,
+        input: &InsertServerInput,
+        snapshot: &ServerTemplateSnapshot,
+    ) -> Result<(Server, Vec<RoleId>), CoreError> {
+        let (default_role_name, default_permissions) = match snapshot.default_role() {
+            Some(role) => (role.name.as_str(), role.permissions.clone()),
+            None => (
+                DEFAULT_ROLE_NAME,
+                Permissions::from(vec![
+                    Permission::SendMessages,
+                    Permission::AttachFiles,
+                    Permission::ViewChannels,
+                    Permission::Connect,
+                    Permission::Speak,
+                ]),
+            ),
+        };
+
+        let server = self
+            .insert_server_with_owner(conn, input, default_role_name, &default_permissions)
+            .await?;
+
+        let mut role_ids = Vec::with_capacity(snapshot.roles.len());
+        let mut position: i32 = 0;
+        // Snapshot roles are ordered from lowest to highest
+        for template_role in &snapshot.roles {
+            if template_role.is_default {
+                role_ids.push(RoleId(*server.id));
+                continue;
+            }
+            position += 1;
+            let role_row = sqlx::query(&format!(
+                r#"
+                INSERT INTO roles (id, server_id, name, permissions, position)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING {ROLE_COLUMNS}
+                "#
+            ))
+            .bind(Uuid::new_v4())
+            .bind(*server.id)
+            .bind(&template_role.name)
+            .bind(*template_role.permissions)
+            .bind(position)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+            let role = Role::from(&role_row);
+
+            role_ids.push(role.id);
+            OutboxEventRecord::new(self.create_role_router.clone(), role)
+                .write(&mut *conn)
+                .await?;
+        }
+
+        // Snapshot channels are ordered parent first, so parents always exist
+        // by the time their children are inserted
+        let mut channel_ids: HashMap<u32, ChannelId> = HashMap::new();
+        for template_channel in &snapshot.channels {
+            let parent_id = template_channel
+                .parent_key
+                .and_then(|key| channel_ids.get(&key))
+                .map(|id| id.0);
+
+            let channel_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO channels (name, server_id, parent_id, channel_type)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id
+                "#,
+            )
+            .bind(&template_channel.name)
+            .bind(server.id.0)
+            .bind(parent_id)
+            .bind(template_channel.channel_type)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to create channel: {}", e),
+            })?;
+
+            channel_ids.insert(template_channel.key, ChannelId(channel_id));
+
+            let server_channel = ServerChannelCreation {
+                id: ChannelId(channel_id),
+                server_id: server.id,
+            };
+            OutboxEventRecord::new(self.create_channel_router.clone(), server_channel)
+                .write(&mut *conn)
+                .await?;
+        }
+
+        Ok((server, role_ids))
+    }
+
+    /// Assigns a role to a member inside the given transaction and writes the assign event
+    async fn assign_role_in_tx(
+        &self,
+        conn: &mut PgConnection,
+        member_id: MemberId,
+        user_id: UserId,
+        role_id: RoleId,
+    ) -> Result<(), CoreError> {
+        // The member may already hold the role, the default one being assigned by the database
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO member_roles (member_id, role_id)
+            VALUES ($1, $2)
+            ON CONFLICT (member_id, role_id) DO NOTHING
+            "#,
+        )
+        .bind(*member_id)
+        .bind(*role_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|_| CoreError::AssignMemberRoleError { member_id, role_id })?;
+        if inserted.rows_affected() == 0 {
+            return Ok(());
+        }
+
+        OutboxEventRecord::new(
+            self.assign_role_routing.clone(),
+            AssignUserRole { user_id, role_id },
+        )
+        .write(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
 impl ServerRepository for PostgresServerRepository {
     async fn find_by_id(&self, id: &ServerId) -> Result<Server, CoreError> {
         let server = query_as!(
@@ -112,113 +378,17 @@ impl ServerRepository for PostgresServerRepository {
                 name: input.name.clone(),
             })?;
 
-        // Insert the server into the database
-        let server = query_as!(
-            Server,
-            r#"
-            INSERT INTO servers (name, owner_id, picture_url, banner_url, description, visibility)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, name, banner_url, picture_url, description, owner_id, 
-                      visibility as "visibility: _", created_at, updated_at
-            "#,
-            input.name,
-            input.owner_id.0,
-            input.picture_url,
-            input.banner_url,
-            input.description,
-            input.visibility as _
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|_| CoreError::FailedToInsertServer {
-            name: input.name.clone(),
-        })?;
-
-        let member_id = Uuid::new_v4();
-        let server_member = query_as!(
-            ServerMember,
-            r#"
-            INSERT INTO server_members (id, server_id, user_id)
-            VALUES ($1, $2, $3)
-            RETURNING id, server_id, user_id, nickname, joined_at, updated_at
-            "#,
-            member_id,
-            *server.id,
-            *input.owner_id,
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|_| CoreError::FailedToInsertMember {
-            server_id: server.id,
-            user_id: input.owner_id,
-        })?;
-
-        let member_join_server =
-            OutboxEventRecord::new(self.user_join_server_router.clone(), server_member.clone());
-
-        member_join_server.write(&mut *tx).await?;
-
-        // Write the create event to the outbox table for eventual processing
-        let create_server_event =
-            OutboxEventRecord::new(self.create_server_router.clone(), server.clone());
-        create_server_event.write(&mut *tx).await?;
-
         let base_permission = Permissions::from(vec![
             Permission::SendMessages,
             Permission::AttachFiles,
             Permission::ViewChannels,
+            Permission::Connect,
+            Permission::Speak,
         ]);
 
-        let role = &query_as!(
-            Role,
-            r#"
-            INSERT INTO roles (id, server_id, name, permissions)
-            VALUES ($1, $2, $3, $4)
-            RETURNING id, server_id, name, permissions as "permissions: _", created_at, updated_at
-            "#,
-            *server.id,
-            *server.id,
-            "BasicUser",
-            *base_permission
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
-
-        let create_role_event = OutboxEventRecord::new(self.create_role_router.clone(), role);
-
-        create_role_event.write(&mut *tx).await?;
-
-        let role_id = &role.clone().id.clone();
-        let member_role = query_as!(
-            MemberRole,
-            r#"
-                INSERT INTO member_roles (role_id, member_id)
-                VALUES ($1, $2)
-                RETURNING role_id, member_id, created_at, updated_at
-                "#,
-            **role_id,
-            member_id
-        )
-        .fetch_one(&mut *tx)
-        .await
-        .map_err(|_| CoreError::AssignMemberRoleError {
-            member_id: MemberId(member_id),
-            role_id: role.clone().id,
-        })?;
-
-        debug!("{:?}", self.assign_role_routing.clone());
-        debug!("{:?}", member_role.clone());
-
-        let user_assign = AssignUserRole {
-            user_id: input.owner_id,
-            role_id: member_role.role_id,
-        };
-
-        let assign_member_to_role_event =
-            OutboxEventRecord::new(self.assign_role_routing.clone(), user_assign);
-
-        assign_member_to_role_event.write(&mut *tx).await?;
+        let server = self
+            .insert_server_with_owner(&mut tx, &input, DEFAULT_ROLE_NAME, &base_permission)
+            .await?;
 
         tx.commit()
             .await
@@ -365,6 +535,59 @@ impl ServerRepository for PostgresServerRepository {
         Ok((servers, total as u64))
     }
 
+    async fn list_user_servers_after(
+        &self,
+        after: Option<Cursor>,
+        limit: u32,
+        user_id: UserId,
+    ) -> Result<CursorPage<Server>, CoreError> {
+        let limit = std::cmp::min(limit, 50);
+
+        let total: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM server_members WHERE user_id = $1")
+                .bind(user_id.0)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        // Keyed by the membership join date, the server id breaks ties
+        let rows = sqlx::query(
+            r#"
+            SELECT s.id, s.name, s.banner_url, s.picture_url, s.description, s.owner_id,
+                   s.visibility, s.created_at, s.updated_at, sm.joined_at
+            FROM servers s
+            INNER JOIN server_members sm ON s.id = sm.server_id
+            WHERE sm.user_id = $1
+              AND ($2::TIMESTAMPTZ IS NULL OR (sm.joined_at, s.id) < ($2, $3))
+            ORDER BY sm.joined_at DESC, s.id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(user_id.0)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.id))
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        let servers: Vec<(Cursor, Server)> = rows
+            .iter()
+            .map(|row| {
+                let server = Server::from(row);
+                (Cursor::new(row.get("joined_at"), server.id.0), server)
+            })
+            .collect();
+        let (servers, total, next_cursor) =
+            next_page(servers, limit, |(cursor, _)| *cursor, total as u64);
+
+        Ok((
+            servers.into_iter().map(|(_, server)| server).collect(),
+            total,
+            next_cursor,
+        ))
+    }
+
     async fn search_or_discover(
         &self,
         query: Option<String>,
@@ -434,6 +657,366 @@ impl ServerRepository for PostgresServerRepository {
 
         Ok((servers, total as u64))
     }
+
+    async fn insert_from_template(
+        &self,
+        input: InsertServerInput,
+        snapshot: ServerTemplateSnapshot,
+    ) -> Result<Server, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|_| CoreError::FailedToInsertServer {
+                name: input.name.clone(),
+            })?;
+
+        let (server, _) = self
+            .insert_server_from_snapshot(&mut tx, &input, &snapshot)
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|_| CoreError::FailedToInsertServer { name: input.name })?;
+
+        Ok(server)
+    }
+
+    async fn insert_template(
+        &self,
+        input: InsertServerTemplateInput,
+    ) -> Result<ServerTemplate, CoreError> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO server_templates (name, description, source_server_id, creator_id, snapshot)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, description, source_server_id, creator_id, snapshot, created_at, updated_at
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(input.source_server_id.map(|id| id.0))
+        .bind(input.creator_id.0)
+        .bind(sqlx::types::Json(&input.snapshot))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to insert server template: {}", e),
+        })?;
+
+        Ok(ServerTemplate::from(&row))
+    }
+
+    async fn find_template_by_id(
+        &self,
+        id: &ServerTemplateId,
+    ) -> Result<ServerTemplate, CoreError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, description, source_server_id, creator_id, snapshot, created_at, updated_at
+            FROM server_templates
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to find server template: {}", e),
+        })?
+        .ok_or(CoreError::ServerTemplateNotFound { id: *id })?;
+
+        Ok(ServerTemplate::from(&row))
+    }
+
+    async fn export(&self, id: &ServerId) -> Result<ServerExport, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        // Every read below must see the same state of the server
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        let server = query_as!(
+            Server,
+            r#"
+            SELECT id, name, banner_url, picture_url, description, owner_id, 
+                   visibility as "visibility: _", created_at, updated_at
+            FROM servers
+            WHERE id = $1
+            "#,
+            id.0
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?
+        .ok_or(CoreError::ServerNotFound { id: *id })?;
+
+        let roles = sqlx::query(
+            r#"
+            SELECT id, name, permissions, is_default
+            FROM roles
+            WHERE server_id = $1
+            ORDER BY position ASC, created_at ASC
+            "#,
+        )
+        .bind(id.0)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to export roles: {}", e),
+        })?
+        .iter()
+        .map(|row| ExportedRole {
+            id: RoleId(row.get("id")),
+            name: row.get("name"),
+            permissions: Permissions(row.get("permissions")),
+            is_default: row.get("is_default"),
+        })
+        .collect();
+
+        let channels = sqlx::query(
+            r#"
+            SELECT id, name, channel_type, parent_id
+            FROM channels
+            WHERE server_id = $1
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(id.0)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to export channels: {}", e),
+        })?
+        .iter()
+        .map(|row| ExportedChannel {
+            id: ChannelId(row.get("id")),
+            name: row.get("name"),
+            channel_type: row.get::<ChannelType, _>("channel_type"),
+            parent_id: row.get::<Option<Uuid>, _>("parent_id").map(ChannelId),
+        })
+        .collect();
+
+        // The default role is held implicitly by every member, it is not exported
+        let assignments = sqlx::query(
+            r#"
+            SELECT mr.member_id, mr.role_id
+            FROM member_roles mr
+            INNER JOIN server_members sm ON sm.id = mr.member_id
+            WHERE sm.server_id = $1 AND mr.role_id <> $1
+            "#,
+        )
+        .bind(id.0)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to export member roles: {}", e),
+        })?;
+        let mut member_roles: HashMap<Uuid, Vec<RoleId>> = HashMap::new();
+        for row in &assignments {
+            member_roles
+                .entry(row.get("member_id"))
+                .or_default()
+                .push(RoleId(row.get("role_id")));
+        }
+
+        let members = sqlx::query(
+            r#"
+            SELECT id, user_id, nickname, joined_at
+            FROM server_members
+            WHERE server_id = $1
+            ORDER BY joined_at ASC
+            "#,
+        )
+        .bind(id.0)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to export members: {}", e),
+        })?
+        .iter()
+        .map(|row| {
+            let member_id: Uuid = row.get("id");
+            ExportedMember {
+                user_id: UserId(row.get("user_id")),
+                nickname: row.get("nickname"),
+                joined_at: row.get("joined_at"),
+                role_ids: member_roles.remove(&member_id).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+        tx.commit()
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+        Ok(ServerExport {
+            version: SERVER_EXPORT_VERSION,
+            exported_at: chrono::Utc::now(),
+            server: ExportedServer {
+                id: server.id,
+                name: server.name,
+                banner_url: server.banner_url,
+                picture_url: server.picture_url,
+                description: server.description,
+                visibility: server.visibility,
+            },
+            roles,
+            channels,
+            members,
+        })
+    }
+
+    async fn import(&self, input: ImportServerInput) -> Result<Server, CoreError> {
+        let ImportServerInput {
+            owner_id,
+            bundle,
+            snapshot,
+        } = input;
+
+        let server_input = InsertServerInput {
+            name: bundle.server.name.clone(),
+            owner_id,
+            picture_url: bundle.server.picture_url.clone(),
+            banner_url: bundle.server.banner_url.clone(),
+            description: bundle.server.description.clone(),
+            visibility: bundle.server.visibility.clone(),
+        };
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|_| CoreError::FailedToInsertServer {
+                name: server_input.name.clone(),
+            })?;
+
+        let (server, new_role_ids) = self
+            .insert_server_from_snapshot(&mut tx, &server_input, &snapshot)
+            .await?;
+
+        // Roles are recreated in the order of the bundle
+        let role_ids: HashMap<RoleId, RoleId> = bundle
+            .roles
+            .iter()
+            .map(|role| role.id)
+            .zip(new_role_ids)
+            .collect();
+
+        for member in &bundle.members {
+            if member.user_id != owner_id {
+                // Members are only invited, joining stays their decision. The invitation
+                // does not carry their nickname nor roles, they join as new members
+                sqlx::query(
+                    r#"
+                    INSERT INTO server_invitations (server_id, inviter_id, invitee_id)
+                    VALUES ($1, $2, $3)
+                    "#,
+                )
+                .bind(server.id.0)
+                .bind(owner_id.0)
+                .bind(member.user_id.0)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| CoreError::DatabaseError {
+                    msg: format!("Failed to invite member: {}", e),
+                })?;
+                continue;
+            }
+
+            // The owner membership was created along with the server
+            let member_id: Uuid = sqlx::query_scalar(
+                r#"
+                UPDATE server_members
+                SET nickname = $1
+                WHERE server_id = $2 AND user_id = $3
+                RETURNING id
+                "#,
+            )
+            .bind(&member.nickname)
+            .bind(server.id.0)
+            .bind(owner_id.0)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+            let assigned = member
+                .role_ids
+                .iter()
+                .filter_map(|role_id| role_ids.get(role_id))
+                .filter(|role_id| role_id.0 != server.id.0);
+            for role_id in assigned {
+                self.assign_role_in_tx(&mut tx, MemberId(member_id), owner_id, *role_id)
+                    .await?;
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|_| CoreError::FailedToInsertServer {
+                name: server_input.name,
+            })?;
+
+        Ok(server)
+    }
+
+    async fn find_settings(&self, id: &ServerId) -> Result<ServerSettings, CoreError> {
+        let row = sqlx::query(
+            r#"
+            SELECT server_id, join_mode, system_channel_id, default_role_ids, allow_nickname_change, join_questions, updated_at
+            FROM server_settings
+            WHERE server_id = $1
+            "#,
+        )
+        .bind(id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to find server settings: {}", e),
+        })?;
+
+        Ok(row
+            .as_ref()
+            .map(ServerSettings::from)
+            .unwrap_or_else(|| ServerSettings::default_for(*id)))
+    }
+
+    async fn upsert_settings(&self, settings: ServerSettings) -> Result<ServerSettings, CoreError> {
+        let default_role_ids: Vec<Uuid> = settings.default_role_ids.iter().map(|id| id.0).collect();
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO server_settings (server_id, join_mode, system_channel_id, default_role_ids, allow_nickname_change, join_questions)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (server_id) DO UPDATE
+            SET join_mode = EXCLUDED.join_mode,
+                system_channel_id = EXCLUDED.system_channel_id,
+                default_role_ids = EXCLUDED.default_role_ids,
+                allow_nickname_change = EXCLUDED.allow_nickname_change,
+                join_questions = EXCLUDED.join_questions
+            RETURNING server_id, join_mode, system_channel_id, default_role_ids, allow_nickname_change, join_questions, updated_at
+            "#,
+        )
+        .bind(settings.server_id.0)
+        .bind(settings.join_mode)
+        .bind(settings.system_channel_id.map(|id| id.0))
+        .bind(default_role_ids)
+        .bind(settings.allow_nickname_change)
+        .bind(&settings.join_questions)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to save server settings: {}", e),
+        })?;
+
+        Ok(ServerSettings::from(&row))
+    }
 }
 
 #[sqlx::test(migrations = "./migrations")]
@@ -454,6 +1037,7 @@ async fn test_insert_server_writes_row_and_outbox(pool: PgPool) -> Result<(), Co
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -566,6 +1150,7 @@ async fn test_find_by_id_returns_none_for_nonexistent(pool: PgPool) -> Result<()
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Try to find a server with a random UUID that doesn't exist
@@ -596,6 +1181,7 @@ async fn test_delete_nonexistent_returns_error(pool: PgPool) -> Result<(), CoreE
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Try to delete a server with a random UUID that doesn't exist
@@ -634,6 +1220,7 @@ async fn test_delete_server_removes_row_and_outbox(pool: PgPool) -> Result<(), C
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Arrange: insert a server first
@@ -710,6 +1297,7 @@ async fn test_update_server_updates_fields(pool: PgPool) -> Result<(), CoreError
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Arrange: insert a server first
@@ -775,6 +1363,7 @@ async fn test_update_nonexistent_server_returns_error(pool: PgPool) -> Result<()
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Try to update a server with a random UUID that doesn't exist
@@ -817,6 +1406,7 @@ async fn test_update_server_with_no_fields_returns_unchanged(
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Arrange: insert a server first
@@ -868,6 +1458,7 @@ async fn test_list_servers_with_pagination(pool: PgPool) -> Result<(), CoreError
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Arrange: insert multiple servers
@@ -934,6 +1525,7 @@ async fn test_list_servers_filters_only_public(pool: PgPool) -> Result<(), CoreE
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     // Arrange: insert servers with mixed visibility
@@ -1010,6 +1602,7 @@ async fn test_search_or_discover_with_query(pool: PgPool) -> Result<(), CoreErro
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -1070,6 +1663,7 @@ async fn test_search_or_discover_case_insensitive(pool: PgPool) -> Result<(), Co
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -1117,6 +1711,7 @@ async fn test_search_or_discover_without_query_returns_random(
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -1168,6 +1763,7 @@ async fn test_search_or_discover_respects_limit(pool: PgPool) -> Result<(), Core
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -1216,6 +1812,7 @@ async fn test_search_or_discover_enforces_max_limit(pool: PgPool) -> Result<(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -1269,6 +1866,7 @@ async fn test_search_or_discover_only_public_servers(pool: PgPool) -> Result<(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
         MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
     );
 
     let owner_id = UserId(Uuid::new_v4());
@@ -1314,3 +1912,236 @@ async fn test_search_or_discover_only_public_servers(pool: PgPool) -> Result<(),
 
     Ok(())
 }
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_insert_from_template_creates_roles_and_channels(
+    pool: PgPool,
+) -> Result<(), CoreError> {
+    use crate::domain::{
+        channel::entities::ChannelType,
+        server::entities::{
+            SERVER_TEMPLATE_VERSION, ServerVisibility, TemplateChannel, TemplateRole,
+            TemplateSettings,
+        },
+    };
+    use crate::infrastructure::outbox::MessageRouter;
+
+    let create_channel_router = MessageRoutingInfo::new("channel.exchange");
+    let repository = PostgresServerRepository::new(
+        pool.clone(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        create_channel_router.clone(),
+    );
+
+    let snapshot = ServerTemplateSnapshot {
+        version: SERVER_TEMPLATE_VERSION,
+        settings: TemplateSettings {
+            description: None,
+            visibility: ServerVisibility::Public,
+        },
+        roles: vec![
+            TemplateRole {
+                name: "Everyone".to_string(),
+                permissions: Permissions(0x40),
+                is_default: true,
+            },
+            TemplateRole {
+                name: "Moderator".to_string(),
+                permissions: Permissions(0x104),
+                is_default: false,
+            },
+        ],
+        channels: vec![
+            TemplateChannel {
+                key: 0,
+                name: "General".to_string(),
+                channel_type: ChannelType::ServerFolder,
+                parent_key: None,
+            },
+            TemplateChannel {
+                key: 1,
+                name: "chat".to_string(),
+                channel_type: ChannelType::ServerText,
+                parent_key: Some(0),
+            },
+        ],
+    };
+
+    let server = repository
+        .insert_from_template(
+            InsertServerInput {
+                name: "from template".to_string(),
+                owner_id: UserId(Uuid::new_v4()),
+                picture_url: None,
+                banner_url: None,
+                description: None,
+                visibility: ServerVisibility::Public,
+            },
+            snapshot,
+        )
+        .await?;
+
+    // The default role keeps the server id and takes the template permissions
+    let default_permissions: i64 =
+        sqlx::query_scalar("SELECT permissions FROM roles WHERE id = $1")
+            .bind(server.id.0)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(default_permissions, 0x40);
+
+    let role_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM roles WHERE server_id = $1")
+        .bind(server.id.0)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(role_count, 2);
+
+    // The text channel is nested under the folder
+    let nested: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM channels c
+        INNER JOIN channels p ON c.parent_id = p.id
+        WHERE c.server_id = $1 AND c.name = 'chat' AND p.name = 'General'
+        "#,
+    )
+    .bind(server.id.0)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(nested, 1);
+
+    let channel_events: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM outbox_messages WHERE exchange_name = $1")
+            .bind(create_channel_router.exchange_name())
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(channel_events, 2);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_export_then_import_remaps_ids(pool: PgPool) -> Result<(), CoreError> {
+    use crate::domain::server::entities::ServerVisibility;
+
+    let repository = PostgresServerRepository::new(
+        pool.clone(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+        MessageRoutingInfo::default(),
+    );
+
+    let owner_id = UserId(Uuid::new_v4());
+    let source = repository
+        .insert(InsertServerInput {
+            name: "source".to_string(),
+            owner_id,
+            picture_url: None,
+            banner_url: None,
+            description: Some("exported".to_string()),
+            visibility: ServerVisibility::Public,
+        })
+        .await?;
+
+    // Arrange: a moderator role held by the owner, and a second member
+    let moderator_role_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO roles (id, server_id, name, permissions) VALUES ($1, $2, 'Moderator', 4)",
+    )
+    .bind(moderator_role_id)
+    .bind(source.id.0)
+    .execute(&pool)
+    .await
+    .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    sqlx::query(
+        r#"
+        INSERT INTO member_roles (member_id, role_id)
+        SELECT id, $1 FROM server_members WHERE server_id = $2 AND user_id = $3
+        "#,
+    )
+    .bind(moderator_role_id)
+    .bind(source.id.0)
+    .bind(owner_id.0)
+    .execute(&pool)
+    .await
+    .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    let member_user_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO server_members (id, server_id, user_id, nickname) VALUES ($1, $2, $3, 'mod')",
+    )
+    .bind(Uuid::new_v4())
+    .bind(source.id.0)
+    .bind(member_user_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+
+    let bundle = repository.export(&source.id).await?;
+    assert_eq!(bundle.roles.len(), 2);
+    assert_eq!(bundle.members.len(), 2);
+
+    // Act: import back by the owner
+    let snapshot = bundle.to_snapshot()?;
+    let imported = repository
+        .import(ImportServerInput {
+            owner_id,
+            bundle,
+            snapshot,
+        })
+        .await?;
+
+    assert_ne!(imported.id, source.id);
+    assert_eq!(imported.owner_id, owner_id);
+    assert_eq!(imported.description, Some("exported".to_string()));
+
+    // Assert: the owner kept the remapped role
+    let role_name: String = sqlx::query_scalar(
+        r#"
+        SELECT r.name
+        FROM member_roles mr
+        INNER JOIN server_members sm ON sm.id = mr.member_id
+        INNER JOIN roles r ON r.id = mr.role_id
+        WHERE sm.server_id = $1 AND sm.user_id = $2 AND r.id <> $1
+        "#,
+    )
+    .bind(imported.id.0)
+    .bind(owner_id.0)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(role_name, "Moderator");
+
+    // Assert: the other member is invited instead of joined
+    let member_count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM server_members WHERE server_id = $1")
+            .bind(imported.id.0)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(member_count, 1);
+    let invitation_count: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM server_invitations
+        WHERE server_id = $1 AND invitee_id = $2 AND status = 'pending'
+        "#,
+    )
+    .bind(imported.id.0)
+    .bind(member_user_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+    assert_eq!(invitation_count, 1);
+
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use sqlx::{PgConnection, PgPool};
+
+use crate::{
+    domain::{
+        common::{CoreError, GetPaginated, TotalPaginatedElements},
+        friend::entities::UserId,
+        server::entities::ServerId,
+        server_ban::{
+            entities::{InsertServerBanInput, MemberBannedEvent, MemberUnbannedEvent, ServerBan},
+            ports::ServerBanRepository,
+        },
+        server_member::entities::{DeleteMemberEvent, MemberRemoval},
+    },
+    infrastructure::{MessageRoutingInfo, outbox::OutboxEventRecord},
+};
+
+#[derive(Debug, Clone)]
+pub struct PostgresServerBanRepository {
+    pub(crate) pool: PgPool,
+    member_ban_router: MessageRoutingInfo,
+    member_unban_router: MessageRoutingInfo,
+    user_leave_server_router: MessageRoutingInfo,
+    member_remove_router: MessageRoutingInfo,
+}
+
+impl PostgresServerBanRepository {
+    pub fn new(
+        pool: PgPool,
+        member_ban_router: MessageRoutingInfo,
+        member_unban_router: MessageRoutingInfo,
+        user_leave_server_router: MessageRoutingInfo,
+        member_remove_router: MessageRoutingInfo,
+    ) -> Self {
+        Self {
+            pool,
+            member_ban_router,
+            member_unban_router,
+            user_leave_server_router,
+            member_remove_router,
+        }
+    }
+}
+
+impl PostgresServerBanRepository {
+    /// Bans a user within the transaction, removing their membership and writing the events
+    async fn insert_in(
+        &self,
+        tx: &mut PgConnection,
+        input: InsertServerBanInput,
+    ) -> Result<ServerBan, CoreError> {
+        // Banning again replaces the reason, moderator and expiry of the previous ban
+        let row = sqlx::query(
+            r#"
+            INSERT INTO server_bans (server_id, user_id, moderator_id, reason, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (server_id, user_id) DO UPDATE
+            SET moderator_id = EXCLUDED.moderator_id,
+                reason = EXCLUDED.reason,
+                expires_at = EXCLUDED.expires_at,
+                created_at = CURRENT_TIMESTAMP
+            RETURNING server_id, user_id, moderator_id, reason, expires_at, created_at
+            "#,
+        )
+        .bind(input.server_id.0)
+        .bind(input.user_id.0)
+        .bind(input.moderator_id.0)
+        .bind(&input.reason)
+        .bind(input.expires_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to insert server ban: {}", e),
+        })?;
+        let ban = ServerBan::from(&row);
+
+        // A banned member is removed from the server
+        let removed =
+            sqlx::query(r#"DELETE FROM server_members WHERE server_id = $1 AND user_id = $2"#)
+                .bind(input.server_id.0)
+                .bind(input.user_id.0)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| CoreError::DatabaseError {
+                    msg: format!("Failed to delete member: {}", e),
+                })?;
+        if removed.rows_affected() > 0 {
+            let leave_event = DeleteMemberEvent::new(
+                input.server_id,
+                input.user_id,
+                MemberRemoval::ban(input.moderator_id, input.reason.clone()),
+            );
+            OutboxEventRecord::new(self.user_leave_server_router.clone(), leave_event.clone())
+                .write(&mut *tx)
+                .await?;
+            OutboxEventRecord::new(self.member_remove_router.clone(), leave_event)
+                .write(&mut *tx)
+                .await?;
+        }
+
+        OutboxEventRecord::new(
+            self.member_ban_router.clone(),
+            MemberBannedEvent::from(&ban),
+        )
+        .write(&mut *tx)
+        .await?;
+
+        Ok(ban)
+    }
+}
+
+impl ServerBanRepository for PostgresServerBanRepository {
+    async fn insert(&self, input: InsertServerBanInput) -> Result<ServerBan, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let ban = self.insert_in(&mut tx, input).await?;
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(ban)
+    }
+
+    async fn insert_many(
+        &self,
+        inputs: Vec<InsertServerBanInput>,
+    ) -> Result<Vec<ServerBan>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let mut bans = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            bans.push(self.insert_in(&mut tx, input).await?);
+        }
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(bans)
+    }
+
+    async fn find_active(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+    ) -> Result<Option<ServerBan>, CoreError> {
+        let row = sqlx::query(
+            r#"
+            SELECT server_id, user_id, moderator_id, reason, expires_at, created_at
+            FROM server_bans
+            WHERE server_id = $1 AND user_id = $2
+              AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+        )
+        .bind(server_id.0)
+        .bind(user_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to find server ban: {}", e),
+        })?;
+
+        Ok(row.as_ref().map(ServerBan::from))
+    }
+
+    async fn delete(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        moderator_id: &UserId,
+    ) -> Result<(), CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let result =
+            sqlx::query(r#"DELETE FROM server_bans WHERE server_id = $1 AND user_id = $2"#)
+                .bind(server_id.0)
+                .bind(user_id.0)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| CoreError::DatabaseError {
+                    msg: format!("Failed to delete server ban: {}", e),
+                })?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::ServerBanNotFound {
+                server_id: *server_id,
+                user_id: *user_id,
+            });
+        }
+
+        let unban_event = MemberUnbannedEvent {
+            server_id: *server_id,
+            user_id: *user_id,
+            moderator_id: *moderator_id,
+        };
+        OutboxEventRecord::new(self.member_unban_router.clone(), unban_event)
+            .write(&mut *tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(())
+    }
+
+    async fn list_by_server(
+        &self,
+        server_id: &ServerId,
+        pagination: &GetPaginated,
+    ) -> Result<(Vec<ServerBan>, TotalPaginatedElements), CoreError> {
+        let offset = (pagination.page - 1) * pagination.limit;
+        let limit = std::cmp::min(pagination.limit, 50) as i64;
+
+        let total: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM server_bans
+            WHERE server_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+        )
+        .bind(server_id.0)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to count server bans: {}", e),
+        })?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT server_id, user_id, moderator_id, reason, expires_at, created_at
+            FROM server_bans
+            WHERE server_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+            ORDER BY created_at DESC, user_id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(server_id.0)
+        .bind(limit)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to list server bans: {}", e),
+        })?;
+
+        Ok((rows.iter().map(ServerBan::from).collect(), total as u64))
+    }
+}
@@ -3,10 +3,11 @@ use sqlx::PgPool;
 use crate::domain::{
     common::CoreError,
     friend::entities::UserId,
+    server::entities::ServerId,
     server_invitation::{
         entities::{
             InsertServerInvitationInput, ServerInvitation, ServerInvitationId,
-            ServerInvitationStatus, UpdateServerInvitationInput,
+            ServerInvitationStatus, ServerVanityUrl, UpdateServerInvitationInput, VanitySlug,
         },
         ports::ServerInvitationRepository,
     },
@@ -23,6 +24,23 @@ impl PostgresServerInvitationRepository {
     }
 }
 
+/// Name of the unique index enforcing case-insensitive vanity slug uniqueness
+const VANITY_SLUG_UNIQUE_INDEX: &str = "idx_server_invitations_vanity_slug";
+
+fn map_vanity_error(e: sqlx::Error, slug: &VanitySlug) -> CoreError {
+    let is_slug_taken = e.as_database_error().is_some_and(|db| {
+        db.is_unique_violation() && db.constraint() == Some(VANITY_SLUG_UNIQUE_INDEX)
+    });
+    if is_slug_taken {
+        return CoreError::VanitySlugAlreadyTaken {
+            slug: slug.to_string(),
+        };
+    }
+    CoreError::DatabaseError {
+        msg: format!("Failed to save vanity slug: {}", e),
+    }
+}
+
 impl ServerInvitationRepository for PostgresServerInvitationRepository {
     async fn insert(
         &self,
@@ -151,4 +169,97 @@ impl ServerInvitationRepository for PostgresServerInvitationRepository {
 
         Ok(())
     }
+
+    async fn upsert_vanity(
+        &self,
+        server_id: &ServerId,
+        inviter_id: &UserId,
+        slug: &VanitySlug,
+    ) -> Result<ServerVanityUrl, CoreError> {
+        // A single statement, so that concurrent claims for the same server cannot race
+        // between an update and an insert
+        let row = sqlx::query(
+            r#"
+            INSERT INTO server_invitations (server_id, inviter_id, vanity_slug)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (server_id) WHERE vanity_slug IS NOT NULL
+            DO UPDATE SET vanity_slug = EXCLUDED.vanity_slug
+            RETURNING id, server_id, vanity_slug, created_at, updated_at
+            "#,
+        )
+        .bind(server_id.0)
+        .bind(inviter_id.0)
+        .bind(slug.as_str())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| map_vanity_error(e, slug))?;
+
+        Ok(ServerVanityUrl::from(&row))
+    }
+
+    async fn find_vanity_by_slug(&self, slug: &VanitySlug) -> Result<ServerVanityUrl, CoreError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, server_id, vanity_slug, created_at, updated_at
+            FROM server_invitations
+            WHERE LOWER(vanity_slug) = LOWER($1)
+            "#,
+        )
+        .bind(slug.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to fetch vanity slug: {}", e),
+        })?;
+
+        row.as_ref()
+            .map(ServerVanityUrl::from)
+            .ok_or_else(|| CoreError::VanitySlugNotFound {
+                slug: slug.to_string(),
+            })
+    }
+
+    async fn find_vanity_by_server(
+        &self,
+        server_id: &ServerId,
+    ) -> Result<Option<ServerVanityUrl>, CoreError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, server_id, vanity_slug, created_at, updated_at
+            FROM server_invitations
+            WHERE server_id = $1 AND vanity_slug IS NOT NULL
+            "#,
+        )
+        .bind(server_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to fetch vanity slug: {}", e),
+        })?;
+
+        Ok(row.as_ref().map(ServerVanityUrl::from))
+    }
+
+    async fn delete_vanity(&self, server_id: &ServerId) -> Result<(), CoreError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM server_invitations
+            WHERE server_id = $1 AND vanity_slug IS NOT NULL
+            "#,
+        )
+        .bind(server_id.0)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to delete vanity slug: {}", e),
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::ServerVanityNotFound {
+                server_id: *server_id,
+            });
+        }
+
+        Ok(())
+    }
 }
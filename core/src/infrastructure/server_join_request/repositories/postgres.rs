@@ -0,0 +1,189 @@
+use sqlx::PgPool;
+
+use crate::domain::{
+    common::{CoreError, GetPaginated, TotalPaginatedElements},
+    friend::entities::UserId,
+    server::entities::ServerId,
+    server_join_request::{
+        entities::{
+            InsertJoinRequestInput, JoinRequestStatus, ServerJoinRequest, ServerJoinRequestId,
+        },
+        ports::ServerJoinRequestRepository,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct PostgresServerJoinRequestRepository {
+    pub(crate) pool: PgPool,
+}
+
+impl PostgresServerJoinRequestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Name of the unique index allowing a single pending request per user and server
+const PENDING_JOIN_REQUEST_UNIQUE_INDEX: &str = "idx_server_join_requests_pending";
+
+const JOIN_REQUEST_COLUMNS: &str =
+    "id, server_id, user_id, status, answers, reviewer_id, created_at, updated_at";
+
+impl ServerJoinRequestRepository for PostgresServerJoinRequestRepository {
+    async fn insert(&self, input: InsertJoinRequestInput) -> Result<ServerJoinRequest, CoreError> {
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO server_join_requests (server_id, user_id, answers)
+            VALUES ($1, $2, $3)
+            RETURNING {}
+            "#,
+            JOIN_REQUEST_COLUMNS
+        ))
+        .bind(input.server_id.0)
+        .bind(input.user_id.0)
+        .bind(sqlx::types::Json(&input.answers))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            let is_pending = e.as_database_error().is_some_and(|db| {
+                db.is_unique_violation()
+                    && db.constraint() == Some(PENDING_JOIN_REQUEST_UNIQUE_INDEX)
+            });
+            if is_pending {
+                return CoreError::JoinRequestAlreadyPending {
+                    server_id: input.server_id,
+                    user_id: input.user_id,
+                };
+            }
+            CoreError::DatabaseError {
+                msg: format!("Failed to insert join request: {}", e),
+            }
+        })?;
+
+        Ok(ServerJoinRequest::from(&row))
+    }
+
+    async fn find_by_id(&self, id: &ServerJoinRequestId) -> Result<ServerJoinRequest, CoreError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM server_join_requests WHERE id = $1",
+            JOIN_REQUEST_COLUMNS
+        ))
+        .bind(id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to find join request: {}", e),
+        })?;
+
+        row.as_ref()
+            .map(ServerJoinRequest::from)
+            .ok_or(CoreError::JoinRequestNotFound)
+    }
+
+    async fn find_latest(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+    ) -> Result<Option<ServerJoinRequest>, CoreError> {
+        let row = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_join_requests
+            WHERE server_id = $1 AND user_id = $2
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+            JOIN_REQUEST_COLUMNS
+        ))
+        .bind(server_id.0)
+        .bind(user_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to find join request: {}", e),
+        })?;
+
+        Ok(row.as_ref().map(ServerJoinRequest::from))
+    }
+
+    async fn list_by_server(
+        &self,
+        server_id: &ServerId,
+        status: JoinRequestStatus,
+        pagination: &GetPaginated,
+    ) -> Result<(Vec<ServerJoinRequest>, TotalPaginatedElements), CoreError> {
+        let offset = (pagination.page - 1) * pagination.limit;
+        let limit = std::cmp::min(pagination.limit, 50) as i64;
+
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM server_join_requests WHERE server_id = $1 AND status = $2",
+        )
+        .bind(server_id.0)
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to count join requests: {}", e),
+        })?;
+
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM server_join_requests
+            WHERE server_id = $1 AND status = $2
+            ORDER BY created_at ASC, id
+            LIMIT $3 OFFSET $4
+            "#,
+            JOIN_REQUEST_COLUMNS
+        ))
+        .bind(server_id.0)
+        .bind(status)
+        .bind(limit)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to list join requests: {}", e),
+        })?;
+
+        Ok((
+            rows.iter().map(ServerJoinRequest::from).collect(),
+            total as u64,
+        ))
+    }
+
+    async fn review(
+        &self,
+        id: &ServerJoinRequestId,
+        status: JoinRequestStatus,
+        reviewer_id: &UserId,
+    ) -> Result<ServerJoinRequest, CoreError> {
+        // Only a pending request can be decided, so concurrent reviews cannot both succeed
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE server_join_requests
+            SET status = $2, reviewer_id = $3
+            WHERE id = $1 AND status = 'pending'
+            RETURNING {}
+            "#,
+            JOIN_REQUEST_COLUMNS
+        ))
+        .bind(id.0)
+        .bind(status)
+        .bind(reviewer_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to review join request: {}", e),
+        })?;
+
+        match row {
+            Some(row) => Ok(ServerJoinRequest::from(&row)),
+            None => {
+                // Tell a missing request apart from one already reviewed
+                self.find_by_id(id).await?;
+                Err(CoreError::JoinRequestAlreadyReviewed { id: *id })
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
 mod postgres;
 
+pub(crate) use postgres::DISPLAY_COLOR;
 pub use postgres::PostgresMemberRepository;
@@ -1,17 +1,21 @@
+use chrono::{DateTime, Utc};
 use events_protobuf::communities_events::MemberAssignedToRole;
-use sqlx::{PgPool, query_as};
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::{
     domain::{
-        common::{CoreError, GetPaginated, TotalPaginatedElements},
+        common::{CoreError, Cursor, CursorPage, GetPaginated, TotalPaginatedElements, next_page},
         friend::entities::UserId,
-        member_role::entities::{AssignUserRole, MemberRole},
+        member_role::entities::AssignUserRole,
         role::entities::RoleId,
         server::entities::ServerId,
         server_member::{
             MemberId,
-            entities::{CreateMemberInput, DeleteMemberEvent, ServerMember, UpdateMemberInput},
+            entities::{
+                CreateMemberInput, DeleteMemberEvent, MemberFilter, MemberRemoval, MemberSortOrder,
+                MemberTimeoutEvent, ServerMember, UpdateMemberEvent, UpdateMemberInput,
+            },
             ports::MemberRepository,
         },
     },
@@ -24,6 +28,9 @@ pub struct PostgresMemberRepository {
     user_join_server_router: MessageRoutingInfo,
     delete_member_router: MessageRoutingInfo,
     assign_role_routing: MessageRoutingInfo,
+    member_timeout_router: MessageRoutingInfo,
+    member_remove_router: MessageRoutingInfo,
+    member_update_router: MessageRoutingInfo,
 }
 
 impl PostgresMemberRepository {
@@ -32,18 +39,32 @@ impl PostgresMemberRepository {
         delete_member_router: MessageRoutingInfo,
         user_join_server_router: MessageRoutingInfo,
         assign_role_routing: MessageRoutingInfo,
+        member_timeout_router: MessageRoutingInfo,
+        member_remove_router: MessageRoutingInfo,
+        member_update_router: MessageRoutingInfo,
     ) -> Self {
         Self {
             pool,
             delete_member_router,
             user_join_server_router,
             assign_role_routing,
+            member_timeout_router,
+            member_remove_router,
+            member_update_router,
         }
     }
 }
 
 impl MemberRepository for PostgresMemberRepository {
     async fn insert(&self, input: CreateMemberInput) -> Result<ServerMember, CoreError> {
+        self.insert_with_roles(input, &[]).await
+    }
+
+    async fn insert_with_roles(
+        &self,
+        input: CreateMemberInput,
+        role_ids: &[RoleId],
+    ) -> Result<ServerMember, CoreError> {
         let mut tx = self
             .pool
             .begin()
@@ -54,60 +75,54 @@ impl MemberRepository for PostgresMemberRepository {
         let member_id = Uuid::new_v4();
 
         // Insert the member into the database
-        let server_member = sqlx::query_as!(
-            ServerMember,
+        let row = sqlx::query(&format!(
             r#"
-            INSERT INTO server_members (id, server_id, user_id, nickname)
+            INSERT INTO server_members AS sm (id, server_id, user_id, nickname)
             VALUES ($1, $2, $3, $4)
-            RETURNING id, server_id, user_id, nickname, joined_at, updated_at
+            RETURNING id, server_id, user_id, nickname, joined_at, updated_at, timed_out_until,
+                profile,
+                {}
             "#,
-            member_id,
-            *input.server_id,
-            *input.user_id,
-            input.nickname,
-        )
+            DISPLAY_COLOR
+        ))
+        .bind(member_id)
+        .bind(input.server_id.0)
+        .bind(input.user_id.0)
+        .bind(&input.nickname)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to insert member: {}", e),
         })?;
+        let mut server_member = ServerMember::from(&row);
 
         let member_join_server =
             OutboxEventRecord::new(self.user_join_server_router.clone(), server_member.clone());
 
         member_join_server.write(&mut *tx).await?;
 
-        // Get the default role for this server (the role with ID = server_id, created when server was created)
-        // Or get any role if multiple exist - typically the "BasicUser" role
-        let default_role_id: Option<Uuid> = sqlx::query_scalar!(
-            r#"
-            SELECT id
-            FROM roles
-            WHERE server_id = $1
-            ORDER BY created_at ASC
-            LIMIT 1
-            "#,
-            *input.server_id
-        )
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| CoreError::DatabaseError {
-            msg: format!("Failed to fetch default role: {}", e),
-        })?;
+        // Get the default role for this server, created along with the server
+        let default_role_id: Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM roles WHERE server_id = $1 AND is_default")
+                .bind(*input.server_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| CoreError::DatabaseError {
+                    msg: format!("Failed to fetch default role: {}", e),
+                })?;
 
-        // Only assign role if one exists for this server
+        // The database already assigns the default role on insert, the event still has to go out
         if let Some(role_id) = default_role_id {
-            let _ = query_as!(
-                MemberRole,
+            sqlx::query(
                 r#"
                 INSERT INTO member_roles (role_id, member_id)
                 VALUES ($1, $2)
-                RETURNING role_id, member_id, created_at, updated_at
+                ON CONFLICT (member_id, role_id) DO NOTHING
                 "#,
-                role_id,
-                member_id
             )
-            .fetch_one(&mut *tx)
+            .bind(role_id)
+            .bind(member_id)
+            .execute(&mut *tx)
             .await
             .map_err(|_| CoreError::AssignMemberRoleError {
                 member_id: MemberId(member_id),
@@ -125,6 +140,53 @@ impl MemberRepository for PostgresMemberRepository {
             assign_member_to_role_event.write(&mut *tx).await?;
         }
 
+        // Roles deleted since they were picked no longer exist and are left out
+        let role_ids: Vec<Uuid> = role_ids.iter().map(|id| id.0).collect();
+        let assigned: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO member_roles (member_id, role_id)
+            SELECT $1, r.id
+            FROM roles r
+            WHERE r.id = ANY($2) AND r.server_id = $3 AND NOT r.is_default
+            ON CONFLICT (member_id, role_id) DO NOTHING
+            RETURNING role_id
+            "#,
+        )
+        .bind(member_id)
+        .bind(&role_ids)
+        .bind(*input.server_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to assign member roles: {}", e),
+        })?;
+
+        // The inserted row was returned before its roles, which may give it a color
+        if !assigned.is_empty() {
+            server_member.display_color = sqlx::query_scalar(&format!(
+                "SELECT {} FROM server_members sm WHERE sm.id = $1",
+                DISPLAY_COLOR
+            ))
+            .bind(member_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to fetch member color: {}", e),
+            })?;
+        }
+
+        for role_id in assigned {
+            OutboxEventRecord::new(
+                self.assign_role_routing.clone(),
+                AssignUserRole {
+                    user_id: input.user_id,
+                    role_id: RoleId(role_id),
+                },
+            )
+            .write(&mut *tx)
+            .await?;
+        }
+
         tx.commit().await.map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to commit transaction: {}", e),
         })?;
@@ -137,23 +199,26 @@ impl MemberRepository for PostgresMemberRepository {
         server_id: &ServerId,
         user_id: &UserId,
     ) -> Result<ServerMember, CoreError> {
-        let row = sqlx::query_as!(
-            ServerMember,
+        let row = sqlx::query(&format!(
             r#"
-            SELECT id, server_id, user_id, nickname, joined_at, updated_at
-            FROM server_members
+            SELECT id, server_id, user_id, nickname, joined_at, updated_at,
+                CASE WHEN timed_out_until > NOW() THEN timed_out_until END AS timed_out_until,
+                profile,
+                {}
+            FROM server_members sm
             WHERE server_id = $1 AND user_id = $2
             "#,
-            **server_id,
-            **user_id,
-        )
+            DISPLAY_COLOR
+        ))
+        .bind(server_id.0)
+        .bind(user_id.0)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to find member: {}", e),
         })?;
         match row {
-            Some(member) => Ok(member),
+            Some(row) => Ok(ServerMember::from(&row)),
             None => Err(CoreError::MemberNotFound {
                 server_id: *server_id,
                 user_id: *user_id,
@@ -165,31 +230,35 @@ impl MemberRepository for PostgresMemberRepository {
         &self,
         server_id: &ServerId,
         pagination: &GetPaginated,
+        filter: &MemberFilter,
     ) -> Result<(Vec<ServerMember>, TotalPaginatedElements), CoreError> {
         let offset = (pagination.page - 1) * pagination.limit;
         let limit = std::cmp::min(pagination.limit, 50) as i64;
+        let (nickname_pattern, user_ids, role_id) = filter_params(filter);
 
-        // Get total count of members for this server
-        let total: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM server_members WHERE server_id = $1")
-                .bind(server_id.0)
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|e| CoreError::DatabaseError {
-                    msg: format!("Failed to count members: {}", e),
-                })?;
+        // Get total count of matching members for this server
+        let total = self.count_filtered(server_id, filter).await?;
 
         // Get paginated members
-        let rows = sqlx::query(
+        let rows = sqlx::query(&format!(
             r#"
-            SELECT id, server_id, user_id, nickname, joined_at, updated_at
-            FROM server_members
-            WHERE server_id = $1
-            ORDER BY joined_at DESC
-            LIMIT $2 OFFSET $3
+            SELECT sm.id, sm.server_id, sm.user_id, sm.nickname, sm.joined_at, sm.updated_at,
+                CASE WHEN sm.timed_out_until > NOW() THEN sm.timed_out_until END AS timed_out_until,
+                sm.profile,
+                {}
+            FROM server_members sm
+            {}
+            ORDER BY {}
+            LIMIT $5 OFFSET $6
             "#,
-        )
+            DISPLAY_COLOR,
+            MEMBER_FILTER,
+            sort_order(filter.sort)
+        ))
         .bind(server_id.0)
+        .bind(&nickname_pattern)
+        .bind(&user_ids)
+        .bind(role_id)
         .bind(limit)
         .bind(offset as i64)
         .fetch_all(&self.pool)
@@ -200,7 +269,59 @@ impl MemberRepository for PostgresMemberRepository {
 
         let members: Vec<ServerMember> = rows.into_iter().map(|r| (&r).into()).collect();
 
-        Ok((members, total as u64))
+        Ok((members, total))
+    }
+
+    async fn list_by_server_after(
+        &self,
+        server_id: &ServerId,
+        after: Option<Cursor>,
+        limit: u32,
+        filter: &MemberFilter,
+    ) -> Result<CursorPage<ServerMember>, CoreError> {
+        let limit = std::cmp::min(limit, 50);
+        let (nickname_pattern, user_ids, role_id) = filter_params(filter);
+
+        let total = self.count_filtered(server_id, filter).await?;
+
+        // Row comparison follows the (joined_at, id) index in both directions
+        let keyset = match filter.sort {
+            MemberSortOrder::JoinedAtDesc => "(sm.joined_at, sm.id) < ($5, $6)",
+            MemberSortOrder::JoinedAtAsc => "(sm.joined_at, sm.id) > ($5, $6)",
+        };
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT sm.id, sm.server_id, sm.user_id, sm.nickname, sm.joined_at, sm.updated_at,
+                CASE WHEN sm.timed_out_until > NOW() THEN sm.timed_out_until END AS timed_out_until,
+                sm.profile,
+                {}
+            FROM server_members sm
+            {}
+              AND ($5::TIMESTAMPTZ IS NULL OR {})
+            ORDER BY {}
+            LIMIT $7
+            "#,
+            DISPLAY_COLOR,
+            MEMBER_FILTER,
+            keyset,
+            sort_order(filter.sort)
+        ))
+        .bind(server_id.0)
+        .bind(&nickname_pattern)
+        .bind(&user_ids)
+        .bind(role_id)
+        .bind(after.map(|cursor| cursor.at))
+        .bind(after.map(|cursor| cursor.id))
+        .bind(limit as i64 + 1)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to list members: {}", e),
+        })?;
+
+        let members: Vec<ServerMember> = rows.into_iter().map(|r| (&r).into()).collect();
+
+        Ok(next_page(members, limit, ServerMember::cursor, total))
     }
 
     async fn update(&self, input: UpdateMemberInput) -> Result<ServerMember, CoreError> {
@@ -213,17 +334,22 @@ impl MemberRepository for PostgresMemberRepository {
             })?;
 
         // Update the member and return the updated row
-        let row = sqlx::query(
+        let row = sqlx::query(&format!(
             r#"
-            UPDATE server_members
-            SET nickname = $1, updated_at = NOW()
+            UPDATE server_members sm
+            SET nickname = $1, profile = COALESCE($4, profile), updated_at = NOW()
             WHERE server_id = $2 AND user_id = $3
-            RETURNING id, server_id, user_id, nickname, joined_at, updated_at
+            RETURNING id, server_id, user_id, nickname, joined_at, updated_at,
+                CASE WHEN timed_out_until > NOW() THEN timed_out_until END AS timed_out_until,
+                profile,
+                {}
             "#,
-        )
+            DISPLAY_COLOR
+        ))
         .bind(&input.nickname)
         .bind(input.server_id.0)
         .bind(input.user_id.0)
+        .bind(input.profile.as_ref().map(sqlx::types::Json))
         .fetch_optional(&mut *tx)
         .await
         .map_err(|e| CoreError::DatabaseError {
@@ -237,6 +363,16 @@ impl MemberRepository for PostgresMemberRepository {
 
         let member: ServerMember = (&row).into();
 
+        let update_event = UpdateMemberEvent {
+            server_id: member.server_id,
+            user_id: member.user_id,
+            nickname: member.nickname.clone(),
+            profile: member.profile.clone(),
+        };
+        OutboxEventRecord::new(self.member_update_router.clone(), update_event)
+            .write(&mut *tx)
+            .await?;
+
         tx.commit().await.map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to commit transaction: {}", e),
         })?;
@@ -244,7 +380,12 @@ impl MemberRepository for PostgresMemberRepository {
         Ok(member)
     }
 
-    async fn delete(&self, server_id: &ServerId, user_id: &UserId) -> Result<(), CoreError> {
+    async fn delete(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        removal: MemberRemoval,
+    ) -> Result<(), CoreError> {
         let mut tx = self
             .pool
             .begin()
@@ -271,13 +412,8 @@ impl MemberRepository for PostgresMemberRepository {
             });
         }
 
-        // Write the delete event to the outbox table
-        let delete_event = DeleteMemberEvent {
-            server_id: *server_id,
-            user_id: *user_id,
-        };
-        let outbox_event = OutboxEventRecord::new(self.delete_member_router.clone(), delete_event);
-        outbox_event.write(&mut *tx).await?;
+        self.write_removal_events(&mut tx, *server_id, *user_id, removal)
+            .await?;
 
         tx.commit().await.map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to commit transaction: {}", e),
@@ -286,26 +422,313 @@ impl MemberRepository for PostgresMemberRepository {
         Ok(())
     }
 
+    async fn delete_many(
+        &self,
+        server_id: &ServerId,
+        user_ids: &[UserId],
+        removal: MemberRemoval,
+    ) -> Result<Vec<UserId>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let ids: Vec<Uuid> = user_ids.iter().map(|user_id| user_id.0).collect();
+        let removed: Vec<UserId> = sqlx::query_scalar::<_, Uuid>(
+            r#"DELETE FROM server_members WHERE server_id = $1 AND user_id = ANY($2) RETURNING user_id"#,
+        )
+        .bind(server_id.0)
+        .bind(&ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to delete members: {}", e),
+        })?
+        .into_iter()
+        .map(UserId)
+        .collect();
+
+        for user_id in &removed {
+            self.write_removal_events(&mut tx, *server_id, *user_id, removal.clone())
+                .await?;
+        }
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(removed)
+    }
+
+    async fn count_prunable(
+        &self,
+        server_id: &ServerId,
+        owner_id: &UserId,
+        joined_before: DateTime<Utc>,
+    ) -> Result<u64, CoreError> {
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM server_members sm {}",
+            PRUNABLE_FILTER
+        ))
+        .bind(server_id.0)
+        .bind(owner_id.0)
+        .bind(joined_before)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to count prunable members: {}", e),
+        })?;
+
+        Ok(total as u64)
+    }
+
+    async fn prune_batch(
+        &self,
+        server_id: &ServerId,
+        owner_id: &UserId,
+        joined_before: DateTime<Utc>,
+        limit: u32,
+        removal: MemberRemoval,
+    ) -> Result<Vec<UserId>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let removed: Vec<UserId> = sqlx::query_scalar::<_, Uuid>(&format!(
+            r#"
+            DELETE FROM server_members
+            WHERE id IN (
+                SELECT sm.id FROM server_members sm {}
+                ORDER BY sm.joined_at ASC
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING user_id
+            "#,
+            PRUNABLE_FILTER
+        ))
+        .bind(server_id.0)
+        .bind(owner_id.0)
+        .bind(joined_before)
+        .bind(limit as i64)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to prune members: {}", e),
+        })?
+        .into_iter()
+        .map(UserId)
+        .collect();
+
+        for user_id in &removed {
+            self.write_removal_events(&mut tx, *server_id, *user_id, removal.clone())
+                .await?;
+        }
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(removed)
+    }
+
     async fn find_by_id(&self, member_id: MemberId) -> Result<ServerMember, CoreError> {
-        let row = sqlx::query_as!(
-            ServerMember,
+        let row = sqlx::query(&format!(
             r#"
-            SELECT id, server_id, user_id, nickname, joined_at, updated_at
-            FROM server_members
-            WHERE id = $1 
+            SELECT id, server_id, user_id, nickname, joined_at, updated_at,
+                CASE WHEN timed_out_until > NOW() THEN timed_out_until END AS timed_out_until,
+                profile,
+                {}
+            FROM server_members sm
+            WHERE id = $1
             "#,
-            *member_id
-        )
+            DISPLAY_COLOR
+        ))
+        .bind(member_id.0)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| CoreError::DatabaseError {
             msg: format!("Failed to find member: {}", e),
         })?;
         match row {
-            Some(member) => Ok(member),
+            Some(row) => Ok(ServerMember::from(&row)),
             None => Err(CoreError::MemberNotFoundById { member_id }),
         }
     }
+
+    async fn set_timeout(
+        &self,
+        server_id: &ServerId,
+        user_id: &UserId,
+        moderator_id: &UserId,
+        timed_out_until: Option<DateTime<Utc>>,
+    ) -> Result<ServerMember, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| CoreError::DatabaseError {
+                msg: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        let row = sqlx::query(&format!(
+            r#"
+            UPDATE server_members sm
+            SET timed_out_until = $1, updated_at = NOW()
+            WHERE server_id = $2 AND user_id = $3
+            RETURNING id, server_id, user_id, nickname, joined_at, updated_at, timed_out_until,
+                profile,
+                {}
+            "#,
+            DISPLAY_COLOR
+        ))
+        .bind(timed_out_until)
+        .bind(server_id.0)
+        .bind(user_id.0)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to update member timeout: {}", e),
+        })?;
+
+        let row = row.ok_or_else(|| CoreError::MemberNotFound {
+            server_id: *server_id,
+            user_id: *user_id,
+        })?;
+        let member = ServerMember::from(&row);
+
+        let timeout_event = MemberTimeoutEvent {
+            server_id: *server_id,
+            user_id: *user_id,
+            moderator_id: *moderator_id,
+            timed_out_until,
+        };
+        OutboxEventRecord::new(self.member_timeout_router.clone(), timeout_event)
+            .write(&mut *tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(member)
+    }
+}
+
+impl PostgresMemberRepository {
+    /// Write the leave event to the outbox table, the removal details only
+    /// travel with the member removal event
+    async fn write_removal_events(
+        &self,
+        tx: &mut PgConnection,
+        server_id: ServerId,
+        user_id: UserId,
+        removal: MemberRemoval,
+    ) -> Result<(), CoreError> {
+        let delete_event = DeleteMemberEvent::new(server_id, user_id, removal);
+        OutboxEventRecord::new(self.delete_member_router.clone(), delete_event.clone())
+            .write(&mut *tx)
+            .await?;
+        OutboxEventRecord::new(self.member_remove_router.clone(), delete_event)
+            .write(&mut *tx)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn count_filtered(
+        &self,
+        server_id: &ServerId,
+        filter: &MemberFilter,
+    ) -> Result<TotalPaginatedElements, CoreError> {
+        let (nickname_pattern, user_ids, role_id) = filter_params(filter);
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM server_members sm {}",
+            MEMBER_FILTER
+        ))
+        .bind(server_id.0)
+        .bind(&nickname_pattern)
+        .bind(&user_ids)
+        .bind(role_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError {
+            msg: format!("Failed to count members: {}", e),
+        })?;
+
+        Ok(total as TotalPaginatedElements)
+    }
+}
+
+/// Member filter bound as $1 server, $2 nickname pattern, $3 user ids and $4 role
+const MEMBER_FILTER: &str = r#"
+    WHERE sm.server_id = $1
+      AND ($2::TEXT IS NULL OR LOWER(sm.nickname) LIKE $2 OR sm.user_id = ANY($3))
+      AND ($4::UUID IS NULL OR EXISTS (
+          SELECT 1 FROM member_roles mr
+          WHERE mr.member_id = sm.id
+            AND mr.role_id = $4
+            AND (mr.expires_at IS NULL OR mr.expires_at > NOW())
+      ))
+"#;
+
+/// Prunable members bound as $1 server, $2 owner and $3 join date limit.
+/// The default role shares the id of its server, expired assignments no longer protect a member
+const PRUNABLE_FILTER: &str = r#"
+    WHERE sm.server_id = $1
+      AND sm.user_id <> $2
+      AND sm.joined_at < $3
+      AND NOT EXISTS (
+          SELECT 1 FROM member_roles mr
+          WHERE mr.member_id = sm.id
+            AND mr.role_id <> sm.server_id
+            AND (mr.expires_at IS NULL OR mr.expires_at > NOW())
+      )
+"#;
+
+/// Color of the highest active role of the member aliased as `sm` that has one
+pub(crate) const DISPLAY_COLOR: &str = r#"(
+    SELECT r.color
+    FROM member_roles held
+    INNER JOIN roles r ON r.id = held.role_id
+    WHERE held.member_id = sm.id
+      AND r.color <> 0
+      AND (held.expires_at IS NULL OR held.expires_at > NOW())
+    ORDER BY r.position DESC
+    LIMIT 1
+) AS display_color"#;
+
+/// Nicknames match by case insensitive prefix, display name matches by user id
+fn filter_params(filter: &MemberFilter) -> (Option<String>, Vec<Uuid>, Option<Uuid>) {
+    let nickname_pattern = filter
+        .nickname_prefix
+        .as_ref()
+        .map(|prefix| format!("{}%", escape_like(&prefix.to_lowercase())));
+    let user_ids = filter.user_ids.iter().map(|user_id| user_id.0).collect();
+    let role_id = filter.role_id.map(|role_id| role_id.0);
+    (nickname_pattern, user_ids, role_id)
+}
+
+fn sort_order(sort: MemberSortOrder) -> &'static str {
+    match sort {
+        MemberSortOrder::JoinedAtDesc => "sm.joined_at DESC, sm.id DESC",
+        MemberSortOrder::JoinedAtAsc => "sm.joined_at ASC, sm.id ASC",
+    }
+}
+
+/// Escapes the LIKE wildcards of user input
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
 
 #[cfg(test)]
@@ -313,6 +736,7 @@ mod tests {
     use super::*;
     use crate::domain::friend::entities::UserId;
     use crate::domain::server::entities::{ServerId, ServerVisibility};
+    use crate::domain::server_member::entities::MemberProfile;
     use crate::infrastructure::outbox::MessageRouter;
     use sqlx::Row;
 
@@ -337,14 +761,14 @@ mod tests {
         // Create a default role for the server (mimicking what happens in real server creation)
         sqlx::query(
             r#"
-            INSERT INTO roles (id, server_id, name, permissions)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO roles (id, server_id, name, permissions, is_default)
+            VALUES ($1, $2, $3, $4, TRUE)
             "#,
         )
         .bind(server_id.0) // Role ID = Server ID (same as in real server creation)
         .bind(server_id.0)
         .bind("BasicUser")
-        .bind(0i32) // Basic permissions
+        .bind(0i64) // Basic permissions
         .execute(pool)
         .await
         .map_err(|e| CoreError::DatabaseError {
@@ -363,6 +787,9 @@ mod tests {
             delete_router,
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         let server_id = ServerId(Uuid::new_v4());
@@ -396,6 +823,123 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_insert_with_roles_skips_stale_roles(pool: PgPool) -> Result<(), CoreError> {
+        let repository = PostgresMemberRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
+        let server_id = ServerId(Uuid::new_v4());
+        create_test_server(&pool, server_id).await?;
+        let role_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO roles (id, server_id, name, permissions) VALUES ($1, $2, $3, 0)")
+            .bind(role_id)
+            .bind(server_id.0)
+            .bind("Newcomer")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Act: join with a live role and one deleted since it was picked
+        let member = repository
+            .insert_with_roles(
+                CreateMemberInput {
+                    server_id,
+                    user_id: UserId(Uuid::new_v4()),
+                    nickname: None,
+                },
+                &[RoleId(role_id), RoleId(Uuid::new_v4())],
+            )
+            .await?;
+
+        // Assert: the member holds the default role and the live one
+        let mut held: Vec<Uuid> =
+            sqlx::query_scalar("SELECT role_id FROM member_roles WHERE member_id = $1")
+                .bind(member.id.0)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        held.sort();
+        let mut expected = vec![server_id.0, role_id];
+        expected.sort();
+        assert_eq!(held, expected);
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_display_color_follows_the_highest_colored_role(
+        pool: PgPool,
+    ) -> Result<(), CoreError> {
+        let repository = PostgresMemberRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
+        let server_id = ServerId(Uuid::new_v4());
+        create_test_server(&pool, server_id).await?;
+        let user_id = UserId(Uuid::new_v4());
+        let member = repository
+            .insert(CreateMemberInput {
+                server_id,
+                user_id,
+                nickname: None,
+            })
+            .await?;
+        assert_eq!(member.display_color, None);
+
+        // Arrange: a low red role, an uncolored role above it and an expired blue role on top
+        for (name, position, color, expires_at) in [
+            ("Red", 1, 0xff0000, None),
+            ("Plain", 2, 0, None),
+            (
+                "Blue",
+                3,
+                0x0000ff,
+                Some(Utc::now() - chrono::Duration::hours(1)),
+            ),
+        ] {
+            let role_id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO roles (id, server_id, name, permissions, position, color) VALUES ($1, $2, $3, 0, $4, $5)",
+            )
+            .bind(role_id)
+            .bind(server_id.0)
+            .bind(name)
+            .bind(position)
+            .bind(color)
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO member_roles (member_id, role_id, expires_at) VALUES ($1, $2, $3)",
+            )
+            .bind(member.id.0)
+            .bind(role_id)
+            .bind(expires_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        // Assert: the highest active colored role gives the color
+        let member = repository
+            .find_by_server_and_user(&server_id, &user_id)
+            .await?;
+        assert_eq!(member.display_color, Some(0xff0000));
+
+        Ok(())
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_find_by_server_and_user_returns_member(pool: PgPool) -> Result<(), CoreError> {
         let repository = PostgresMemberRepository::new(
@@ -403,6 +947,9 @@ mod tests {
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         let server_id = ServerId(Uuid::new_v4());
@@ -443,6 +990,9 @@ mod tests {
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         // Try to find a member that doesn't exist
@@ -463,6 +1013,9 @@ mod tests {
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         let server_id = ServerId(Uuid::new_v4());
@@ -482,7 +1035,9 @@ mod tests {
 
         // Act: list members with pagination
         let pagination = GetPaginated { page: 1, limit: 3 };
-        let (members, total) = repository.list_by_server(&server_id, &pagination).await?;
+        let (members, total) = repository
+            .list_by_server(&server_id, &pagination, &MemberFilter::default())
+            .await?;
 
         // Assert: correct count and total
         assert_eq!(members.len(), 3);
@@ -496,6 +1051,155 @@ mod tests {
         Ok(())
     }
 
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_list_by_server_after_walks_all_members(pool: PgPool) -> Result<(), CoreError> {
+        let repository = PostgresMemberRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
+
+        let server_id = ServerId(Uuid::new_v4());
+        create_test_server(&pool, server_id).await?;
+        for i in 0..5 {
+            let input = CreateMemberInput {
+                server_id,
+                user_id: UserId(Uuid::new_v4()),
+                nickname: Some(format!("Member{}", i)),
+            };
+            repository.insert(input).await?;
+        }
+
+        let filter = MemberFilter::default();
+        let (first, total, cursor) = repository
+            .list_by_server_after(&server_id, None, 3, &filter)
+            .await?;
+        assert_eq!(first.len(), 3);
+        assert_eq!(total, 5);
+        assert_eq!(cursor, Some(first[2].cursor()));
+
+        let (second, _, cursor) = repository
+            .list_by_server_after(&server_id, cursor, 3, &filter)
+            .await?;
+        assert_eq!(second.len(), 2);
+        assert_eq!(cursor, None);
+        assert!(first.iter().all(|m| !second.iter().any(|s| s.id == m.id)));
+        assert!(first[2].cursor() > second[0].cursor());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_prune_removes_old_members_with_only_the_default_role(
+        pool: PgPool,
+    ) -> Result<(), CoreError> {
+        let repository = PostgresMemberRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
+
+        let server_id = ServerId(Uuid::new_v4());
+        create_test_server(&pool, server_id).await?;
+        let owner_id: Uuid = sqlx::query_scalar("SELECT owner_id FROM servers WHERE id = $1")
+            .bind(server_id.0)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        let mut members = Vec::new();
+        for user_id in [
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            owner_id,
+            Uuid::new_v4(),
+        ] {
+            let input = CreateMemberInput {
+                server_id,
+                user_id: UserId(user_id),
+                nickname: None,
+            };
+            members.push(repository.insert(input).await?);
+        }
+        let (inactive, with_role, recent, owner, with_expired_role) = (
+            &members[0],
+            &members[1],
+            &members[2],
+            &members[3],
+            &members[4],
+        );
+
+        for member in [inactive, with_role, owner, with_expired_role] {
+            sqlx::query(
+                "UPDATE server_members SET joined_at = NOW() - INTERVAL '60 days' WHERE id = $1",
+            )
+            .bind(member.id.0)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let role_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO roles (id, server_id, name, permissions) VALUES ($1, $2, $3, 0)")
+            .bind(role_id)
+            .bind(server_id.0)
+            .bind("Regular")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO member_roles (role_id, member_id) VALUES ($1, $2)")
+            .bind(role_id)
+            .bind(with_role.id.0)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO member_roles (role_id, member_id, expires_at) VALUES ($1, $2, NOW() - INTERVAL '1 day')",
+        )
+        .bind(role_id)
+        .bind(with_expired_role.id.0)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let joined_before = Utc::now() - chrono::Duration::days(30);
+        let owner_id = UserId(owner_id);
+        let count = repository
+            .count_prunable(&server_id, &owner_id, joined_before)
+            .await?;
+        assert_eq!(count, 2);
+
+        let mut removed = repository
+            .prune_batch(
+                &server_id,
+                &owner_id,
+                joined_before,
+                10,
+                MemberRemoval::prune(owner_id, 30),
+            )
+            .await?;
+        removed.sort_by_key(|user_id| user_id.0);
+        let mut expected = vec![inactive.user_id, with_expired_role.user_id];
+        expected.sort_by_key(|user_id| user_id.0);
+        assert_eq!(removed, expected);
+
+        for member in [with_role, recent, owner] {
+            repository
+                .find_by_server_and_user(&server_id, &member.user_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
     #[sqlx::test(migrations = "./migrations")]
     async fn test_update_member_updates_fields(pool: PgPool) -> Result<(), CoreError> {
         let repository = PostgresMemberRepository::new(
@@ -503,6 +1207,9 @@ mod tests {
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         let server_id = ServerId(Uuid::new_v4());
@@ -524,6 +1231,7 @@ mod tests {
             server_id,
             user_id,
             nickname: Some("NewNick".to_string()),
+            profile: None,
         };
         let updated = repository.update(update_input.clone()).await?;
 
@@ -549,6 +1257,9 @@ mod tests {
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         // Try to update a member that doesn't exist
@@ -558,6 +1269,7 @@ mod tests {
             server_id: nonexistent_server,
             user_id: nonexistent_user,
             nickname: Some("NewNick".to_string()),
+            profile: None,
         };
 
         let result = repository.update(update_input).await;
@@ -583,6 +1295,9 @@ mod tests {
             delete_router.clone(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         let server_id = ServerId(Uuid::new_v4());
@@ -600,7 +1315,9 @@ mod tests {
         repository.insert(input).await?;
 
         // Act: delete the member
-        repository.delete(&server_id, &user_id).await?;
+        repository
+            .delete(&server_id, &user_id, MemberRemoval::leave(user_id))
+            .await?;
 
         // Assert: member is gone
         let fetched = repository
@@ -640,6 +1357,11 @@ mod tests {
             payload.get("user_id").and_then(|v| v.as_str()),
             Some(user_str.as_str())
         );
+        assert_eq!(payload.get("kind").and_then(|v| v.as_str()), Some("leave"));
+        assert_eq!(
+            payload.get("actor_id").and_then(|v| v.as_str()),
+            Some(user_str.as_str())
+        );
 
         Ok(())
     }
@@ -651,13 +1373,20 @@ mod tests {
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
             MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
         );
 
         // Try to delete a member that doesn't exist
         let nonexistent_server = ServerId(Uuid::new_v4());
         let nonexistent_user = UserId(Uuid::new_v4());
         let result = repository
-            .delete(&nonexistent_server, &nonexistent_user)
+            .delete(
+                &nonexistent_server,
+                &nonexistent_user,
+                MemberRemoval::leave(nonexistent_user),
+            )
             .await;
 
         // Assert: should return MemberNotFound error
@@ -672,4 +1401,145 @@ mod tests {
 
         Ok(())
     }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_set_timeout_expires_lazily_and_writes_outbox(
+        pool: PgPool,
+    ) -> Result<(), CoreError> {
+        let timeout_router = MessageRoutingInfo::new("member.timeout");
+        let repository = PostgresMemberRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            timeout_router.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+        );
+
+        let server_id = ServerId(Uuid::new_v4());
+        let user_id = UserId(Uuid::new_v4());
+        let moderator_id = UserId(Uuid::new_v4());
+        create_test_server(&pool, server_id).await?;
+        repository
+            .insert(CreateMemberInput {
+                server_id,
+                user_id,
+                nickname: None,
+            })
+            .await?;
+
+        // Act: time the member out for an hour
+        let until = Utc::now() + chrono::Duration::hours(1);
+        let member = repository
+            .set_timeout(&server_id, &user_id, &moderator_id, Some(until))
+            .await?;
+        assert!(member.is_timed_out());
+
+        let fetched = repository
+            .find_by_server_and_user(&server_id, &user_id)
+            .await?;
+        assert!(fetched.timed_out_until.is_some());
+
+        // Assert: an elapsed timeout is no longer returned
+        sqlx::query(
+            "UPDATE server_members SET timed_out_until = NOW() - INTERVAL '1 minute' WHERE id = $1",
+        )
+        .bind(member.id.0)
+        .execute(&pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        let fetched = repository
+            .find_by_server_and_user(&server_id, &user_id)
+            .await?;
+        assert!(fetched.timed_out_until.is_none());
+
+        // Assert: the timeout was published
+        let payload: serde_json::Value = sqlx::query_scalar(
+            "SELECT payload FROM outbox_messages WHERE exchange_name = $1 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(timeout_router.exchange_name())
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        let moderator_str = moderator_id.0.to_string();
+        assert_eq!(
+            payload.get("moderator_id").and_then(|v| v.as_str()),
+            Some(moderator_str.as_str())
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn test_update_profile_persists_and_writes_outbox(pool: PgPool) -> Result<(), CoreError> {
+        let update_router = MessageRoutingInfo::new("member.update");
+        let repository = PostgresMemberRepository::new(
+            pool.clone(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            MessageRoutingInfo::default(),
+            update_router.clone(),
+        );
+
+        let server_id = ServerId(Uuid::new_v4());
+        let user_id = UserId(Uuid::new_v4());
+        create_test_server(&pool, server_id).await?;
+        let member = repository
+            .insert(CreateMemberInput {
+                server_id,
+                user_id,
+                nickname: None,
+            })
+            .await?;
+        assert_eq!(member.profile, MemberProfile::default());
+
+        let profile = MemberProfile {
+            avatar_url: None,
+            bio: Some("Hello".to_string()),
+            pronouns: Some("they/them".to_string()),
+        };
+        repository
+            .update(UpdateMemberInput {
+                server_id,
+                user_id,
+                nickname: None,
+                profile: Some(profile.clone()),
+            })
+            .await?;
+
+        // Assert: a nickname only update keeps the profile
+        let updated = repository
+            .update(UpdateMemberInput {
+                server_id,
+                user_id,
+                nickname: Some("Nick".to_string()),
+                profile: None,
+            })
+            .await?;
+        assert_eq!(updated.profile, profile);
+
+        // Assert: the update was published with the profile
+        let payload: serde_json::Value = sqlx::query_scalar(
+            "SELECT payload FROM outbox_messages WHERE exchange_name = $1 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(update_router.exchange_name())
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| CoreError::DatabaseError { msg: e.to_string() })?;
+        assert_eq!(
+            payload
+                .pointer("/profile/pronouns")
+                .and_then(|v| v.as_str()),
+            Some("they/them")
+        );
+        assert_eq!(
+            payload.get("nickname").and_then(|v| v.as_str()),
+            Some("Nick")
+        );
+
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
 use futures_util::future::join_all;
 use reqwest::{Client, Url};
 use tracing::{debug, error};
+use uuid::Uuid;
 
 use crate::{
     domain::{
         common::CoreError,
+        role::entities::RoleId,
         server::entities::ServerId,
         server_pictures::{
             Content, ContentVerb, PresignedUrl, ServerPictureUrls, ServerPicturesMap,
@@ -32,7 +34,7 @@ impl ReqwestServerPicturesRepository {
 impl ServerPicturesRepository for ReqwestServerPicturesRepository {
     async fn get_signed_url(
         &self,
-        server_id: ServerId,
+        resource_id: Uuid,
         content: Content,
         verb: ContentVerb,
     ) -> Result<PresignedUrl, CoreError> {
@@ -49,9 +51,9 @@ impl ServerPicturesRepository for ReqwestServerPicturesRepository {
         })?;
 
         let url = url_with_prefix
-            .join(server_id.to_string().as_str())
+            .join(resource_id.to_string().as_str())
             .map_err(|_| CoreError::ParseContentUrl {
-                part: server_id.to_string(),
+                part: resource_id.to_string(),
             })?;
 
         debug!("Fetch presigned url: {}", url);
@@ -77,22 +79,22 @@ impl ServerPicturesRepository for ReqwestServerPicturesRepository {
     }
 
     async fn put_banner(&self, server_id: ServerId) -> Result<PresignedUrl, CoreError> {
-        self.get_signed_url(server_id, Content::ServerBanner, ContentVerb::Put)
+        self.get_signed_url(*server_id, Content::ServerBanner, ContentVerb::Put)
             .await
     }
 
     async fn get_banner(&self, server_id: ServerId) -> Result<PresignedUrl, CoreError> {
-        self.get_signed_url(server_id, Content::ServerBanner, ContentVerb::Get)
+        self.get_signed_url(*server_id, Content::ServerBanner, ContentVerb::Get)
             .await
     }
 
     async fn get_picture(&self, server_id: ServerId) -> Result<PresignedUrl, CoreError> {
-        self.get_signed_url(server_id, Content::ServerPicture, ContentVerb::Get)
+        self.get_signed_url(*server_id, Content::ServerPicture, ContentVerb::Get)
             .await
     }
 
     async fn put_picture(&self, server_id: ServerId) -> Result<PresignedUrl, CoreError> {
-        self.get_signed_url(server_id, Content::ServerPicture, ContentVerb::Put)
+        self.get_signed_url(*server_id, Content::ServerPicture, ContentVerb::Put)
             .await
     }
 
@@ -123,4 +125,14 @@ impl ServerPicturesRepository for ReqwestServerPicturesRepository {
         });
         join_all(futures).await.into_iter().flatten().collect()
     }
+
+    async fn put_role_icon(&self, role_id: RoleId) -> Result<PresignedUrl, CoreError> {
+        self.get_signed_url(*role_id, Content::RoleIcon, ContentVerb::Put)
+            .await
+    }
+
+    async fn get_role_icon(&self, role_id: RoleId) -> Result<PresignedUrl, CoreError> {
+        self.get_signed_url(*role_id, Content::RoleIcon, ContentVerb::Get)
+            .await
+    }
 }
@@ -4,12 +4,15 @@ use thiserror::Error;
 pub enum UserError {
     #[error("User not found")]
     UserNotFound,
+    #[error("User service is unavailable: {msg}")]
+    ServiceUnavailable { msg: String },
 }
 
 impl UserError {
     pub fn error_code(&self) -> &'static str {
         match self {
             UserError::UserNotFound => "E_USER_NOT_FOUND",
+            UserError::ServiceUnavailable { .. } => "E_USER_SERVICE_UNAVAILABLE",
         }
     }
 }
@@ -1,17 +1,32 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use reqwest::{self, StatusCode};
+use uuid::Uuid;
 
 use crate::{
-    domain::user::{entities::User, port::UserRepository},
+    domain::user::{
+        entities::{GetUsersByIds, User},
+        port::UserRepository,
+    },
     infrastructure::user::repositories::error::UserError,
 };
 
 use tracing::{error, info};
 use urlencoding::encode;
 
+/// How long a fetched user is served from memory before asking the User service again
+const USER_CACHE_TTL: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone)]
 pub struct HttpUserRepository {
     base_url: String,
     client: reqwest::Client,
+    cache: Arc<Mutex<HashMap<Uuid, (Instant, User)>>>,
+    cache_ttl: Duration,
 }
 
 impl HttpUserRepository {
@@ -19,6 +34,34 @@ impl HttpUserRepository {
         Self {
             base_url,
             client: reqwest::Client::new(),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            cache_ttl: USER_CACHE_TTL,
+        }
+    }
+
+    /// Split the requested ids into the users still cached and the ids to fetch
+    fn cached_users(&self, user_ids: &[Uuid]) -> (Vec<User>, Vec<Uuid>) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        cache.retain(|_, (fetched_at, _)| now.duration_since(*fetched_at) < self.cache_ttl);
+
+        let mut users = Vec::new();
+        let mut missing = Vec::new();
+        for user_id in user_ids {
+            match cache.get(user_id) {
+                Some((_, user)) => users.push(user.clone()),
+                None if !missing.contains(user_id) => missing.push(*user_id),
+                None => {}
+            }
+        }
+        (users, missing)
+    }
+
+    fn cache_users(&self, users: &[User]) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        for user in users {
+            cache.insert(user.sub, (now, user.clone()));
         }
     }
 }
@@ -67,4 +110,95 @@ impl UserRepository for HttpUserRepository {
 
         Ok(user)
     }
+
+    async fn get_users_by_ids(&self, user_ids: &[Uuid]) -> Result<Vec<User>, UserError> {
+        let (mut users, missing) = self.cached_users(user_ids);
+        if missing.is_empty() {
+            return Ok(users);
+        }
+
+        info!("Fetching {} users by id", missing.len());
+        let res = match self
+            .client
+            .post(format!("{}/users/batch", self.base_url))
+            .json(&GetUsersByIds { user_ids: missing })
+            .send()
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                error!("An error occurred with the User service: {}", e);
+                return Err(UserError::ServiceUnavailable { msg: e.to_string() });
+            }
+        };
+
+        // Unknown ids are left out of a successful response, a 404 means none of them exist
+        match res.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Err(UserError::UserNotFound),
+            status => {
+                error!(
+                    "User service returned an unexpected status code: {}",
+                    status
+                );
+                return Err(UserError::ServiceUnavailable {
+                    msg: format!("unexpected status code {}", status),
+                });
+            }
+        }
+
+        let fetched = match res.json::<Vec<User>>().await {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                error!("Failed to deserialize users: {}", e);
+                return Err(UserError::ServiceUnavailable { msg: e.to_string() });
+            }
+        };
+
+        self.cache_users(&fetched);
+        users.extend(fetched);
+
+        Ok(users)
+    }
+
+    async fn search_users_by_display_name(&self, prefix: &str) -> Result<Vec<User>, UserError> {
+        info!("Searching users by display name prefix: {}", prefix);
+        let res = match self
+            .client
+            .get(format!(
+                "{}/users/search?display_name={}",
+                self.base_url,
+                encode(prefix)
+            ))
+            .send()
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                error!("An error occurred with the User service: {}", e);
+                return Err(UserError::ServiceUnavailable { msg: e.to_string() });
+            }
+        };
+
+        if res.status() != StatusCode::OK {
+            error!(
+                "User service returned an unexpected status code: {}",
+                res.status()
+            );
+            return Err(UserError::ServiceUnavailable {
+                msg: format!("unexpected status code {}", res.status()),
+            });
+        }
+
+        let users = match res.json::<Vec<User>>().await {
+            Ok(users) => users,
+            Err(e) => {
+                error!("Failed to deserialize users: {}", e);
+                return Err(UserError::ServiceUnavailable { msg: e.to_string() });
+            }
+        };
+
+        self.cache_users(&users);
+        Ok(users)
+    }
 }
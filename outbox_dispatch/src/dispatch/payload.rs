@@ -1,12 +1,19 @@
 use communities_core::{
     application::Routing,
     domain::{
-        channel::entities::{DeleteChannelEvent, ServerChannelCreation},
+        channel::entities::{
+            DeleteChannelEvent, DeletePermissionOverwriteEvent, PermissionOverwrite,
+            ServerChannelCreation,
+        },
         member_role::entities::{AssignUserRole, MemberRole, UnassignUserRole},
         outbox::entities::OutboxMessage,
-        role::entities::{DeleteRole, Role},
+        role::entities::{DeleteRole, Role, RoleAppearanceEvent},
         server::entities::{DeleteServerEvent, Server},
-        server_member::{ServerMember, entities::DeleteMemberEvent},
+        server_ban::entities::{MemberBannedEvent, MemberUnbannedEvent},
+        server_member::{
+            ServerMember,
+            entities::{DeleteMemberEvent, MemberTimeoutEvent, UpdateMemberEvent},
+        },
     },
 };
 use events_protobuf::communities_events::{
@@ -14,7 +21,7 @@ use events_protobuf::communities_events::{
     MemberRemovedFromRole, UpsertRole, UserJoinServer, UserLeaveServer,
 };
 use prost::Message;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{dispatch::DispatcherError, lapin::ExchangeName};
 
@@ -28,8 +35,16 @@ pub enum ExchangePayload {
     UserLeaveServer(ProcessedEvent<UserLeaveServer, DeleteMemberEvent>),
     UpsertRole(ProcessedEvent<UpsertRole, Role>),
     DeleteRole(ProcessedEvent<communities_events::DeleteRole, DeleteRole>),
+    RoleAppearance(JsonEvent<RoleAppearanceEvent>),
     MemberAssignToRole(ProcessedEvent<MemberAssignedToRole, AssignUserRole>),
     MemberUnassignFromRole(ProcessedEvent<MemberRemovedFromRole, UnassignUserRole>),
+    MemberBan(JsonEvent<MemberBannedEvent>),
+    MemberUnban(JsonEvent<MemberUnbannedEvent>),
+    MemberTimeout(JsonEvent<MemberTimeoutEvent>),
+    MemberRemove(JsonEvent<DeleteMemberEvent>),
+    MemberUpdate(JsonEvent<UpdateMemberEvent>),
+    UpsertChannelOverwrite(JsonEvent<PermissionOverwrite>),
+    DeleteChannelOverwrite(JsonEvent<DeletePermissionOverwriteEvent>),
 }
 
 impl TryFrom<(OutboxMessage, Routing)> for ExchangePayload {
@@ -47,6 +62,7 @@ impl TryFrom<(OutboxMessage, Routing)> for ExchangePayload {
             }
             Routing::UpsertRole => ExchangePayload::UpsertRole(ProcessedEvent::new(outbox)?),
             Routing::DeleteRole => ExchangePayload::DeleteRole(ProcessedEvent::new(outbox)?),
+            Routing::RoleAppearance => ExchangePayload::RoleAppearance(JsonEvent::new(outbox)?),
             Routing::MemberAssignToRole => {
                 ExchangePayload::MemberAssignToRole(ProcessedEvent::new(outbox)?)
             }
@@ -55,6 +71,17 @@ impl TryFrom<(OutboxMessage, Routing)> for ExchangePayload {
             }
             Routing::CreateChannel => ExchangePayload::CreateChannel(ProcessedEvent::new(outbox)?),
             Routing::DeleteChannel => ExchangePayload::DeleteChannel(ProcessedEvent::new(outbox)?),
+            Routing::MemberBan => ExchangePayload::MemberBan(JsonEvent::new(outbox)?),
+            Routing::MemberUnban => ExchangePayload::MemberUnban(JsonEvent::new(outbox)?),
+            Routing::MemberTimeout => ExchangePayload::MemberTimeout(JsonEvent::new(outbox)?),
+            Routing::MemberRemove => ExchangePayload::MemberRemove(JsonEvent::new(outbox)?),
+            Routing::MemberUpdate => ExchangePayload::MemberUpdate(JsonEvent::new(outbox)?),
+            Routing::UpsertChannelOverwrite => {
+                ExchangePayload::UpsertChannelOverwrite(JsonEvent::new(outbox)?)
+            }
+            Routing::DeleteChannelOverwrite => {
+                ExchangePayload::DeleteChannelOverwrite(JsonEvent::new(outbox)?)
+            }
         };
         Ok(payload)
     }
@@ -69,10 +96,18 @@ impl ExchangePayload {
             ExchangePayload::UserLeaveServer(event) => &event.2,
             ExchangePayload::UpsertRole(event) => &event.2,
             ExchangePayload::DeleteRole(event) => &event.2,
+            ExchangePayload::RoleAppearance(event) => &event.1,
             ExchangePayload::MemberAssignToRole(event) => &event.2,
             ExchangePayload::MemberUnassignFromRole(event) => &event.2,
             ExchangePayload::CreateChannel(event) => &event.2,
             ExchangePayload::DeleteChannel(event) => &event.2,
+            ExchangePayload::MemberBan(event) => &event.1,
+            ExchangePayload::MemberUnban(event) => &event.1,
+            ExchangePayload::MemberTimeout(event) => &event.1,
+            ExchangePayload::MemberRemove(event) => &event.1,
+            ExchangePayload::MemberUpdate(event) => &event.1,
+            ExchangePayload::UpsertChannelOverwrite(event) => &event.1,
+            ExchangePayload::DeleteChannelOverwrite(event) => &event.1,
         }
     }
 
@@ -84,10 +119,18 @@ impl ExchangePayload {
             ExchangePayload::UserLeaveServer(event) => event.0.encode_to_vec(),
             ExchangePayload::UpsertRole(event) => event.0.encode_to_vec(),
             ExchangePayload::DeleteRole(event) => event.0.encode_to_vec(),
+            ExchangePayload::RoleAppearance(event) => event.encode_json(),
             ExchangePayload::MemberAssignToRole(event) => event.0.encode_to_vec(),
             ExchangePayload::MemberUnassignFromRole(event) => event.0.encode_to_vec(),
             ExchangePayload::CreateChannel(event) => event.0.encode_to_vec(),
             ExchangePayload::DeleteChannel(event) => event.0.encode_to_vec(),
+            ExchangePayload::MemberBan(event) => event.encode_json(),
+            ExchangePayload::MemberUnban(event) => event.encode_json(),
+            ExchangePayload::MemberTimeout(event) => event.encode_json(),
+            ExchangePayload::MemberRemove(event) => event.encode_json(),
+            ExchangePayload::MemberUpdate(event) => event.encode_json(),
+            ExchangePayload::UpsertChannelOverwrite(event) => event.encode_json(),
+            ExchangePayload::DeleteChannelOverwrite(event) => event.encode_json(),
         }
     }
 
@@ -131,3 +174,34 @@ where
         &self.2
     }
 }
+
+/// Event without a protobuf definition yet, published as JSON.
+#[derive(Debug)]
+pub struct JsonEvent<TOutboxPayload: for<'a> Deserialize<'a> + Serialize>(
+    TOutboxPayload,
+    ExchangeName,
+);
+
+impl<TOutboxPayload> JsonEvent<TOutboxPayload>
+where
+    TOutboxPayload: for<'a> Deserialize<'a> + Serialize,
+{
+    pub fn new(outbox_event: OutboxMessage) -> Result<Self, DispatcherError> {
+        let payload = outbox_event
+            .payload::<TOutboxPayload>()
+            .map_err(|e| DispatcherError::WrongPayloadError { msg: e.to_string() })?;
+        Ok(Self(payload, outbox_event.exchange_name))
+    }
+
+    pub fn encode_json(&self) -> Vec<u8> {
+        serde_json::to_vec(&self.0).unwrap_or_default()
+    }
+
+    pub fn payload(&self) -> &TOutboxPayload {
+        &self.0
+    }
+
+    pub fn exchange_name(&self) -> &ExchangeName {
+        &self.1
+    }
+}